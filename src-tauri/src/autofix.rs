@@ -0,0 +1,287 @@
+//! Frontmatter auto-fix
+//!
+//! Repairs what the validator and the indexer trip over: values whose quoting breaks YAML
+//! parsing, `created`/`modified` in mixed date formats, missing `id`/`state`, out-of-range
+//! `maturity` and miscased enum values. Edits go through `yaml_edit`, so lines that need no
+//! fix keep their text.
+
+use crate::frontmatter::schemas::VaultSchemas;
+use crate::frontmatter::types::Frontmatter;
+use crate::frontmatter::workflow::{CONFIDENCE_STATES, WORKFLOW_STATES};
+use crate::frontmatter::{yaml_edit, FrontmatterParser};
+use crate::lint::NOTE_TYPES;
+use crate::search::parser::split_frontmatter_raw;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone};
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+
+/// Format `created`/`modified` are normalized to unless the caller picks another
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%:z";
+
+const RELATION_TYPES: [&str; 8] = [
+    "supports", "refutes", "extends", "implements", "derives-from", "part-of", "is-example-of", "causes",
+];
+
+const NAIVE_DATETIME_FORMATS: [&str; 5] =
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M", "%Y%m%d%H%M%S"];
+const OFFSET_DATETIME_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S%:z", "%Y-%m-%d %H:%M:%S %z"];
+
+/// Fixes applied to one note
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NoteFix {
+    pub path: String,
+    /// One line per fix ("created: 2026/01/05 -> 2026-01-05T00:00:00+09:00")
+    pub fixes: Vec<String>,
+    /// Validator errors still present after fixing (need a manual edit)
+    pub remaining: Vec<String>,
+}
+
+/// Reject date formats that are not ISO-like, i.e. whose output this module cannot read back
+pub fn validate_date_format(format: &str) -> Result<(), String> {
+    use chrono::format::{Item, StrftimeItems};
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(format!("Invalid date format: {}", format));
+    }
+    let sample = Local
+        .with_ymd_and_hms(2026, 1, 31, 13, 45, 30)
+        .earliest()
+        .ok_or("Local time zone has no sample date")?
+        .fixed_offset();
+    let formatted = sample.format(format).to_string();
+    match parse_datetime(&formatted) {
+        Some(parsed) if parsed.format(format).to_string() == formatted => Ok(()),
+        _ => Err(format!("Date format is not an ISO date format: {}", format)),
+    }
+}
+
+/// Dates as notes write them: RFC 3339, with a space instead of `T`, without seconds or
+/// offset (local time), date only, or with `/` or `.` between date parts
+pub fn parse_datetime(value: &str) -> Option<DateTime<FixedOffset>> {
+    let value = value.trim();
+    let value = match value.get(..10) {
+        Some(date) => format!("{}{}", date.replace(['/', '.'], "-"), &value[10..]),
+        None => value.to_string(),
+    };
+    if let Ok(dt) = DateTime::parse_from_rfc3339(&value) {
+        return Some(dt);
+    }
+    if let Some(dt) = OFFSET_DATETIME_FORMATS.iter().find_map(|f| DateTime::parse_from_str(&value, f).ok()) {
+        return Some(dt);
+    }
+    let naive = NAIVE_DATETIME_FORMATS
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(&value, f).ok())
+        .or_else(|| NaiveDate::parse_from_str(&value, "%Y-%m-%d").ok().and_then(|d| d.and_hms_opt(0, 0, 0)))?;
+    Local.from_local_datetime(&naive).earliest().map(|dt| dt.fixed_offset())
+}
+
+/// Canonical spelling of `value` among `allowed`, ignoring case and separators
+/// ("In_Progress" -> "in-progress", "note" -> "NOTE")
+fn canonical(value: &str, allowed: &[&'static str]) -> Option<&'static str> {
+    let key = |s: &str| s.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+    let value = key(value);
+    allowed.iter().find(|a| key(a) == value).copied()
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other).map(|s| s.trim_end().to_string()).unwrap_or_default(),
+    }
+}
+
+/// Double-quote values that break parsing (`title: Q&A: 정리`, `title: 'it's'`, `@home`)
+fn fix_quoting(yaml: &str, fixes: &mut Vec<String>) -> String {
+    let lines: Vec<String> = yaml
+        .split('\n')
+        .map(|line| {
+            let indent = line.len() - line.trim_start().len();
+            let text = &line[indent..];
+            let (prefix, item) = match text.strip_prefix("- ") {
+                Some(item) => ("- ", item),
+                None => ("", text),
+            };
+            let (lead, value) = match item.find(": ") {
+                Some(colon) if !item.starts_with(['"', '\'']) => (&item[..colon + 2], &item[colon + 2..]),
+                _ if !prefix.is_empty() => ("", item),
+                _ => return line.to_string(),
+            };
+            let value = value.trim_end_matches('\r');
+            let trimmed = value.trim();
+            let multi_line_flow = (trimmed.starts_with('[') && !trimmed.ends_with(']'))
+                || (trimmed.starts_with('{') && !trimmed.ends_with('}'));
+            if trimmed.is_empty() || trimmed.starts_with('#') || multi_line_flow {
+                return line.to_string();
+            }
+            if serde_yaml::from_str::<Value>(&format!("k: {}", trimmed)).is_ok() {
+                return line.to_string();
+            }
+            let inner = trimmed
+                .strip_prefix(['"', '\''])
+                .unwrap_or(trimmed)
+                .strip_suffix(['"', '\''])
+                .unwrap_or_else(|| trimmed.strip_prefix(['"', '\'']).unwrap_or(trimmed));
+            let quoted = format!("\"{}\"", inner.replace('\\', "\\\\").replace('"', "\\\""));
+            fixes.push(format!("quoted value: {} -> {}", trimmed, quoted));
+            let cr = if line.ends_with('\r') { "\r" } else { "" };
+            format!("{}{}{}{}{}", &line[..indent], prefix, lead, quoted, cr)
+        })
+        .collect();
+    lines.join("\n")
+}
+
+fn fix_date(fm: &mut Mapping, key: &str, date_format: &str, fixes: &mut Vec<String>) -> Option<DateTime<FixedOffset>> {
+    let raw = fm.get(key).map(display)?;
+    let parsed = parse_datetime(&raw)?;
+    let normalized = parsed.format(date_format).to_string();
+    if normalized != raw {
+        fixes.push(format!("{}: {} -> {}", key, raw, normalized));
+        fm.insert(Value::from(key), Value::String(normalized));
+    }
+    Some(parsed)
+}
+
+fn fix_enum(map: &mut Mapping, key: &str, allowed: &[&'static str], label: &str, fixes: &mut Vec<String>) {
+    let Some(current) = map.get(key).and_then(Value::as_str).map(str::to_string) else { return };
+    if let Some(fixed) = canonical(&current, allowed).filter(|fixed| *fixed != current) {
+        fixes.push(format!("{}: {} -> {}", label, current, fixed));
+        map.insert(Value::from(key), Value::from(fixed));
+    }
+}
+
+fn fix_state(fm: &mut Mapping, fixes: &mut Vec<String>) {
+    if !matches!(fm.get("state"), Some(Value::Mapping(_))) {
+        fixes.push("state: added defaults".to_string());
+        fm.insert(Value::from("state"), Value::Mapping(Mapping::new()));
+    }
+    let Some(Value::Mapping(state)) = fm.get_mut("state") else { return };
+    let filled_all = state.is_empty();
+
+    for (field, default) in [("workflow", Value::from("draft")), ("confidence", Value::from("unverified")), ("maturity", Value::from(1))] {
+        if !state.contains_key(field) {
+            if !filled_all {
+                fixes.push(format!("state.{}: added {}", field, display(&default)));
+            }
+            state.insert(Value::from(field), default);
+        }
+    }
+    fix_enum(state, "workflow", &WORKFLOW_STATES, "state.workflow", fixes);
+    fix_enum(state, "confidence", &CONFIDENCE_STATES, "state.confidence", fixes);
+
+    let maturity = state.get("maturity").cloned().unwrap_or(Value::Null);
+    let number = match &maturity {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    };
+    let fixed = number.map(|n| n.round().clamp(1.0, 5.0) as u64).unwrap_or(1);
+    if maturity.as_u64() != Some(fixed) {
+        fixes.push(format!("state.maturity: {} -> {}", display(&maturity), fixed));
+        state.insert(Value::from("maturity"), Value::from(fixed));
+    }
+}
+
+/// Apply every fix to a frontmatter mapping. A missing id comes from `new_id`, seeded with
+/// `created` (empty if unknown); it returns that id if unused, otherwise a free one.
+fn fix_mapping(fm: &mut Mapping, date_format: &str, new_id: &dyn Fn(&str) -> String, fixes: &mut Vec<String>) {
+    let created = fix_date(fm, "created", date_format, fixes);
+    fix_date(fm, "modified", date_format, fixes);
+
+    let has_id = match fm.get("id") {
+        Some(Value::String(s)) => !s.trim().is_empty(),
+        Some(Value::Number(_)) => true,
+        _ => false,
+    };
+    if !has_id {
+        let id = new_id(&created.map(|dt| dt.format("%Y%m%d%H%M%S").to_string()).unwrap_or_default());
+        fixes.push(format!("id: generated {}", id));
+        fm.insert(Value::from("id"), Value::String(id));
+    }
+
+    fix_enum(fm, "type", &NOTE_TYPES, "type", fixes);
+    fix_state(fm, fixes);
+
+    if let Some(Value::Sequence(relations)) = fm.get_mut("relations") {
+        for relation in relations.iter_mut().filter_map(Value::as_mapping_mut) {
+            fix_enum(relation, "relation_type", &RELATION_TYPES, "relation_type", fixes);
+        }
+    }
+}
+
+/// Validator errors of a fixed mapping
+fn remaining_errors(fm: &Mapping, schemas: &VaultSchemas) -> Vec<String> {
+    match serde_yaml::from_value::<Frontmatter>(Value::Mapping(fm.clone())) {
+        Ok(typed) => match FrontmatterParser::validate_with(&typed, schemas) {
+            Ok(errors) => errors.into_iter().map(|e| format!("{}: {}", e.path, e.message)).collect(),
+            Err(e) => vec![e],
+        },
+        Err(e) => vec![e.to_string()],
+    }
+}
+
+/// Fixed content of a note (None if nothing needed fixing) and what was done.
+/// Notes without frontmatter are left alone; YAML the quoting fix cannot repair is an error.
+pub fn fix_note_content(
+    content: &str,
+    date_format: &str,
+    schemas: &VaultSchemas,
+    new_id: &dyn Fn(&str) -> String,
+) -> Result<(Option<String>, NoteFix), String> {
+    let mut report = NoteFix::default();
+    let Some((yaml, rest)) = split_frontmatter_raw(content) else {
+        return Ok((None, report));
+    };
+
+    let yaml = match serde_yaml::from_str::<Value>(yaml) {
+        Ok(_) => yaml.to_string(),
+        Err(_) => fix_quoting(yaml, &mut report.fixes),
+    };
+    let mut fm = match serde_yaml::from_str::<Value>(&yaml) {
+        Ok(Value::Mapping(map)) => map,
+        Ok(Value::Null) => Mapping::new(),
+        Ok(_) => return Err("Frontmatter is not a mapping".to_string()),
+        Err(e) => return Err(format!("Invalid YAML that cannot be fixed automatically: {}", e)),
+    };
+
+    fix_mapping(&mut fm, date_format, new_id, &mut report.fixes);
+    report.remaining = remaining_errors(&fm, schemas);
+    if report.fixes.is_empty() {
+        return Ok((None, report));
+    }
+    let repaired = format!("---{}{}", yaml, rest);
+    Ok((Some(yaml_edit::update_frontmatter_content(&repaired, &fm)?), report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_datetime_variants() {
+        for value in ["2026-01-05", "2026/01/05", "2026.01.05", "2026-01-05 00:00", "2026-01-05T00:00:00"] {
+            let dt = parse_datetime(value).unwrap_or_else(|| panic!("{}", value));
+            assert_eq!(dt.format("%Y-%m-%d %H:%M").to_string(), "2026-01-05 00:00", "{}", value);
+        }
+        let dt = parse_datetime("2026-01-05T09:30:00+09:00").unwrap();
+        assert_eq!(dt.format(DEFAULT_DATE_FORMAT).to_string(), "2026-01-05T09:30:00+09:00", "오프셋 유지");
+        assert!(parse_datetime("어제").is_none());
+
+        assert!(validate_date_format(DEFAULT_DATE_FORMAT).is_ok());
+        assert!(validate_date_format("%Y-%m-%d").is_ok());
+        assert!(validate_date_format("%d/%m/%Y").is_err());
+        assert!(validate_date_format("%Q").is_err());
+    }
+
+    #[test]
+    fn test_canonical_and_quoting() {
+        assert_eq!(canonical("In-Progress", &WORKFLOW_STATES), Some("in-progress"));
+        assert_eq!(canonical("in_progress", &WORKFLOW_STATES), Some("in-progress"));
+        assert_eq!(canonical("mtg", &NOTE_TYPES), Some("MTG"));
+        assert_eq!(canonical("done", &WORKFLOW_STATES), None);
+
+        let mut fixes = Vec::new();
+        let fixed = fix_quoting("\ntitle: Q&A: 정리\nalias: 'it's'\nwho:\n  - @김\nrelations:\n  - target: a\nok: \"그대로\"\n", &mut fixes);
+        assert_eq!(fixed, "\ntitle: \"Q&A: 정리\"\nalias: \"it's\"\nwho:\n  - \"@김\"\nrelations:\n  - target: a\nok: \"그대로\"\n");
+        assert_eq!(fixes.len(), 3);
+    }
+}
@@ -0,0 +1,100 @@
+// Frontmatter auto-fix - 날짜 정규화, id/state 보충, maturity 범위, enum 대소문자, 따옴표 복구, 백업 검증
+
+#[cfg(test)]
+mod autofix_tests {
+    use crate::autofix::DEFAULT_DATE_FORMAT;
+    use crate::frontmatter::FrontmatterParser;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const MESSY: &str = "---\ntitle: Q&A: 정리\ntype: mtg   # 회의\ncreated: 2026/01/05\nmodified: \"2026-01-06T10:30:00+09:00\"\nstate:\n  workflow: In-Progress\n  maturity: 9\n---\n\n본문\n";
+
+    #[test]
+    fn test_fix_preview_then_apply() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        let note = vault.join("정리.md");
+        fs::write(&note, MESSY).unwrap();
+        let paths = vec![note.to_string_lossy().to_string()];
+
+        let (preview, written) = crate::fix_frontmatter_in_files(&paths, DEFAULT_DATE_FORMAT, true, vault).unwrap();
+        assert!(written.is_empty());
+        assert_eq!(fs::read_to_string(&note).unwrap(), MESSY, "dry-run은 파일 유지");
+        assert_eq!(preview.result.changed.len(), 1);
+        assert!(preview.result.changed[0].diff.contains("+  workflow: in-progress"));
+        let fixes = &preview.notes[0].fixes;
+        for expected in ["quoted value", "created: 2026/01/05 ->", "id: generated 20260105000000", "type: mtg -> MTG", "state.workflow: In-Progress -> in-progress", "state.confidence: added", "state.maturity: 9 -> 5"] {
+            assert!(fixes.iter().any(|f| f.starts_with(expected)), "{} 없음: {:?}", expected, fixes);
+        }
+        assert!(!fixes.iter().any(|f| f.starts_with("modified")), "이미 정규형이면 그대로");
+
+        let (report, written) = crate::fix_frontmatter_in_files(&paths, DEFAULT_DATE_FORMAT, false, vault).unwrap();
+        assert_eq!(written.len(), 1);
+        assert!(report.notes[0].remaining.is_empty(), "{:?}", report.notes[0].remaining);
+        let content = fs::read_to_string(&note).unwrap();
+        assert!(content.contains("type: MTG   # 회의\n"), "주석 유지");
+        assert!(content.contains("modified: \"2026-01-06T10:30:00+09:00\"\n"));
+        assert!(content.ends_with("---\n\n본문\n"));
+        let (fm, _) = FrontmatterParser::parse(&content).unwrap();
+        let fm = fm.unwrap();
+        assert_eq!(fm.title, "Q&A: 정리");
+        assert_eq!(fm.state.maturity, 5);
+        assert!(FrontmatterParser::validate(&fm).unwrap().is_empty());
+        assert_eq!(fs::read_dir(vault.join(".notology").join("backups")).unwrap().count(), 1);
+
+        let (again, _) = crate::fix_frontmatter_in_files(&paths, DEFAULT_DATE_FORMAT, false, vault).unwrap();
+        assert!(again.result.changed.is_empty(), "두 번째 실행은 변경 없음");
+    }
+
+    #[test]
+    fn test_generated_ids_are_unique() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        let note = |title: &str| format!("---\ntitle: {}\ntype: NOTE\ncreated: 2024-03-07\nmodified: 2024-03-07\n---\n", title);
+        fs::write(vault.join("기존.md"), "---\nid: \"20240307000000\"\ntitle: 기존\ntype: NOTE\ncreated: 2024-03-07\nmodified: 2024-03-07\n---\n").unwrap();
+        let paths: Vec<String> = ["a", "b", "c"]
+            .iter()
+            .map(|name| {
+                let path = vault.join(format!("{}.md", name));
+                fs::write(&path, note(name)).unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+
+        let (preview, _) = crate::fix_frontmatter_in_files(&paths, DEFAULT_DATE_FORMAT, true, vault).unwrap();
+        let (report, _) = crate::fix_frontmatter_in_files(&paths, DEFAULT_DATE_FORMAT, false, vault).unwrap();
+        let ids = |report: &crate::FrontmatterFixReport| -> Vec<String> {
+            let mut ids: Vec<String> = report.notes.iter()
+                .flat_map(|n| n.fixes.iter().filter_map(|f| f.strip_prefix("id: generated ").map(|id| id.to_string())))
+                .collect();
+            ids.sort();
+            ids
+        };
+        let mut generated = ids(&report);
+        assert_eq!(generated.len(), 3);
+        assert_eq!(ids(&preview).len(), 3);
+        assert!(!generated.contains(&"20240307000000".to_string()), "볼트에 있는 id는 피함");
+        generated.dedup();
+        assert_eq!(generated.len(), 3, "날짜만 같은 노트도 서로 다른 id");
+    }
+
+    #[test]
+    fn test_custom_date_format_and_unfixable_yaml() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let good = vault.join("a.md");
+        let broken = vault.join("b.md");
+        fs::write(&good, "---\nid: \"1\"\ntitle: a\ntype: NOTE\ncreated: 2026-01-05T09:00:00+09:00\nmodified: 2026-01-05\n---\n").unwrap();
+        fs::write(&broken, "---\ntitle: b\ntags: [a\n  b: c\n---\n").unwrap();
+        let paths: Vec<String> = [&good, &broken].iter().map(|p| p.to_string_lossy().to_string()).collect();
+
+        assert!(crate::fix_frontmatter_in_files(&paths, "%d.%m.%Y", true, vault).is_err(), "ISO가 아닌 형식 거부");
+        let (report, _) = crate::fix_frontmatter_in_files(&paths, "%Y-%m-%d", false, vault).unwrap();
+        assert_eq!(report.result.failed.len(), 1);
+        assert!(report.result.failed[0].path.ends_with("b.md"));
+        let content = fs::read_to_string(&good).unwrap();
+        assert!(content.contains("created: 2026-01-05\nmodified: 2026-01-05\n"), "{}", content);
+    }
+}
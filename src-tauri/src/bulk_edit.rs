@@ -0,0 +1,334 @@
+//! Bulk frontmatter edits and vault-wide tag refactoring
+//!
+//! Edits are applied to the raw YAML mapping (key order and unknown keys kept) rather than
+//! the typed `Frontmatter`, so notes with partial or legacy frontmatter can still be edited.
+//! Only changed entries are rewritten (see `yaml_edit`); the body is never touched.
+
+use crate::frontmatter::schemas::TASK_PRIORITIES;
+use crate::frontmatter::types::{State, WorkflowState};
+use crate::frontmatter::yaml_edit::update_frontmatter_content;
+use crate::search::parser::split_frontmatter_raw;
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+
+pub const TAG_FACETS: [&str; 7] = ["domain", "who", "org", "ctx", "source", "method", "status"];
+
+/// Keys with a dedicated meaning in `Frontmatter`; `SetExtra` may not touch them
+const RESERVED_KEYS: [&str; 23] = [
+    "id", "title", "type", "created", "modified", "state", "tags", "relations", "cssclasses",
+    "participants", "date", "authors", "venue", "year", "doi", "url", "due", "priority",
+    "assignee", "email", "phone", "organization", "role",
+];
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum FrontmatterEdit {
+    AddTag { facet: String, tag: String },
+    RemoveTag { facet: String, tag: String },
+    SetWorkflow { workflow: WorkflowState },
+    /// `None` removes the field
+    SetPriority { priority: Option<String> },
+    /// `null` removes the key
+    SetExtra { key: String, value: serde_json::Value },
+}
+
+/// Reject edits that can never apply (unknown facet, reserved or empty extra key, unknown priority)
+pub fn validate_edits(edits: &[FrontmatterEdit]) -> Result<(), String> {
+    if edits.is_empty() {
+        return Err("No edits given".to_string());
+    }
+    for edit in edits {
+        match edit {
+            FrontmatterEdit::AddTag { facet, tag } | FrontmatterEdit::RemoveTag { facet, tag } => {
+                if !TAG_FACETS.contains(&facet.as_str()) {
+                    return Err(format!("Unknown tag facet: {}", facet));
+                }
+                if tag.trim().is_empty() {
+                    return Err("Tag must not be empty".to_string());
+                }
+            }
+            FrontmatterEdit::SetExtra { key, .. } => {
+                if key.trim().is_empty() || RESERVED_KEYS.contains(&key.as_str()) {
+                    return Err(format!("'{}' cannot be set as an extra field", key));
+                }
+            }
+            FrontmatterEdit::SetPriority { priority: Some(priority) } => {
+                if !TASK_PRIORITIES.contains(&priority.as_str()) {
+                    return Err(format!("Unknown priority: {} (expected one of {})", priority, TASK_PRIORITIES.join(", ")));
+                }
+            }
+            FrontmatterEdit::SetWorkflow { .. } | FrontmatterEdit::SetPriority { priority: None } => {}
+        }
+    }
+    Ok(())
+}
+
+fn key(name: &str) -> Value {
+    Value::String(name.to_string())
+}
+
+/// Tag list for `facet`, converting an empty/legacy-empty `tags` value to the faceted mapping
+fn facet_list<'a>(fm: &'a mut Mapping, facet: &str) -> Result<&'a mut Vec<Value>, String> {
+    let tags = fm.entry(key("tags")).or_insert(Value::Null);
+    let is_empty_legacy = matches!(tags, Value::Null) || tags.as_sequence().is_some_and(|s| s.is_empty());
+    if is_empty_legacy {
+        *tags = Value::Mapping(Mapping::new());
+    }
+    let Value::Mapping(facets) = tags else {
+        return Err("Legacy tags format; migrate tags before bulk editing".to_string());
+    };
+    let list = facets.entry(key(facet)).or_insert_with(|| Value::Sequence(Vec::new()));
+    if list.is_null() {
+        *list = Value::Sequence(Vec::new());
+    }
+    list.as_sequence_mut().ok_or_else(|| format!("tags.{} is not a list", facet))
+}
+
+/// Tag without its facet prefix ("domain/예산" -> "예산")
+fn bare_tag<'a>(facet: &str, tag: &'a str) -> &'a str {
+    let tag = tag.trim();
+    tag.strip_prefix(facet).and_then(|r| r.strip_prefix('/')).unwrap_or(tag)
+}
+
+fn tag_matches(value: &Value, facet: &str, tag: &str) -> bool {
+    value.as_str().is_some_and(|v| bare_tag(facet, v) == tag)
+}
+
+/// Split a `facet/name` tag ("domain/ml" -> ("domain", "ml"))
+pub fn parse_tag(tag: &str) -> Result<(String, String), String> {
+    let (facet, name) = tag.trim().split_once('/').ok_or_else(|| format!("Tag must be facet/name: {}", tag))?;
+    if !TAG_FACETS.contains(&facet) {
+        return Err(format!("Unknown tag facet: {}", facet));
+    }
+    if name.trim().is_empty() {
+        return Err(format!("Tag name is empty: {}", tag));
+    }
+    Ok((facet.to_string(), name.trim().to_string()))
+}
+
+/// Vault-wide tag change. Tags are given as `facet/name`, as listed by `get_all_used_tags`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TagRefactor {
+    /// Rename a tag; a different facet in `to` also moves it
+    Rename { from: String, to: String },
+    /// Replace every source tag with `into`
+    Merge { sources: Vec<String>, into: String },
+    /// Move a tag to another facet, keeping its name
+    Move { tag: String, facet: String },
+    Delete { tag: String },
+}
+
+pub type TagReplacement = ((String, String), Option<(String, String)>);
+
+impl TagRefactor {
+    /// (old tag, replacement) pairs; `None` deletes
+    pub fn replacements(&self) -> Result<Vec<TagReplacement>, String> {
+        let pairs = match self {
+            TagRefactor::Rename { from, to } => vec![(parse_tag(from)?, Some(parse_tag(to)?))],
+            TagRefactor::Merge { sources, into } => {
+                let into = parse_tag(into)?;
+                sources
+                    .iter()
+                    .map(|s| Ok((parse_tag(s)?, Some(into.clone()))))
+                    .collect::<Result<Vec<_>, String>>()?
+            }
+            TagRefactor::Move { tag, facet } => {
+                let from = parse_tag(tag)?;
+                let to = parse_tag(&format!("{}/{}", facet, from.1))?;
+                vec![(from, Some(to))]
+            }
+            TagRefactor::Delete { tag } => vec![(parse_tag(tag)?, None)],
+        };
+        let pairs: Vec<TagReplacement> = pairs.into_iter().filter(|(from, to)| to.as_ref() != Some(from)).collect();
+        if pairs.is_empty() {
+            return Err("Tag refactor changes nothing".to_string());
+        }
+        Ok(pairs)
+    }
+}
+
+/// Replace (or delete) `from` in a frontmatter mapping; Ok(true) if the note had the tag.
+/// A rename within the same facet keeps the tag's position in the list.
+pub fn retag(fm: &mut Mapping, from: &(String, String), to: Option<&(String, String)>) -> Result<bool, String> {
+    let (facet, name) = from;
+    let has_tag = fm
+        .get("tags")
+        .and_then(|t| t.as_mapping())
+        .and_then(|t| t.get(facet.as_str()))
+        .and_then(|l| l.as_sequence())
+        .is_some_and(|l| l.iter().any(|v| tag_matches(v, facet, name)));
+    if !has_tag {
+        return Ok(false);
+    }
+
+    let list = facet_list(fm, facet)?;
+    let position = list.iter().position(|v| tag_matches(v, facet, name)).unwrap_or(0);
+    list.retain(|v| !tag_matches(v, facet, name));
+
+    let Some((to_facet, to_name)) = to else {
+        return Ok(true);
+    };
+    let insert_at = if to_facet == facet { position } else { usize::MAX };
+    let target = facet_list(fm, to_facet)?;
+    if !target.iter().any(|v| tag_matches(v, to_facet, to_name)) {
+        target.insert(insert_at.min(target.len()), Value::String(to_name.clone()));
+    }
+    Ok(true)
+}
+
+/// Apply `edits` to a frontmatter mapping; Ok(true) if anything changed
+pub fn apply_edits(fm: &mut Mapping, edits: &[FrontmatterEdit]) -> Result<bool, String> {
+    let before = fm.clone();
+    for edit in edits {
+        match edit {
+            FrontmatterEdit::AddTag { facet, tag } => {
+                let tag = bare_tag(facet, tag);
+                let list = facet_list(fm, facet)?;
+                if !list.iter().any(|v| tag_matches(v, facet, tag)) {
+                    list.push(Value::String(tag.to_string()));
+                }
+            }
+            FrontmatterEdit::RemoveTag { facet, tag } => {
+                let has_facet = fm
+                    .get("tags")
+                    .and_then(|t| t.as_mapping())
+                    .is_some_and(|t| t.contains_key(facet.as_str()));
+                if has_facet {
+                    facet_list(fm, facet)?.retain(|v| !tag_matches(v, facet, bare_tag(facet, tag)));
+                }
+            }
+            FrontmatterEdit::SetWorkflow { workflow } => {
+                let workflow = serde_yaml::to_value(workflow).map_err(|e| e.to_string())?;
+                let state = fm.entry(key("state")).or_insert(Value::Null);
+                if !state.is_mapping() {
+                    *state = serde_yaml::to_value(State::default()).map_err(|e| e.to_string())?;
+                }
+                if let Value::Mapping(state) = state {
+                    state.insert(key("workflow"), workflow);
+                }
+            }
+            FrontmatterEdit::SetPriority { priority: Some(priority) } => {
+                fm.insert(key("priority"), Value::String(priority.clone()));
+            }
+            FrontmatterEdit::SetPriority { priority: None } => {
+                fm.remove("priority");
+            }
+            FrontmatterEdit::SetExtra { key: name, value: serde_json::Value::Null } => {
+                fm.remove(name.as_str());
+            }
+            FrontmatterEdit::SetExtra { key: name, value } => {
+                let value = serde_yaml::to_value(value).map_err(|e| e.to_string())?;
+                fm.insert(key(name), value);
+            }
+        }
+    }
+    Ok(*fm != before)
+}
+
+/// Run `edit` on the note's frontmatter mapping; None if it reports no change.
+/// `modified` is stamped on changed notes.
+pub fn edit_frontmatter_content(
+    content: &str,
+    modified: &str,
+    edit: impl FnOnce(&mut Mapping) -> Result<bool, String>,
+) -> Result<Option<String>, String> {
+    let (yaml, _) = split_frontmatter_raw(content).ok_or("Note has no frontmatter")?;
+
+    let mut fm: Mapping = match serde_yaml::from_str::<Value>(yaml).map_err(|e| format!("Invalid frontmatter: {}", e))? {
+        Value::Mapping(m) => m,
+        Value::Null => Mapping::new(),
+        _ => return Err("Frontmatter is not a mapping".to_string()),
+    };
+    if !edit(&mut fm)? {
+        return Ok(None);
+    }
+    fm.insert(key("modified"), Value::String(modified.to_string()));
+
+    update_frontmatter_content(content, &fm).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edits(json: &str) -> Vec<FrontmatterEdit> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn edit_note_content(content: &str, edits: &[FrontmatterEdit], modified: &str) -> Result<Option<String>, String> {
+        edit_frontmatter_content(content, modified, |fm| apply_edits(fm, edits))
+    }
+
+    #[test]
+    fn test_tag_workflow_and_extra_edits() {
+        let content = "---\ntitle: 회의\ntags: []\npriority: low\n---\n\n본문 ---\n";
+        let edits = edits(
+            r#"[{"op":"add_tag","facet":"domain","tag":"예산"},
+                {"op":"set_workflow","workflow":"in-progress"},
+                {"op":"set_priority","priority":null},
+                {"op":"set_extra","key":"project","value":"알파"}]"#,
+        );
+        let updated = edit_note_content(content, &edits, "2026-02-01T10:00:00+09:00").unwrap().unwrap();
+        assert!(updated.ends_with("---\n\n본문 ---\n"), "본문은 그대로");
+
+        let (_, body) = crate::search::parser::split_frontmatter_body(&updated);
+        assert_eq!(body, "본문 ---\n");
+        let fm: Mapping = serde_yaml::from_str(&updated[3..updated.find("\n---\n").unwrap()]).unwrap();
+        assert_eq!(fm["tags"]["domain"][0], "예산");
+        assert_eq!(fm["state"]["workflow"], "in-progress");
+        assert_eq!(fm["state"]["maturity"], 1, "state 생성 시 기본값 채움");
+        assert!(!fm.contains_key("priority"));
+        assert_eq!(fm["project"], "알파");
+        assert_eq!(fm["modified"], "2026-02-01T10:00:00+09:00");
+    }
+
+    #[test]
+    fn test_no_op_and_legacy_tags() {
+        let content = "---\ntags:\n  domain: [예산]\n---\n본문";
+        let add = edits(r#"[{"op":"add_tag","facet":"domain","tag":"domain/예산"}]"#);
+        assert_eq!(edit_note_content(content, &add, "t").unwrap(), None, "이미 있는 태그");
+
+        let remove = edits(r#"[{"op":"remove_tag","facet":"domain","tag":"예산"}]"#);
+        assert!(edit_note_content(content, &remove, "t").unwrap().unwrap().contains("domain: []"));
+
+        let legacy = "---\ntags: [옛태그]\n---\n";
+        assert!(edit_note_content(legacy, &add, "t").is_err());
+        assert!(edit_note_content("본문만", &add, "t").is_err());
+    }
+
+    #[test]
+    fn test_retag_rename_merge_move_delete() {
+        let yaml = "tags:\n  domain: [ai, ml, domain/stats]\n  ctx: [ml]\n";
+        let run = |refactor: TagRefactor| {
+            let mut fm: Mapping = serde_yaml::from_str(yaml).unwrap();
+            for (from, to) in refactor.replacements().unwrap() {
+                retag(&mut fm, &from, to.as_ref()).unwrap();
+            }
+            serde_yaml::to_string(&fm["tags"]).unwrap()
+        };
+
+        let renamed = run(TagRefactor::Rename { from: "domain/ml".into(), to: "domain/machine-learning".into() });
+        assert_eq!(renamed, "domain:\n- ai\n- machine-learning\n- domain/stats\nctx:\n- ml\n", "위치 유지, 다른 facet 무관");
+
+        let merged = run(TagRefactor::Merge { sources: vec!["domain/ml".into(), "domain/stats".into()], into: "domain/ai".into() });
+        assert_eq!(merged, "domain:\n- ai\nctx:\n- ml\n");
+
+        let moved = run(TagRefactor::Move { tag: "domain/ai".into(), facet: "method".into() });
+        assert!(moved.contains("method:\n- ai\n") && !moved.contains("- ai\n- ml"));
+
+        let deleted = run(TagRefactor::Delete { tag: "domain/stats".into() });
+        assert_eq!(deleted, "domain:\n- ai\n- ml\nctx:\n- ml\n");
+
+        assert!(TagRefactor::Rename { from: "ml".into(), to: "domain/x".into() }.replacements().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_edits() {
+        assert!(validate_edits(&edits(r#"[{"op":"set_extra","key":"title","value":"x"}]"#)).is_err());
+        assert!(validate_edits(&edits(r#"[{"op":"add_tag","facet":"topic","tag":"x"}]"#)).is_err());
+        assert!(validate_edits(&[]).is_err());
+        assert!(validate_edits(&edits(r#"[{"op":"set_priority","priority":"critical"}]"#)).unwrap_err().contains("Unknown priority"));
+        assert!(validate_edits(&edits(r#"[{"op":"set_priority","priority":"urgent"},{"op":"set_priority","priority":null}]"#)).is_ok());
+    }
+}
@@ -0,0 +1,83 @@
+// Bulk frontmatter edit - dry-run 무변경, 백업 생성, 단일 커밋 인덱스 반영 검증
+
+#[cfg(test)]
+mod bulk_edit_tests {
+    use crate::bulk_edit::FrontmatterEdit;
+    use crate::search::NoteFilter;
+    use crate::test_common::{open_index, path_str};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn setup_vault(vault: &Path) -> Vec<String> {
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        let notes = [
+            ("회의1.md", "---\ntitle: 회의1\ntype: MTG\ntags:\n  domain: [기획]\n---\n\n본문1\n"),
+            ("회의2.md", "---\ntitle: 회의2\ntype: MTG\ntags:\n  domain: []\n---\n\n본문2\n"),
+            ("메모.md", "본문만 있는 노트\n"),
+        ];
+        notes
+            .iter()
+            .map(|(name, content)| {
+                fs::write(vault.join(name), content).unwrap();
+                path_str(&vault.join(name))
+            })
+            .collect()
+    }
+
+    fn add_tag(tag: &str) -> Vec<FrontmatterEdit> {
+        vec![FrontmatterEdit::AddTag { facet: "domain".to_string(), tag: tag.to_string() }]
+    }
+
+    #[test]
+    fn test_dry_run_reports_diffs_without_writing() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let paths = setup_vault(vault);
+        let before: Vec<String> = paths.iter().map(|p| fs::read_to_string(p).unwrap()).collect();
+
+        let (result, written) = crate::apply_bulk_frontmatter_edits(&paths, &add_tag("기획"), true, vault).unwrap();
+
+        assert!(written.is_empty());
+        assert_eq!(result.changed.len(), 1, "회의1은 이미 태그가 있음");
+        assert_eq!(result.unchanged, 1);
+        assert_eq!(result.failed.len(), 1, "frontmatter 없는 노트는 실패로 보고");
+        let after: Vec<String> = paths.iter().map(|p| fs::read_to_string(p).unwrap()).collect();
+        assert_eq!(before, after);
+        assert!(!vault.join(".notology").join("backups").exists());
+    }
+
+    #[test]
+    fn test_bulk_edit_backs_up_and_commits_index_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let paths = setup_vault(vault);
+
+        let (index, _guard) = open_index(vault);
+
+        let filter = NoteFilter {
+            note_type: Some("MTG".to_string()),
+            tags: None,
+            created_after: None,
+            created_before: None,
+            modified_after: None,
+            modified_before: None,
+            sort_by: None,
+            sort_order: None,
+            metrics: None,
+        };
+        let targets: Vec<String> = index.query_notes(&filter).unwrap().into_iter().map(|m| m.path).collect();
+        assert_eq!(targets.len(), 2);
+
+        let (result, written) = crate::apply_bulk_frontmatter_edits(&targets, &add_tag("예산"), false, vault).unwrap();
+        assert_eq!(result.changed.len(), 2);
+        assert_eq!(index.index_files(&written).unwrap(), 2);
+
+        let backups = fs::read_dir(vault.join(".notology").join("backups")).unwrap().count();
+        assert_eq!(backups, 2);
+        assert!(fs::read_to_string(&paths[1]).unwrap().contains("domain: [예산]\n"));
+
+        let tagged = NoteFilter { tags: Some(vec!["domain/예산".to_string()]), ..filter };
+        assert_eq!(index.query_notes(&tagged).unwrap().len(), 2);
+    }
+}
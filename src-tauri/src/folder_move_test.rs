@@ -0,0 +1,111 @@
+// Folder rename/move - 경로형 링크([[폴더/노트]]), 폴더 노트와 _att 폴더 동기화 검증
+
+#[cfg(test)]
+mod folder_move_tests {
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn s(p: &Path) -> String {
+        p.to_string_lossy().to_string()
+    }
+
+    /// vault/
+    ///   .notology/
+    ///   프로젝트/알파/알파.md (+ 알파_att/계획.pdf)
+    ///   프로젝트/알파/회의.md
+    ///   색인.md (링크 모음)
+    fn setup_vault(vault: &Path) {
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        let alpha = vault.join("프로젝트").join("알파");
+        fs::create_dir_all(alpha.join("알파_att")).unwrap();
+        fs::write(alpha.join("알파.md"), "---\ntype: CONTAINER\n---\n").unwrap();
+        fs::write(alpha.join("알파_att").join("계획.pdf"), b"%PDF").unwrap();
+        fs::write(alpha.join("회의.md"), "[[알파]]").unwrap();
+        fs::write(
+            vault.join("색인.md"),
+            "[[회의]]\n[[프로젝트/알파/회의]]\n[[알파/회의#결정|결정]]\n[[알파]]\n[[프로젝트/알파/알파_att/계획.pdf]]\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_move_folder_rewrites_path_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+        fs::create_dir_all(vault.join("보관")).unwrap();
+
+        let new_path = crate::move_folder(
+            s(&vault.join("프로젝트").join("알파")),
+            s(&vault.join("보관")),
+            s(vault),
+        )
+        .unwrap();
+
+        assert_eq!(Path::new(&new_path.final_path), vault.join("보관").join("알파"));
+        assert!(vault.join("보관").join("알파").join("알파_att").join("계획.pdf").exists());
+        assert_eq!(
+            fs::read_to_string(vault.join("색인.md")).unwrap(),
+            "[[회의]]\n[[보관/알파/회의]]\n[[알파/회의#결정|결정]]\n[[알파]]\n[[보관/알파/알파_att/계획.pdf]]\n"
+        );
+    }
+
+    #[test]
+    fn test_rename_folder_updates_folder_note_and_path_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+
+        let new_path = crate::rename_folder(
+            s(&vault.join("프로젝트").join("알파")),
+            "베타".to_string(),
+            s(vault),
+        )
+        .unwrap();
+
+        let beta = vault.join("프로젝트").join("베타");
+        assert_eq!(Path::new(&new_path.final_path), beta);
+        assert!(beta.join("베타.md").exists(), "폴더 노트도 이름 변경");
+        assert!(beta.join("베타_att").join("계획.pdf").exists(), "폴더 노트 _att도 이름 변경");
+        assert_eq!(fs::read_to_string(beta.join("회의.md")).unwrap(), "[[베타]]");
+        assert_eq!(
+            fs::read_to_string(vault.join("색인.md")).unwrap(),
+            "[[회의]]\n[[프로젝트/베타/회의]]\n[[베타/회의#결정|결정]]\n[[베타]]\n[[프로젝트/베타/베타_att/계획.pdf]]\n"
+        );
+    }
+
+    #[test]
+    fn test_move_note_rewrites_path_links_in_vault() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+        let dest = vault.join("보관");
+        fs::create_dir_all(&dest).unwrap();
+
+        let preview = crate::preview_move_note(
+            s(&vault.join("프로젝트").join("알파").join("회의.md")),
+            s(&dest),
+            s(vault),
+        )
+        .unwrap();
+        assert_eq!(preview.diffs.len(), 1);
+        assert!(preview.diffs[0].diff.contains("+[[보관/회의]]"));
+
+        crate::move_note(s(&vault.join("프로젝트").join("알파").join("회의.md")), s(&dest)).unwrap();
+        let index = fs::read_to_string(vault.join("색인.md")).unwrap();
+        assert!(index.contains("[[보관/회의]]\n"));
+        assert!(index.contains("[[보관/회의#결정|결정]]"), "구성요소 수는 유지: {}", index);
+        assert!(index.starts_with("[[회의]]\n"), "단순 이름 링크는 그대로");
+    }
+
+    #[test]
+    fn test_move_folder_into_itself_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+        let alpha = vault.join("프로젝트").join("알파");
+
+        assert!(crate::move_folder(s(&alpha), s(&alpha.join("하위")), s(vault)).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//! Note type conversion
+//!
+//! Type-specific fields the target type's schema does not define are mapped by the rules in
+//! `FIELD_RULES` (to another field or to a tag facet); fields with no rule are moved under
+//! the `extra` mapping so nothing is lost.
+
+use super::schemas::{schema_properties, VaultSchemas};
+use super::types::{Frontmatter, NoteType};
+use super::FrontmatterParser;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Fields of `Frontmatter` that only some note types define
+pub const TYPE_FIELDS: [&str; 14] = [
+    "participants", "date", "authors", "venue", "year", "doi", "url", "due", "priority",
+    "assignee", "email", "phone", "organization", "role",
+];
+
+/// Key of the mapping that receives fields the target type has no place for
+pub const EXTRA_KEY: &str = "extra";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldTarget {
+    Field(&'static str),
+    /// Values become tags of this facet
+    Tags(&'static str),
+}
+
+/// (from type, to type, field, target); "*" matches any type. Pair-specific rules come first.
+const FIELD_RULES: &[(&str, &str, &str, FieldTarget)] = &[
+    ("TASK", "EVENT", "due", FieldTarget::Field("date")),
+    ("TASK", "MTG", "due", FieldTarget::Field("date")),
+    ("EVENT", "TASK", "date", FieldTarget::Field("due")),
+    ("MTG", "TASK", "date", FieldTarget::Field("due")),
+    ("MTG", "*", "participants", FieldTarget::Tags("who")),
+    ("TASK", "*", "assignee", FieldTarget::Tags("who")),
+    ("PAPER", "*", "authors", FieldTarget::Tags("who")),
+    ("PAPER", "*", "venue", FieldTarget::Tags("source")),
+    ("CONTACT", "*", "organization", FieldTarget::Tags("org")),
+];
+
+fn rule_for(from: &str, to: &str, field: &str) -> Option<FieldTarget> {
+    FIELD_RULES
+        .iter()
+        .find(|(f, t, name, _)| *name == field && *f == from && (*t == to || *t == "*"))
+        .map(|(_, _, _, target)| *target)
+}
+
+/// Where a field of the old type ended up ("date", "tags.who", "extra.venue")
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FieldMove {
+    pub field: String,
+    pub target: String,
+}
+
+/// "[[김]]" -> "김"; lists give one tag per string item
+fn tag_values(value: &Value) -> Vec<String> {
+    let items = match value {
+        Value::Array(items) => items.iter().collect(),
+        other => vec![other],
+    };
+    items
+        .into_iter()
+        .filter_map(|v| v.as_str())
+        .map(|s| s.trim().trim_start_matches("[[").trim_end_matches("]]").trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Frontmatter converted to `to`, with the list of moved fields. Fails if the result does
+/// not validate against the target type's schema (including vault schema extensions).
+pub fn convert_frontmatter(
+    fm: &Frontmatter,
+    to: NoteType,
+    schemas: &VaultSchemas,
+) -> Result<(Frontmatter, Vec<FieldMove>), String> {
+    let from_name = format!("{:?}", fm.note_type);
+    let to_name = format!("{:?}", to);
+    if from_name == to_name {
+        return Err(format!("Note is already {}", to_name));
+    }
+
+    let mut obj: Map<String, Value> = match serde_json::to_value(fm).map_err(|e| e.to_string())? {
+        Value::Object(obj) => obj,
+        _ => return Err("Frontmatter is not an object".to_string()),
+    };
+    let allowed = schema_properties(&schemas.schema_for_type(&to_name));
+    let unplaced: Vec<(&str, Value)> = TYPE_FIELDS
+        .iter()
+        .filter(|field| !allowed.iter().any(|a| a == *field))
+        .filter_map(|field| obj.remove(*field).map(|value| (*field, value)))
+        .collect();
+
+    let mut moves = Vec::new();
+    let mut extra = Map::new();
+    for (field, value) in unplaced {
+        let target = match rule_for(&from_name, &to_name, field) {
+            Some(FieldTarget::Field(target)) if allowed.iter().any(|a| a == target) && !obj.contains_key(target) => {
+                obj.insert(target.to_string(), value);
+                target.to_string()
+            }
+            Some(FieldTarget::Tags(facet)) => {
+                let tags = obj.entry("tags").or_insert_with(|| Value::Object(Map::new()));
+                let list = tags
+                    .as_object_mut()
+                    .ok_or("tags is not a mapping")?
+                    .entry(facet)
+                    .or_insert_with(|| Value::Array(Vec::new()));
+                let list = list.as_array_mut().ok_or_else(|| format!("tags.{} is not a list", facet))?;
+                for tag in tag_values(&value) {
+                    if !list.iter().any(|v| v.as_str() == Some(tag.as_str())) {
+                        list.push(Value::String(tag));
+                    }
+                }
+                format!("tags.{}", facet)
+            }
+            _ => {
+                extra.insert(field.to_string(), value);
+                format!("{}.{}", EXTRA_KEY, field)
+            }
+        };
+        moves.push(FieldMove { field: field.to_string(), target });
+    }
+
+    if !extra.is_empty() {
+        let existing = obj.entry(EXTRA_KEY).or_insert_with(|| Value::Object(Map::new()));
+        let existing = existing.as_object_mut().ok_or("extra is not a mapping")?;
+        for (field, value) in extra {
+            existing.insert(field, value);
+        }
+    }
+    obj.insert("type".to_string(), Value::String(to_name));
+
+    let converted: Frontmatter = serde_json::from_value(Value::Object(obj)).map_err(|e| e.to_string())?;
+    let errors = FrontmatterParser::validate_with(&converted, schemas)?;
+    if !errors.is_empty() {
+        let messages: Vec<String> = errors.iter().map(|e| format!("{}: {}", e.path, e.message)).collect();
+        return Err(format!("Converted frontmatter is invalid: {}", messages.join("; ")));
+    }
+    Ok((converted, moves))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(yaml: &str) -> Frontmatter {
+        FrontmatterParser::parse_yaml(yaml).unwrap()
+    }
+
+    const BASE: &str = "id: \"20260101090000\"\ntitle: t\ncreated: \"2026-01-01\"\nmodified: \"2026-01-01\"\n";
+
+    #[test]
+    fn test_task_to_event_maps_due_and_assignee() {
+        let fm = parse(&format!("{}type: TASK\ndue: \"2026-03-01\"\nassignee: \"[[김]]\"\npriority: high\n", BASE));
+        let (converted, moves) = convert_frontmatter(&fm, NoteType::EVENT, &VaultSchemas::default()).unwrap();
+
+        assert_eq!(converted.note_type, NoteType::EVENT);
+        assert_eq!(converted.date.as_deref(), Some("2026-03-01"));
+        assert!(converted.due.is_none() && converted.assignee.is_none() && converted.priority.is_none());
+        assert_eq!(converted.tags.who, vec!["김"]);
+        assert_eq!(converted.extra["extra"]["priority"], serde_yaml::Value::from("high"), "규칙 없는 필드는 extra로");
+        assert_eq!(
+            moves,
+            vec![
+                FieldMove { field: "due".into(), target: "date".into() },
+                FieldMove { field: "priority".into(), target: "extra.priority".into() },
+                FieldMove { field: "assignee".into(), target: "tags.who".into() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shared_fields_kept_and_invalid_result_rejected() {
+        let fm = parse(&format!("{}type: MTG\ndate: \"2026-02-02\"\nparticipants: [김, 이]\n", BASE));
+        let (converted, moves) = convert_frontmatter(&fm, NoteType::EVENT, &VaultSchemas::default()).unwrap();
+        assert_eq!(converted.date.as_deref(), Some("2026-02-02"));
+        assert_eq!(converted.participants.unwrap(), vec!["김", "이"], "EVENT도 participants를 가짐");
+        assert!(moves.is_empty());
+
+        let (converted, _) = convert_frontmatter(&fm, NoteType::NOTE, &VaultSchemas::default()).unwrap();
+        assert!(converted.participants.is_none());
+        assert_eq!(converted.tags.who, vec!["김", "이"]);
+
+        let note = parse(&format!("{}type: NOTE\npriority: p1\n", BASE));
+        assert!(convert_frontmatter(&note, NoteType::TASK, &VaultSchemas::default()).unwrap_err().contains("invalid"));
+        assert!(convert_frontmatter(&note, NoteType::NOTE, &VaultSchemas::default()).is_err());
+    }
+}
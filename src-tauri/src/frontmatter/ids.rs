@@ -0,0 +1,107 @@
+//! Note id allocation
+//!
+//! Ids keep the 14-digit `%Y%m%d%H%M%S` form the schema requires. The allocator remembers
+//! every id it handed out (or a caller claimed) in this process, so notes created in the same
+//! second get the following free second instead of the same id. Callers pass the ids already
+//! used in the vault to avoid those as well.
+
+use chrono::{Duration, Local, NaiveDateTime, Timelike};
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+const ID_FORMAT: &str = "%Y%m%d%H%M%S";
+
+#[derive(Default)]
+struct Allocator {
+    last: Option<NaiveDateTime>,
+    issued: HashSet<String>,
+}
+
+static ALLOCATOR: Lazy<Mutex<Allocator>> = Lazy::new(|| Mutex::new(Allocator::default()));
+
+impl Allocator {
+    /// The next free second at or after now (and after the last id handed out)
+    fn next_free(&self, taken: &dyn Fn(&str) -> bool) -> (NaiveDateTime, String) {
+        let now = Local::now().naive_local();
+        let now = now.with_nanosecond(0).unwrap_or(now);
+        let mut at = match self.last {
+            Some(last) if last >= now => last + Duration::seconds(1),
+            _ => now,
+        };
+        loop {
+            let id = at.format(ID_FORMAT).to_string();
+            if !self.issued.contains(&id) && !taken(&id) {
+                return (at, id);
+            }
+            at += Duration::seconds(1);
+        }
+    }
+
+    fn allocate(&mut self, taken: &dyn Fn(&str) -> bool) -> String {
+        let (at, id) = self.next_free(taken);
+        self.last = Some(at);
+        self.issued.insert(id.clone());
+        id
+    }
+
+    fn is_free(&self, id: &str, taken: &dyn Fn(&str) -> bool) -> bool {
+        is_note_id(id) && !self.issued.contains(id) && !taken(id)
+    }
+}
+
+/// 14 ASCII digits, as the schema requires
+pub fn is_note_id(id: &str) -> bool {
+    id.len() == 14 && id.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// A new id (current second or the next free one) that `taken` does not report as used
+pub fn allocate_note_id(taken: impl Fn(&str) -> bool) -> String {
+    let mut allocator = ALLOCATOR.lock().unwrap_or_else(|e| e.into_inner());
+    allocator.allocate(&taken)
+}
+
+/// `id` if it is well-formed and unused, otherwise a newly allocated id.
+/// Either way the returned id is never handed out again in this process.
+pub fn claim_note_id(id: &str, taken: impl Fn(&str) -> bool) -> String {
+    let mut allocator = ALLOCATOR.lock().unwrap_or_else(|e| e.into_inner());
+    if allocator.is_free(id, &taken) {
+        allocator.issued.insert(id.to_string());
+        return id.to_string();
+    }
+    allocator.allocate(&taken)
+}
+
+/// What `claim_note_id` would return, without reserving anything (previews and dry runs)
+pub fn peek_note_id(id: &str, taken: impl Fn(&str) -> bool) -> String {
+    let allocator = ALLOCATOR.lock().unwrap_or_else(|e| e.into_inner());
+    if allocator.is_free(id, &taken) {
+        return id.to_string();
+    }
+    allocator.next_free(&taken).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_in_same_second_are_unique() {
+        let ids: Vec<String> = (0..50).map(|_| allocate_note_id(|_| false)).collect();
+        let unique: HashSet<&String> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len());
+        assert!(ids.iter().all(|id| is_note_id(id)));
+
+        let taken = allocate_note_id(|_| false);
+        let claimed = claim_note_id(&taken, |_| false);
+        assert_ne!(claimed, taken, "이미 발급된 id는 다시 쓰지 않음");
+        let next = allocate_note_id(|id| id == "99991231235959");
+        assert!(is_note_id(&next));
+        assert_ne!(claim_note_id("abc", |_| false), "abc");
+
+        let peeked = peek_note_id("", |_| false);
+        assert_eq!(peek_note_id("", |_| false), peeked, "미리보기는 예약하지 않음");
+        assert_eq!(claim_note_id("", |_| false), peeked);
+        assert_eq!(peek_note_id("20000101000000", |_| false), "20000101000000");
+    }
+}
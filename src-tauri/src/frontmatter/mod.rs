@@ -1,10 +1,15 @@
 pub mod types;
 pub mod schemas;
 pub mod suggestions;
+pub mod convert;
+pub mod ids;
+pub mod yaml_edit;
+pub mod workflow;
 
 use types::{Frontmatter, ValidationError};
-use schemas::get_schema_for_type;
+use schemas::{get_schema_for_type, VaultSchemas};
 use jsonschema::Validator;
+use crate::search::parser::split_frontmatter_raw;
 
 pub struct FrontmatterParser;
 
@@ -49,7 +54,6 @@ impl FrontmatterParser {
     }
 
     /// Combine frontmatter and body into markdown content
-    #[allow(dead_code)]
     pub fn combine(frontmatter: Option<&Frontmatter>, body: &str) -> Result<String, String> {
         match frontmatter {
             Some(fm) => {
@@ -60,6 +64,31 @@ impl FrontmatterParser {
         }
     }
 
+    /// Rewrite `content` (whose frontmatter parsed to `old`) so its frontmatter holds `new`.
+    /// Only fields that differ between `old` and `new` are touched in the file text.
+    pub fn update(content: &str, old: &Frontmatter, new: &Frontmatter) -> Result<String, String> {
+        let to_mapping = |fm: &Frontmatter| match serde_yaml::to_value(fm) {
+            Ok(serde_yaml::Value::Mapping(map)) => Ok(map),
+            Ok(_) => Err("Frontmatter is not a mapping".to_string()),
+            Err(e) => Err(format!("Failed to serialize frontmatter: {}", e)),
+        };
+        let (old, new) = (to_mapping(old)?, to_mapping(new)?);
+
+        let (yaml, _) = split_frontmatter_raw(content).ok_or("Note has no frontmatter")?;
+        let mut raw = match serde_yaml::from_str::<serde_yaml::Value>(yaml) {
+            Ok(serde_yaml::Value::Mapping(map)) => map,
+            Ok(_) => serde_yaml::Mapping::new(),
+            Err(e) => return Err(format!("Failed to parse frontmatter: {}", e)),
+        };
+        raw.retain(|key, _| !old.contains_key(key) || new.contains_key(key));
+        for (key, value) in new {
+            if old.get(&key) != Some(&value) {
+                raw.insert(key, value);
+            }
+        }
+        yaml_edit::update_frontmatter_content(content, &raw)
+    }
+
     /// Validate frontmatter against JSON Schema
     pub fn validate(frontmatter: &Frontmatter) -> Result<Vec<ValidationError>, String> {
         // Convert frontmatter to JSON Value
@@ -68,15 +97,26 @@ impl FrontmatterParser {
 
         // Get appropriate schema based on note type
         let note_type = format!("{:?}", frontmatter.note_type);
-        let schema_value = get_schema_for_type(&note_type);
+        Self::validate_json(&json_value, get_schema_for_type(&note_type))
+    }
 
+    /// Validate frontmatter against the vault's effective schema for its type
+    /// (built-in schema plus `.notology/schemas` extensions)
+    pub fn validate_with(frontmatter: &Frontmatter, schemas: &VaultSchemas) -> Result<Vec<ValidationError>, String> {
+        let json_value = serde_json::to_value(frontmatter)
+            .map_err(|e| format!("Failed to convert frontmatter to JSON: {}", e))?;
+        let note_type = format!("{:?}", frontmatter.note_type);
+        Self::validate_json(&json_value, &schemas.schema_for_type(&note_type))
+    }
+
+    fn validate_json(json_value: &serde_json::Value, schema_value: &serde_json::Value) -> Result<Vec<ValidationError>, String> {
         // Compile schema
         let compiled_schema = Validator::new(schema_value)
             .map_err(|e| format!("Failed to compile schema: {}", e))?;
 
         // Validate
         let mut errors = Vec::new();
-        if let Err(validation_errors) = compiled_schema.validate(&json_value) {
+        if let Err(validation_errors) = compiled_schema.validate(json_value) {
             for error in validation_errors {
                 errors.push(ValidationError {
                     path: error.instance_path.to_string(),
@@ -127,6 +167,18 @@ This is the body content."#;
         assert_eq!(fm.title, "Test Note");
     }
 
+    #[test]
+    fn test_legacy_tags_survive_round_trip() {
+        let content = "---\ntitle: \"Old\"\ntype: NOTE\ncreated: \"2024-01-01T00:00:00\"\nmodified: \"2024-01-01T00:00:00\"\ntags: [ml, 2024]\n---\nBody";
+        let (frontmatter, body) = FrontmatterParser::parse(content).unwrap();
+        let fm = frontmatter.unwrap();
+        assert_eq!(fm.tags.legacy, vec!["ml", "2024"]);
+
+        let combined = FrontmatterParser::combine(Some(&fm), &body).unwrap();
+        let (reparsed, _) = FrontmatterParser::parse(&combined).unwrap();
+        assert_eq!(reparsed.unwrap().tags.legacy, vec!["ml", "2024"]);
+    }
+
     #[test]
     fn test_parse_without_frontmatter() {
         let content = "Just some markdown content.";
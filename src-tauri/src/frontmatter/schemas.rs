@@ -1,5 +1,10 @@
 use lazy_static::lazy_static;
 use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Allowed values of a TASK note's `priority`
+pub const TASK_PRIORITIES: [&str; 4] = ["low", "medium", "high", "urgent"];
 
 lazy_static! {
     pub static ref BASE_SCHEMA: serde_json::Value = json!({
@@ -84,122 +89,212 @@ lazy_static! {
             "cssclasses": {
                 "type": "array",
                 "items": { "type": "string" }
+            },
+            "canvas": {
+                "type": "boolean",
+                "description": "Note body is a canvas"
             }
         }
     });
 
-    pub static ref MTG_SCHEMA: serde_json::Value = {
-        let mut schema = BASE_SCHEMA.clone();
-        if let Some(obj) = schema.as_object_mut() {
-            if let Some(props) = obj.get_mut("properties") {
-                if let Some(props_obj) = props.as_object_mut() {
-                    props_obj.insert("participants".to_string(), json!({
-                        "type": "array",
-                        "items": { "type": "string" },
-                        "description": "Meeting participants"
-                    }));
-                    props_obj.insert("date".to_string(), json!({
-                        "type": "string",
-                        "description": "Meeting date"
-                    }));
-                }
-            }
-        }
-        schema
+    /// Built-in schema of every `NoteType`: the base schema plus the type's own fields
+    static ref BUILTIN_SCHEMAS: HashMap<&'static str, serde_json::Value> = {
+        let date = json!({ "type": "string", "description": "Date (YYYY-MM-DD)" });
+        let people = json!({ "type": "array", "items": { "type": "string" } });
+        let url = json!({ "type": "string", "description": "Source URL" });
+
+        let types = [
+            ("NOTE", json!({})),
+            ("MTG", json!({
+                "participants": { "type": "array", "items": { "type": "string" }, "description": "Meeting participants" },
+                "date": { "type": "string", "description": "Meeting date" }
+            })),
+            ("PAPER", json!({
+                "authors": { "type": "array", "items": { "type": "string" }, "description": "Paper authors" },
+                "venue": { "type": "string", "description": "Publication venue" },
+                "year": { "type": "integer", "minimum": 1900, "maximum": 2100, "description": "Publication year" },
+                "doi": { "type": "string", "description": "DOI identifier" },
+                "url": { "type": "string", "description": "Paper URL" }
+            })),
+            ("THEO", json!({})),
+            ("TASK", json!({
+                "due": { "type": "string", "description": "Task due date" },
+                "priority": { "type": "string", "enum": TASK_PRIORITIES, "description": "Task priority" },
+                "assignee": { "type": "string", "description": "Person assigned to task" }
+            })),
+            ("LIT", json!({
+                "authors": people,
+                "year": { "type": "integer", "minimum": 1000, "maximum": 2100, "description": "Publication year" },
+                "publisher": { "type": "string", "description": "Publisher" },
+                "source": { "type": "string", "description": "Where the literature comes from" },
+                "url": url
+            })),
+            ("EVENT", json!({
+                "date": { "type": "string", "description": "Event date" },
+                "location": { "type": "string", "description": "Event location" },
+                "organizer": { "type": "string", "description": "Event organizer" },
+                "participants": people
+            })),
+            ("CONTACT", json!({
+                "email": { "type": "string", "description": "Contact email" },
+                "phone": { "type": "string", "description": "Phone number" },
+                "organization": { "type": "string", "description": "Organization name" },
+                "role": { "type": "string", "description": "Role or position" }
+            })),
+            ("CONTAINER", json!({})),
+            ("ADM", json!({})),
+            ("OFA", json!({
+                "date": date,
+                "organization": { "type": "string", "description": "Issuing or receiving organization" }
+            })),
+            ("SEM", json!({
+                "date": { "type": "string", "description": "Seminar date" },
+                "participants": people
+            })),
+            ("DATA", json!({
+                "source": { "type": "string", "description": "Dataset source" },
+                "url": url
+            })),
+            ("SETUP", json!({})),
+            ("SKETCH", json!({})),
+        ];
+
+        types
+            .into_iter()
+            .map(|(note_type, properties)| (note_type, with_properties(note_type, properties)))
+            .collect()
     };
+}
 
-    pub static ref PAPER_SCHEMA: serde_json::Value = {
-        let mut schema = BASE_SCHEMA.clone();
-        if let Some(obj) = schema.as_object_mut() {
-            if let Some(props) = obj.get_mut("properties") {
-                if let Some(props_obj) = props.as_object_mut() {
-                    props_obj.insert("authors".to_string(), json!({
-                        "type": "array",
-                        "items": { "type": "string" },
-                        "description": "Paper authors"
-                    }));
-                    props_obj.insert("venue".to_string(), json!({
-                        "type": "string",
-                        "description": "Publication venue"
-                    }));
-                    props_obj.insert("year".to_string(), json!({
-                        "type": "integer",
-                        "minimum": 1900,
-                        "maximum": 2100,
-                        "description": "Publication year"
-                    }));
-                    props_obj.insert("doi".to_string(), json!({
-                        "type": "string",
-                        "description": "DOI identifier"
-                    }));
-                    props_obj.insert("url".to_string(), json!({
-                        "type": "string",
-                        "description": "Paper URL"
-                    }));
-                }
-            }
+/// Base schema with `properties` added, titled after the note type
+fn with_properties(note_type: &str, properties: serde_json::Value) -> serde_json::Value {
+    let mut schema = BASE_SCHEMA.clone();
+    schema["title"] = json!(format!("{} Note Schema", note_type));
+    if let (Some(props), Some(extra)) = (schema["properties"].as_object_mut(), properties.as_object()) {
+        for (name, property) in extra {
+            props.insert(name.clone(), property.clone());
         }
-        schema
-    };
+    }
+    schema
+}
 
-    pub static ref TASK_SCHEMA: serde_json::Value = {
-        let mut schema = BASE_SCHEMA.clone();
-        if let Some(obj) = schema.as_object_mut() {
-            if let Some(props) = obj.get_mut("properties") {
-                if let Some(props_obj) = props.as_object_mut() {
-                    props_obj.insert("due".to_string(), json!({
-                        "type": "string",
-                        "description": "Task due date"
-                    }));
-                    props_obj.insert("priority".to_string(), json!({
-                        "type": "string",
-                        "enum": ["low", "medium", "high", "urgent"],
-                        "description": "Task priority"
-                    }));
-                    props_obj.insert("assignee".to_string(), json!({
-                        "type": "string",
-                        "description": "Person assigned to task"
-                    }));
-                }
-            }
+pub fn get_schema_for_type(note_type: &str) -> &'static serde_json::Value {
+    BUILTIN_SCHEMAS.get(note_type).unwrap_or(&BASE_SCHEMA)
+}
+
+/// Note types a vault schema applies to ("*" = all); defaults to the file stem if it names a
+/// note type, otherwise all types
+pub const SCHEMA_TYPES_KEY: &str = "x-notology-types";
+/// "extend" (default): validated together with the built-in schema; "replace": used instead
+pub const SCHEMA_MODE_KEY: &str = "x-notology-mode";
+
+#[derive(Debug, Clone)]
+struct VaultSchema {
+    types: Vec<String>,
+    replace: bool,
+    schema: serde_json::Value,
+}
+
+impl VaultSchema {
+    fn applies_to(&self, note_type: &str) -> bool {
+        self.types.iter().any(|t| t == "*" || t.eq_ignore_ascii_case(note_type))
+    }
+}
+
+/// User-defined schemas and schema extensions from `.notology/schemas/*.json`
+#[derive(Debug, Clone, Default)]
+pub struct VaultSchemas {
+    schemas: Vec<VaultSchema>,
+}
+
+pub fn schemas_dir(vault_path: &Path) -> PathBuf {
+    vault_path.join(".notology").join("schemas")
+}
+
+impl VaultSchemas {
+    /// Load every schema file, in file name order. A file that is not valid JSON Schema
+    /// fails the whole load so a broken team rule is reported rather than ignored.
+    pub fn load(vault_path: &Path) -> Result<Self, String> {
+        let dir = schemas_dir(vault_path);
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            return Ok(Self::default());
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+            .collect();
+        files.sort();
+
+        let mut schemas = Vec::new();
+        for file in files {
+            let name = file.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let json = std::fs::read_to_string(&file).map_err(|e| format!("schemas/{}: {}", name, e))?;
+            let schema: serde_json::Value =
+                serde_json::from_str(&json).map_err(|e| format!("schemas/{}: {}", name, e))?;
+            schemas.push(Self::parse_schema(&name, schema).map_err(|e| format!("schemas/{}: {}", name, e))?);
         }
-        schema
-    };
+        Ok(Self { schemas })
+    }
 
-    pub static ref CONTACT_SCHEMA: serde_json::Value = {
-        let mut schema = BASE_SCHEMA.clone();
-        if let Some(obj) = schema.as_object_mut() {
-            if let Some(props) = obj.get_mut("properties") {
-                if let Some(props_obj) = props.as_object_mut() {
-                    props_obj.insert("email".to_string(), json!({
-                        "type": "string",
-                        "description": "Contact email"
-                    }));
-                    props_obj.insert("phone".to_string(), json!({
-                        "type": "string",
-                        "description": "Phone number"
-                    }));
-                    props_obj.insert("organization".to_string(), json!({
-                        "type": "string",
-                        "description": "Organization name"
-                    }));
-                    props_obj.insert("role".to_string(), json!({
-                        "type": "string",
-                        "description": "Role or position"
-                    }));
-                }
+    fn parse_schema(file_name: &str, schema: serde_json::Value) -> Result<VaultSchema, String> {
+        if !schema.is_object() {
+            return Err("schema must be an object".to_string());
+        }
+        jsonschema::Validator::new(&schema).map_err(|e| e.to_string())?;
+
+        let stem = file_name.trim_end_matches(".json").to_uppercase();
+        let types = match schema.get(SCHEMA_TYPES_KEY) {
+            Some(value) => serde_json::from_value::<Vec<String>>(value.clone())
+                .map_err(|_| format!("{} must be a list of note types", SCHEMA_TYPES_KEY))?,
+            None if BUILTIN_SCHEMAS.contains_key(stem.as_str()) => vec![stem],
+            None => vec!["*".to_string()],
+        };
+        let replace = match schema.get(SCHEMA_MODE_KEY).and_then(|m| m.as_str()) {
+            None | Some("extend") => false,
+            Some("replace") => true,
+            Some(other) => return Err(format!("unknown {}: {}", SCHEMA_MODE_KEY, other)),
+        };
+        Ok(VaultSchema { types, replace, schema })
+    }
+
+    /// Effective schema of `note_type`: the built-in (or last replacing) schema combined
+    /// with every extension that follows it via `allOf`
+    pub fn schema_for_type(&self, note_type: &str) -> serde_json::Value {
+        let mut base = get_schema_for_type(note_type).clone();
+        let mut extensions = Vec::new();
+        for vault_schema in self.schemas.iter().filter(|s| s.applies_to(note_type)) {
+            if vault_schema.replace {
+                base = vault_schema.schema.clone();
+                extensions.clear();
+            } else {
+                extensions.push(vault_schema.schema.clone());
             }
         }
-        schema
-    };
+        if extensions.is_empty() {
+            return base;
+        }
+        let mut all_of = vec![base];
+        all_of.extend(extensions);
+        json!({ "allOf": all_of })
+    }
 }
 
-pub fn get_schema_for_type(note_type: &str) -> &'static serde_json::Value {
-    match note_type {
-        "MTG" => &MTG_SCHEMA,
-        "PAPER" => &PAPER_SCHEMA,
-        "TASK" => &TASK_SCHEMA,
-        "CONTACT" => &CONTACT_SCHEMA,
-        _ => &BASE_SCHEMA,
+/// Property names declared by `schema`, including those of `allOf` parts
+pub fn schema_properties(schema: &serde_json::Value) -> Vec<String> {
+    let mut names: Vec<String> = schema
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .map(|p| p.keys().cloned().collect())
+        .unwrap_or_default();
+    if let Some(parts) = schema.get("allOf").and_then(|a| a.as_array()) {
+        for part in parts {
+            for name in schema_properties(part) {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
     }
+    names
 }
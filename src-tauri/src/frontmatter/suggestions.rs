@@ -1,5 +1,6 @@
-use super::types::{Frontmatter, RelationType, WorkflowState, ConfidenceState};
-use std::collections::HashMap;
+use super::types::{Frontmatter, RelationType, ConfidenceState};
+use super::workflow::{workflow_name, StateView, WorkflowConfig};
+use crate::search::suggest::SuggestionEvidence;
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,6 +9,9 @@ pub struct Suggestion {
     pub confidence: f32,
     pub description: String,
     pub action: SuggestionAction,
+    /// Why the suggestion was made; empty for frontmatter-only checks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reasons: Vec<SuggestionReason>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,27 +45,46 @@ pub enum SuggestionAction {
     },
 }
 
+/// Evidence behind a content-aware suggestion
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum SuggestionReason {
+    /// Distinctive body terms both notes use; similarity is the share of this note's term weight
+    SharedTerms { terms: Vec<String>, similarity: f32 },
+    /// Titles of notes both link to
+    SharedLinks { notes: Vec<String> },
+    LinksTo,
+    LinkedFrom,
+    SharedTags { tags: Vec<String> },
+    /// `together` of the `with_count` notes tagged `with_tag` also carry the suggested tag
+    TagCooccurrence { with_tag: String, together: usize, with_count: usize },
+}
+
 pub struct SuggestionEngine;
 
 impl SuggestionEngine {
-    /// Generate suggestions for a frontmatter
+    /// Generate suggestions for a note from its frontmatter (None if it has no valid
+    /// frontmatter) and the index's evidence for it
     pub fn generate_suggestions(
-        frontmatter: &Frontmatter,
-        all_notes: &[Frontmatter],
+        frontmatter: Option<&Frontmatter>,
+        evidence: &SuggestionEvidence,
+        workflow: &WorkflowConfig,
     ) -> Vec<Suggestion> {
         let mut suggestions = Vec::new();
 
-        // State transition suggestions
-        suggestions.extend(Self::suggest_state_transitions(frontmatter));
+        if let Some(frontmatter) = frontmatter {
+            // State transition suggestions
+            suggestions.extend(Self::suggest_state_transitions(frontmatter, workflow));
 
-        // Tag co-occurrence suggestions
-        suggestions.extend(Self::suggest_tags_from_cooccurrence(frontmatter, all_notes));
+            // Quality improvement suggestions
+            suggestions.extend(Self::suggest_quality_improvements(frontmatter));
+        }
 
-        // Relation suggestions based on content patterns
-        suggestions.extend(Self::suggest_relations_from_patterns(frontmatter, all_notes));
+        // Tag co-occurrence suggestions
+        suggestions.extend(Self::suggest_tags_from_cooccurrence(evidence));
 
-        // Quality improvement suggestions
-        suggestions.extend(Self::suggest_quality_improvements(frontmatter));
+        // Relation suggestions based on content, links and tags
+        suggestions.extend(Self::suggest_relations(evidence));
 
         // Sort by confidence
         suggestions.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap());
@@ -69,58 +92,37 @@ impl SuggestionEngine {
         suggestions
     }
 
-    /// Suggest state transitions based on current state
-    fn suggest_state_transitions(frontmatter: &Frontmatter) -> Vec<Suggestion> {
+    /// Suggest state transitions whose guard and `suggest_when` thresholds (from the
+    /// vault's workflow rules) the note meets
+    fn suggest_state_transitions(frontmatter: &Frontmatter, workflow: &WorkflowConfig) -> Vec<Suggestion> {
         let mut suggestions = Vec::new();
+        let state = StateView::of(frontmatter);
+        let note_type = format!("{:?}", frontmatter.note_type);
+        let from = workflow_name(&frontmatter.state.workflow);
 
         // Workflow transitions
-        match frontmatter.state.workflow {
-            WorkflowState::Draft => {
-                if frontmatter.state.maturity >= 2 {
-                    suggestions.push(Suggestion {
-                        suggestion_type: SuggestionType::StateTransition,
-                        confidence: 0.8,
-                        description: "이 노트는 진행 중 상태로 전환할 준비가 되었습니다.".to_string(),
-                        action: SuggestionAction::UpdateState {
-                            field: "workflow".to_string(),
-                            value: "in-progress".to_string(),
-                        },
-                    });
-                }
-            }
-            WorkflowState::InProgress => {
-                if frontmatter.state.maturity >= 4 && frontmatter.state.confidence == ConfidenceState::Verified {
-                    suggestions.push(Suggestion {
-                        suggestion_type: SuggestionType::StateTransition,
-                        confidence: 0.85,
-                        description: "성숙도와 신뢰도가 높아 검토 단계로 이동할 수 있습니다.".to_string(),
-                        action: SuggestionAction::UpdateState {
-                            field: "workflow".to_string(),
-                            value: "review".to_string(),
-                        },
-                    });
-                }
-            }
-            WorkflowState::Review => {
-                if frontmatter.state.maturity == 5 {
-                    suggestions.push(Suggestion {
-                        suggestion_type: SuggestionType::StateTransition,
-                        confidence: 0.9,
-                        description: "최고 성숙도에 도달했습니다. 완료 상태로 전환하세요.".to_string(),
-                        action: SuggestionAction::UpdateState {
-                            field: "workflow".to_string(),
-                            value: "final".to_string(),
-                        },
-                    });
-                }
-            }
-            _ => {}
+        for transition in workflow.suggested(&note_type, from, &state) {
+            let (confidence, description) = match transition.to.as_str() {
+                "in-progress" => (0.8, "이 노트는 진행 중 상태로 전환할 준비가 되었습니다.".to_string()),
+                "review" => (0.85, "성숙도와 신뢰도가 높아 검토 단계로 이동할 수 있습니다.".to_string()),
+                "final" => (0.9, "최고 성숙도에 도달했습니다. 완료 상태로 전환하세요.".to_string()),
+                other => (0.75, format!("{} 상태로 전환할 수 있습니다.", other)),
+            };
+            suggestions.push(Suggestion {
+                suggestion_type: SuggestionType::StateTransition,
+                confidence,
+                description,
+                action: SuggestionAction::UpdateState {
+                    field: "workflow".to_string(),
+                    value: transition.to.clone(),
+                },
+                reasons: Vec::new(),
+            });
         }
 
         // Confidence transitions
-        if frontmatter.state.confidence == ConfidenceState::Unverified
-            && frontmatter.state.maturity >= 3
-            && !frontmatter.relations.is_empty() {
+        let ready_to_verify = workflow.verify_when.as_ref().is_some_and(|guard| guard.is_met(&state));
+        if frontmatter.state.confidence == ConfidenceState::Unverified && ready_to_verify {
             suggestions.push(Suggestion {
                 suggestion_type: SuggestionType::StateTransition,
                 confidence: 0.7,
@@ -129,140 +131,88 @@ impl SuggestionEngine {
                     field: "confidence".to_string(),
                     value: "verified".to_string(),
                 },
+                reasons: Vec::new(),
             });
         }
 
         suggestions
     }
 
-    /// Suggest tags based on co-occurrence analysis
-    fn suggest_tags_from_cooccurrence(
-        frontmatter: &Frontmatter,
-        all_notes: &[Frontmatter],
-    ) -> Vec<Suggestion> {
-        let mut suggestions = Vec::new();
-
-        // Get current tags
-        let current_tags = Self::get_all_tags(frontmatter);
-
-        // Count tag co-occurrences
-        let mut cooccurrence: HashMap<String, HashMap<String, usize>> = HashMap::new();
-
-        for note in all_notes {
-            let note_tags = Self::get_all_tags(note);
-
-            // For each pair of tags in this note
-            for tag1 in &note_tags {
-                for tag2 in &note_tags {
-                    if tag1 != tag2 {
-                        *cooccurrence
-                            .entry(tag1.clone())
-                            .or_insert_with(HashMap::new)
-                            .entry(tag2.clone())
-                            .or_insert(0) += 1;
-                    }
-                }
-            }
-        }
-
-        // Find tags that frequently co-occur with current tags
-        let mut tag_scores: HashMap<String, usize> = HashMap::new();
-        for current_tag in &current_tags {
-            if let Some(cooccur_map) = cooccurrence.get(current_tag) {
-                for (other_tag, count) in cooccur_map {
-                    if !current_tags.contains(other_tag) {
-                        *tag_scores.entry(other_tag.clone()).or_insert(0) += count;
-                    }
+    /// Suggest tags that most notes sharing one of this note's tags also carry
+    fn suggest_tags_from_cooccurrence(evidence: &SuggestionEvidence) -> Vec<Suggestion> {
+        evidence.tag_candidates
+            .iter()
+            .map(|candidate| {
+                let (facet, tag) = candidate.tag.split_once('/').unwrap_or(("domain", candidate.tag.as_str()));
+                Suggestion {
+                    suggestion_type: SuggestionType::TagSuggestion,
+                    confidence: (candidate.ratio() * 0.9).min(0.9),
+                    description: format!("이 태그는 현재 태그와 자주 함께 사용됩니다: {}", candidate.tag),
+                    action: SuggestionAction::AddTag {
+                        facet: facet.to_string(),
+                        tag: tag.to_string(),
+                    },
+                    reasons: vec![SuggestionReason::TagCooccurrence {
+                        with_tag: candidate.with_tag.clone(),
+                        together: candidate.together,
+                        with_count: candidate.with_count,
+                    }],
                 }
-            }
-        }
-
-        // Convert to suggestions (top 3)
-        let mut sorted_tags: Vec<_> = tag_scores.into_iter().collect();
-        sorted_tags.sort_by(|a, b| b.1.cmp(&a.1));
-
-        for (tag, score) in sorted_tags.iter().take(3) {
-            let confidence = ((*score as f32) / (all_notes.len() as f32)).min(0.9);
-
-            // Determine facet from tag prefix
-            let facet = tag.split('/').next().unwrap_or("domain").to_string();
-
-            suggestions.push(Suggestion {
-                suggestion_type: SuggestionType::TagSuggestion,
-                confidence,
-                description: format!("이 태그는 현재 태그와 자주 함께 사용됩니다: {}", tag),
-                action: SuggestionAction::AddTag {
-                    facet,
-                    tag: tag.clone(),
-                },
-            });
-        }
-
-        suggestions
+            })
+            .collect()
     }
 
-    /// Suggest relations based on content patterns
-    fn suggest_relations_from_patterns(
-        frontmatter: &Frontmatter,
-        all_notes: &[Frontmatter],
-    ) -> Vec<Suggestion> {
-        let mut suggestions = Vec::new();
-
-        // Pattern 1: Same domain tags -> extends relation
-        for other in all_notes {
-            if other.id == frontmatter.id {
-                continue;
-            }
-
-            let common_domain_tags = Self::get_common_domain_tags(frontmatter, other);
-            if common_domain_tags.len() >= 2 {
-                // Already has relation?
-                let has_relation = frontmatter.relations.iter()
-                    .any(|r| r.target == other.title);
-
-                if !has_relation {
-                    suggestions.push(Suggestion {
-                        suggestion_type: SuggestionType::RelationSuggestion,
-                        confidence: 0.6,
-                        description: format!(
-                            "'{}' 노트와 공통 분야 태그가 있습니다. 확장 관계를 고려하세요.",
-                            other.title
-                        ),
-                        action: SuggestionAction::AddRelation {
-                            relation_type: RelationType::Extends,
-                            target: other.title.clone(),
-                            strength: 0.5,
-                        },
+    /// Suggest relations to notes with similar content, links or tags
+    fn suggest_relations(evidence: &SuggestionEvidence) -> Vec<Suggestion> {
+        evidence.related
+            .iter()
+            .map(|related| {
+                let mut reasons = Vec::new();
+                if !related.shared_terms.is_empty() {
+                    reasons.push(SuggestionReason::SharedTerms {
+                        terms: related.shared_terms.clone(),
+                        similarity: related.term_similarity,
                     });
                 }
-            }
-        }
-
-        // Pattern 2: PAPER -> THEO (implements)
-        if frontmatter.note_type == super::types::NoteType::PAPER {
-            for other in all_notes {
-                if matches!(other.note_type, super::types::NoteType::THEO) {
-                    let common_tags = Self::get_common_domain_tags(frontmatter, other);
-                    if !common_tags.is_empty() {
-                        suggestions.push(Suggestion {
-                            suggestion_type: SuggestionType::RelationSuggestion,
-                            confidence: 0.7,
-                            description: format!(
-                                "이 논문은 이론 노트 '{}'를 구현할 수 있습니다.",
-                                other.title
-                            ),
-                            action: SuggestionAction::AddRelation {
-                                relation_type: RelationType::Implements,
-                                target: other.title.clone(),
-                                strength: 0.6,
-                            },
-                        });
-                    }
+                if !related.shared_links.is_empty() {
+                    reasons.push(SuggestionReason::SharedLinks { notes: related.shared_links.clone() });
+                }
+                if related.links_to {
+                    reasons.push(SuggestionReason::LinksTo);
+                }
+                if related.linked_from {
+                    reasons.push(SuggestionReason::LinkedFrom);
+                }
+                if !related.shared_tags.is_empty() {
+                    reasons.push(SuggestionReason::SharedTags { tags: related.shared_tags.clone() });
                 }
-            }
-        }
 
-        suggestions
+                // PAPER -> THEO (implements)
+                let implements = evidence.note_type.eq_ignore_ascii_case("PAPER")
+                    && related.note_type.eq_ignore_ascii_case("THEO");
+                let (relation_type, description) = if implements {
+                    (RelationType::Implements, format!("이 논문은 이론 노트 '{}'를 구현할 수 있습니다.", related.title))
+                } else {
+                    (RelationType::Extends, format!("'{}' 노트와 연관성이 높습니다. 확장 관계를 고려하세요.", related.title))
+                };
+                let target = std::path::Path::new(&related.path)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_else(|| related.title.clone());
+
+                Suggestion {
+                    suggestion_type: SuggestionType::RelationSuggestion,
+                    confidence: related.score.min(0.9),
+                    description,
+                    action: SuggestionAction::AddRelation {
+                        relation_type,
+                        target,
+                        strength: (related.score * 10.0).round().clamp(1.0, 10.0) / 10.0,
+                    },
+                    reasons,
+                }
+            })
+            .collect()
     }
 
     /// Suggest quality improvements
@@ -279,6 +229,7 @@ impl SuggestionEngine {
                     facet: "domain".to_string(),
                     tag: "".to_string(),
                 },
+                reasons: Vec::new(),
             });
         }
 
@@ -293,6 +244,7 @@ impl SuggestionEngine {
                     target: "".to_string(),
                     strength: 0.5,
                 },
+                reasons: Vec::new(),
             });
         }
 
@@ -311,15 +263,4 @@ impl SuggestionEngine {
         tags.extend(frontmatter.tags.status.clone());
         tags
     }
-
-    /// Get common domain tags between two frontmatters
-    fn get_common_domain_tags(fm1: &Frontmatter, fm2: &Frontmatter) -> Vec<String> {
-        let tags1 = &fm1.tags.domain;
-        let tags2 = &fm2.tags.domain;
-
-        tags1.iter()
-            .filter(|t| tags2.contains(t))
-            .cloned()
-            .collect()
-    }
 }
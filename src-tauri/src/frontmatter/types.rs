@@ -128,6 +128,9 @@ pub struct FacetedTags {
     pub method: Vec<String>,
     #[serde(default)]
     pub status: Vec<String>,
+    /// Legacy list-style tags not yet assigned to a facet (see `migrate_legacy_tags`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub legacy: Vec<String>,
 }
 
 impl Default for FacetedTags {
@@ -140,6 +143,7 @@ impl Default for FacetedTags {
             source: Vec::new(),
             method: Vec::new(),
             status: Vec::new(),
+            legacy: Vec::new(),
         }
     }
 }
@@ -159,24 +163,30 @@ where
         Value::Mapping(_) => {
             FacetedTags::deserialize(value).map_err(Error::custom)
         }
-        // Legacy format: simple array (ignore and return default)
-        Value::Sequence(_) => Ok(FacetedTags::default()),
+        // Legacy format: simple array, kept under `legacy` so re-serializing does not lose it
+        Value::Sequence(items) => Ok(FacetedTags {
+            legacy: items
+                .iter()
+                .filter_map(|v| match v {
+                    Value::String(s) => Some(s.clone()),
+                    Value::Number(n) => Some(n.to_string()),
+                    Value::Bool(b) => Some(b.to_string()),
+                    _ => None,
+                })
+                .collect(),
+            ..FacetedTags::default()
+        }),
         // Empty or null: return default
         Value::Null => Ok(FacetedTags::default()),
         _ => Err(Error::custom("Invalid tags format")),
     }
 }
 
-fn generate_note_id() -> String {
-    use chrono::Local;
-    let now = Local::now();
-    now.format("%Y%m%d%H%M%S").to_string()
-}
-
 /// Base frontmatter structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Frontmatter {
-    #[serde(default = "generate_note_id")]
+    /// Empty when the note has none; ids are allocated where notes are created, not on parse
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub id: String,
     pub title: String,
     #[serde(rename = "type")]
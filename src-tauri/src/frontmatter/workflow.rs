@@ -0,0 +1,324 @@
+//! Workflow state machine
+//!
+//! Allowed `state.workflow` transitions, their guard conditions and the thresholds at which a
+//! transition is suggested come from `.notology/workflow.json`, with per-note-type transition
+//! lists replacing the default list. Every accepted change is appended to the note's
+//! `state_history` with a timestamp and the machine's hostname.
+
+use super::types::{ConfidenceState, Frontmatter, WorkflowState};
+use crate::VaultJsonConfig;
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+
+pub const WORKFLOW_STATES: [&str; 5] = ["draft", "in-progress", "review", "final", "archived"];
+pub const CONFIDENCE_STATES: [&str; 4] = ["unverified", "verified", "outdated", "disputed"];
+/// Frontmatter key of the transition audit trail
+pub const HISTORY_KEY: &str = "state_history";
+/// Workflow of a note without one
+const INITIAL_STATE: &str = "draft";
+/// Matches any source state
+const ANY_STATE: &str = "*";
+
+/// Conditions on the note's state after the transition
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Guard {
+    /// Required `state.confidence`, e.g. "verified"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_maturity: Option<u8>,
+    /// At least one typed relation
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub requires_relations: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Transition {
+    /// Source states; "*" matches any
+    pub from: Vec<String>,
+    pub to: String,
+    #[serde(default)]
+    pub guard: Guard,
+    /// Suggest this transition once these conditions (and the guard) hold
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggest_when: Option<Guard>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WorkflowConfig {
+    /// Transitions of note types without their own list
+    #[serde(default = "default_transitions")]
+    pub transitions: Vec<Transition>,
+    /// Note type (e.g. "PAPER") -> transitions used instead of `transitions`
+    #[serde(default)]
+    pub types: BTreeMap<String, Vec<Transition>>,
+    /// When to suggest raising `confidence` from unverified to verified
+    #[serde(default = "default_verify_when")]
+    pub verify_when: Option<Guard>,
+}
+
+/// State a guard is checked against
+pub struct StateView<'a> {
+    pub confidence: &'a str,
+    pub maturity: u8,
+    pub relation_count: usize,
+}
+
+/// One `state_history` entry
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StateChange {
+    pub from: String,
+    pub to: String,
+    pub at: String,
+    pub host: String,
+}
+
+fn transition(from: &[&str], to: &str, guard: Guard, suggest_when: Option<Guard>) -> Transition {
+    Transition { from: from.iter().map(|s| s.to_string()).collect(), to: to.to_string(), guard, suggest_when }
+}
+
+fn at_least(maturity: u8) -> Guard {
+    Guard { min_maturity: Some(maturity), ..Guard::default() }
+}
+
+fn verified() -> Guard {
+    Guard { confidence: Some("verified".to_string()), ..Guard::default() }
+}
+
+fn default_transitions() -> Vec<Transition> {
+    vec![
+        transition(&["draft"], "in-progress", Guard::default(), Some(at_least(2))),
+        transition(&["in-progress"], "draft", Guard::default(), None),
+        transition(&["in-progress"], "review", Guard::default(), Some(Guard { min_maturity: Some(4), ..verified() })),
+        transition(&["review"], "in-progress", Guard::default(), None),
+        transition(&["review"], "final", verified(), Some(at_least(5))),
+        transition(&["final"], "in-progress", Guard::default(), None),
+        transition(&["final"], "archived", Guard::default(), None),
+        transition(&["archived"], "draft", Guard::default(), None),
+    ]
+}
+
+fn default_verify_when() -> Option<Guard> {
+    Some(Guard { min_maturity: Some(3), requires_relations: true, ..Guard::default() })
+}
+
+impl Default for WorkflowConfig {
+    fn default() -> Self {
+        Self { transitions: default_transitions(), types: BTreeMap::new(), verify_when: default_verify_when() }
+    }
+}
+
+impl Guard {
+    pub fn is_met(&self, state: &StateView) -> bool {
+        self.unmet(state).is_none()
+    }
+
+    /// The first unmet condition, if any
+    fn unmet(&self, state: &StateView) -> Option<String> {
+        if let Some(confidence) = self.confidence.as_deref().filter(|c| *c != state.confidence) {
+            return Some(format!("confidence: {}", confidence));
+        }
+        if let Some(min) = self.min_maturity.filter(|min| state.maturity < *min) {
+            return Some(format!("maturity >= {}", min));
+        }
+        if self.requires_relations && state.relation_count == 0 {
+            return Some("at least one relation".to_string());
+        }
+        None
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if let Some(confidence) = self.confidence.as_deref().filter(|c| !CONFIDENCE_STATES.contains(c)) {
+            return Err(format!("Unknown confidence state: {}", confidence));
+        }
+        if self.min_maturity.is_some_and(|m| !(1..=5).contains(&m)) {
+            return Err("min_maturity must be between 1 and 5".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl VaultJsonConfig for WorkflowConfig {
+    const FILE_NAME: &'static str = "workflow.json";
+
+    fn validate(&self) -> Result<(), String> {
+        for note_type in self.types.keys() {
+            if !crate::lint::NOTE_TYPES.contains(&note_type.as_str()) {
+                return Err(format!("Unknown note type: {}", note_type));
+            }
+        }
+        for transition in self.transitions.iter().chain(self.types.values().flatten()) {
+            for state in transition.from.iter().filter(|s| *s != ANY_STATE).chain([&transition.to]) {
+                if !WORKFLOW_STATES.contains(&state.as_str()) {
+                    return Err(format!("Unknown workflow state: {}", state));
+                }
+            }
+            transition.guard.validate()?;
+            if let Some(guard) = &transition.suggest_when {
+                guard.validate()?;
+            }
+        }
+        if let Some(guard) = &self.verify_when {
+            guard.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl WorkflowConfig {
+    pub fn transitions_for(&self, note_type: &str) -> &[Transition] {
+        self.types.get(&note_type.to_uppercase()).unwrap_or(&self.transitions)
+    }
+
+    /// Transitions out of `from` whose guard and `suggest_when` hold for `state`
+    pub fn suggested(&self, note_type: &str, from: &str, state: &StateView) -> Vec<&Transition> {
+        self.transitions_for(note_type)
+            .iter()
+            .filter(|t| t.from.iter().any(|s| s == from || s == ANY_STATE) && t.to != from)
+            .filter(|t| t.guard.is_met(state))
+            .filter(|t| t.suggest_when.as_ref().is_some_and(|guard| guard.is_met(state)))
+            .collect()
+    }
+
+    /// Ok if a `note_type` note may move from `from` to `to` with `state` after the change
+    pub fn check(&self, note_type: &str, from: &str, to: &str, state: &StateView) -> Result<(), String> {
+        if from == to {
+            return Ok(());
+        }
+        if !WORKFLOW_STATES.contains(&to) {
+            return Err(format!("Unknown workflow state: {}", to));
+        }
+        let candidates: Vec<&Transition> = self
+            .transitions_for(note_type)
+            .iter()
+            .filter(|t| t.to == to && t.from.iter().any(|s| s == from || s == ANY_STATE))
+            .collect();
+        if candidates.is_empty() {
+            return Err(format!("Workflow transition {} -> {} is not allowed for {} notes", from, to, note_type));
+        }
+        match candidates.iter().map(|t| t.guard.unmet(state)).find(|unmet| unmet.is_none()) {
+            Some(_) => Ok(()),
+            None => {
+                let unmet = candidates[0].guard.unmet(state).unwrap_or_default();
+                Err(format!("Workflow transition {} -> {} requires {}", from, to, unmet))
+            }
+        }
+    }
+}
+
+impl<'a> StateView<'a> {
+    pub fn of(frontmatter: &'a Frontmatter) -> Self {
+        Self {
+            confidence: confidence_name(&frontmatter.state.confidence),
+            maturity: frontmatter.state.maturity,
+            relation_count: frontmatter.relations.len(),
+        }
+    }
+}
+
+pub fn workflow_name(state: &WorkflowState) -> &'static str {
+    match state {
+        WorkflowState::Draft => "draft",
+        WorkflowState::InProgress => "in-progress",
+        WorkflowState::Review => "review",
+        WorkflowState::Final => "final",
+        WorkflowState::Archived => "archived",
+    }
+}
+
+pub fn confidence_name(state: &ConfidenceState) -> &'static str {
+    match state {
+        ConfidenceState::Unverified => "unverified",
+        ConfidenceState::Verified => "verified",
+        ConfidenceState::Outdated => "outdated",
+        ConfidenceState::Disputed => "disputed",
+    }
+}
+
+fn state_field<'a>(frontmatter: &'a Mapping, field: &str) -> Option<&'a Value> {
+    frontmatter.get("state").and_then(|state| state.get(field))
+}
+
+fn workflow_of(frontmatter: &Mapping) -> Option<String> {
+    state_field(frontmatter, "workflow").and_then(|w| w.as_str()).map(|w| w.trim().to_string())
+}
+
+/// Validate the workflow change between the frontmatter on disk (`old`) and the one about to
+/// be written (`new`), and carry `state_history` over from `old`, with the change appended.
+/// Returns the recorded change, if the workflow changed.
+pub fn record_transition(
+    old: &Mapping,
+    new: &mut Mapping,
+    config: &WorkflowConfig,
+    host: &str,
+    at: &str,
+) -> Result<Option<StateChange>, String> {
+    let mut history = match old.get(HISTORY_KEY) {
+        Some(Value::Sequence(entries)) => entries.clone(),
+        _ => Vec::new(),
+    };
+    let from = workflow_of(old).unwrap_or_else(|| INITIAL_STATE.to_string());
+    let change = match workflow_of(new) {
+        Some(to) if to != from => {
+            let note_type = new.get("type").and_then(|t| t.as_str()).unwrap_or("NOTE").to_uppercase();
+            let confidence = state_field(new, "confidence").and_then(|c| c.as_str()).unwrap_or("unverified");
+            let state = StateView {
+                confidence,
+                maturity: state_field(new, "maturity").and_then(|m| m.as_u64()).unwrap_or(1).min(u8::MAX as u64) as u8,
+                relation_count: new.get("relations").and_then(|r| r.as_sequence()).map_or(0, |r| r.len()),
+            };
+            config.check(&note_type, &from, &to, &state)?;
+            let change = StateChange { from, to, at: at.to_string(), host: host.to_string() };
+            history.push(serde_yaml::to_value(&change).map_err(|e| e.to_string())?);
+            Some(change)
+        }
+        _ => None,
+    };
+
+    if history.is_empty() {
+        new.remove(HISTORY_KEY);
+    } else {
+        new.insert(Value::from(HISTORY_KEY), Value::Sequence(history));
+    }
+    Ok(change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(confidence: &str, maturity: u8) -> StateView<'_> {
+        StateView { confidence, maturity, relation_count: 0 }
+    }
+
+    #[test]
+    fn test_default_transitions() {
+        let config = WorkflowConfig::default();
+        assert!(config.check("NOTE", "draft", "in-progress", &state("unverified", 1)).is_ok());
+        assert!(config.check("NOTE", "draft", "archived", &state("verified", 5)).unwrap_err().contains("not allowed"));
+        let err = config.check("NOTE", "review", "final", &state("unverified", 5)).unwrap_err();
+        assert!(err.contains("requires confidence: verified"), "{}", err);
+        assert!(config.check("NOTE", "review", "final", &state("verified", 2)).is_ok());
+        assert!(config.check("NOTE", "draft", "in_progress", &state("verified", 2)).unwrap_err().contains("Unknown"));
+        assert!(config.check("NOTE", "final", "final", &state("unverified", 1)).is_ok());
+
+        let suggested: Vec<&str> = config.suggested("NOTE", "in-progress", &state("verified", 4)).iter().map(|t| t.to.as_str()).collect();
+        assert_eq!(suggested, vec!["review"]);
+        assert!(config.suggested("NOTE", "in-progress", &state("unverified", 4)).is_empty());
+    }
+
+    #[test]
+    fn test_per_type_transitions() {
+        let json = r#"{"types": {"PAPER": [{"from": ["*"], "to": "archived"}, {"from": ["draft"], "to": "final", "guard": {"min_maturity": 3}}]}}"#;
+        let config: WorkflowConfig = serde_json::from_str(json).unwrap();
+        config.validate().unwrap();
+        assert!(config.check("paper", "draft", "archived", &state("unverified", 1)).is_ok());
+        assert!(config.check("PAPER", "draft", "final", &state("unverified", 2)).unwrap_err().contains("maturity >= 3"));
+        assert!(config.check("PAPER", "draft", "in-progress", &state("unverified", 2)).is_err(), "타입별 목록이 기본 목록을 대체");
+        assert!(config.check("NOTE", "draft", "in-progress", &state("unverified", 2)).is_ok());
+
+        let bad: WorkflowConfig = serde_json::from_str(r#"{"transitions": [{"from": ["draft"], "to": "done"}]}"#).unwrap();
+        assert!(bad.validate().is_err());
+    }
+}
@@ -0,0 +1,451 @@
+//! Format-preserving frontmatter editing
+//!
+//! Applies a new YAML mapping to existing frontmatter text by rewriting only the entries
+//! whose value changed. Comments, key order, quoting style and every untouched line stay
+//! byte-identical; nested block mappings (`state`, `tags`) are edited per key. Handles the
+//! block-style YAML notes use; anything else returns an error so callers can fall back to
+//! full re-serialization.
+
+use crate::search::parser::split_frontmatter_raw;
+use serde_yaml::{Mapping, Value};
+
+/// One top-level (or nested) entry of a block mapping and its lines
+#[derive(Debug)]
+struct Entry {
+    key: String,
+    lines: Vec<String>,
+}
+
+#[derive(Debug)]
+enum Segment {
+    /// Blank line, comment, or anything that is not part of an entry
+    Other(String),
+    Entry(Entry),
+}
+
+/// How an existing value is written
+#[derive(Debug, Clone, PartialEq)]
+enum ValueForm {
+    /// Value on the key line (scalar, flow collection or block scalar header)
+    Inline(String),
+    BlockSeq(usize),
+    BlockMap(usize),
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_blank_or_comment(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+/// Key of a mapping entry line (indentation removed) and the byte offset after its colon
+fn parse_key(text: &str) -> Option<(String, usize)> {
+    if text.starts_with('-') && (text.len() == 1 || text[1..].starts_with(' ')) {
+        return None;
+    }
+    if text.starts_with('#') || text.starts_with('?') {
+        return None;
+    }
+    let (key, colon) = if text.starts_with('"') || text.starts_with('\'') {
+        let quote = text.as_bytes()[0];
+        let mut end = None;
+        let bytes = text.as_bytes();
+        let mut i = 1;
+        while i < bytes.len() {
+            if quote == b'"' && bytes[i] == b'\\' {
+                i += 2;
+                continue;
+            }
+            if bytes[i] == quote {
+                if quote == b'\'' && bytes.get(i + 1) == Some(&b'\'') {
+                    i += 2;
+                    continue;
+                }
+                end = Some(i);
+                break;
+            }
+            i += 1;
+        }
+        let end = end?;
+        let key: String = serde_yaml::from_str(&text[..=end]).ok()?;
+        (key, end + 1)
+    } else {
+        let colon = text.match_indices(':').map(|(i, _)| i).find(|&i| {
+            let rest = &text[i + 1..];
+            rest.is_empty() || rest.starts_with(' ')
+        })?;
+        (text[..colon].trim_end().to_string(), colon)
+    };
+    if !text[colon..].starts_with(':') || key.is_empty() {
+        return None;
+    }
+    Some((key, colon + 1))
+}
+
+/// Split `lines` of a block mapping at `indent` into entries and other lines.
+/// Comments and blank lines after an entry belong to what follows it.
+fn segments(lines: &[String], indent: usize) -> Result<Vec<Segment>, String> {
+    let mut out = Vec::new();
+    let mut current: Option<Entry> = None;
+
+    let close = |entry: Entry, out: &mut Vec<Segment>| {
+        let mut lines = entry.lines;
+        let mut trailing = Vec::new();
+        while lines.len() > 1 {
+            let last = lines.last().unwrap();
+            if is_blank_or_comment(last) && (last.trim().is_empty() || indent_of(last) <= indent) {
+                trailing.push(lines.pop().unwrap());
+            } else {
+                break;
+            }
+        }
+        out.push(Segment::Entry(Entry { key: entry.key, lines }));
+        out.extend(trailing.into_iter().rev().map(Segment::Other));
+    };
+
+    for line in lines {
+        let starts_entry = !is_blank_or_comment(line) && indent_of(line) == indent;
+        if starts_entry {
+            if let Some((key, _)) = parse_key(&line[indent..]) {
+                if let Some(entry) = current.take() {
+                    close(entry, &mut out);
+                }
+                current = Some(Entry { key, lines: vec![line.clone()] });
+                continue;
+            }
+        }
+        match current.as_mut() {
+            Some(entry) => entry.lines.push(line.clone()),
+            None if is_blank_or_comment(line) => out.push(Segment::Other(line.clone())),
+            None => return Err(format!("Unsupported YAML line: {}", line)),
+        }
+        if !is_blank_or_comment(line) && indent_of(line) < indent {
+            return Err(format!("Unexpected indentation: {}", line));
+        }
+    }
+    if let Some(entry) = current.take() {
+        close(entry, &mut out);
+    }
+    Ok(out)
+}
+
+/// Position where a trailing `# comment` starts in `value` (quote-aware), if any
+fn comment_start(value: &str) -> Option<usize> {
+    let bytes = value.as_bytes();
+    let mut quote: Option<u8> = None;
+    for (i, &b) in bytes.iter().enumerate() {
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'"' || b == b'\'' => quote = Some(b),
+            None if b == b'#' && (i == 0 || bytes[i - 1] == b' ') => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+/// (key part incl. colon, value text, comment with its leading spaces)
+fn split_key_line(line: &str, indent: usize) -> (String, String, String) {
+    let (_, after) = parse_key(&line[indent..]).unwrap_or_default();
+    let key_part = &line[..indent + after];
+    let rest = &line[indent + after..];
+    match comment_start(rest) {
+        Some(pos) => {
+            let value = rest[..pos].trim_end();
+            let comment_from = value.len();
+            (key_part.to_string(), value.trim().to_string(), rest[comment_from..].to_string())
+        }
+        None => (key_part.to_string(), rest.trim().to_string(), String::new()),
+    }
+}
+
+fn value_form(entry: &Entry, indent: usize) -> ValueForm {
+    let (_, value, _) = split_key_line(&entry.lines[0], indent);
+    if !value.is_empty() {
+        return ValueForm::Inline(value);
+    }
+    match entry.lines[1..].iter().find(|l| !is_blank_or_comment(l)) {
+        Some(child) if child.trim_start().starts_with('-') => ValueForm::BlockSeq(indent_of(child)),
+        Some(child) => ValueForm::BlockMap(indent_of(child)),
+        None => ValueForm::Inline(String::new()),
+    }
+}
+
+/// A plain scalar that reads back as the same string
+fn plain_safe(s: &str, in_flow: bool) -> bool {
+    if s.is_empty() || s.trim() != s || s.contains('\n') || s.contains(" #") {
+        return false;
+    }
+    if in_flow && s.contains([',', '[', ']', '{', '}']) {
+        return false;
+    }
+    matches!(serde_yaml::from_str::<Value>(s), Ok(Value::String(parsed)) if parsed == s)
+}
+
+/// Render a scalar, keeping the quoting style (`'`, `"` or plain) of the value it replaces
+fn render_scalar(value: &Value, style: Option<char>, in_flow: bool) -> Result<String, String> {
+    match value {
+        Value::String(s) => Ok(match style {
+            Some('\'') if !s.contains('\n') => format!("'{}'", s.replace('\'', "''")),
+            Some('"') => serde_json::to_string(s).map_err(|e| e.to_string())?,
+            _ if plain_safe(s, in_flow) => s.clone(),
+            _ => serde_json::to_string(s).map_err(|e| e.to_string())?,
+        }),
+        Value::Null => Ok("null".to_string()),
+        Value::Bool(_) | Value::Number(_) => {
+            Ok(serde_yaml::to_string(value).map_err(|e| e.to_string())?.trim_end().to_string())
+        }
+        _ => Err("Not a scalar".to_string()),
+    }
+}
+
+fn quote_style(text: &str) -> Option<char> {
+    text.chars().next().filter(|c| *c == '"' || *c == '\'')
+}
+
+fn is_scalar(value: &Value) -> bool {
+    matches!(value, Value::String(_) | Value::Number(_) | Value::Bool(_) | Value::Null)
+}
+
+/// Lines of an entry `key_part` (e.g. "  title:") holding `value`.
+/// `old` is the form of the value being replaced, if any.
+fn render_entry(
+    key_part: &str,
+    comment: &str,
+    indent: usize,
+    value: &Value,
+    old: Option<&ValueForm>,
+) -> Result<Vec<String>, String> {
+    let old_inline = match old {
+        Some(ValueForm::Inline(text)) => Some(text.as_str()),
+        _ => None,
+    };
+
+    if is_scalar(value) {
+        let style = old_inline.and_then(quote_style);
+        let text = match (value, old_inline) {
+            (Value::Null, Some("")) => String::new(),
+            _ => render_scalar(value, style, false)?,
+        };
+        let sep = if text.is_empty() { "" } else { " " };
+        return Ok(vec![format!("{}{}{}{}", key_part, sep, text, comment)]);
+    }
+
+    if let Value::Sequence(items) = value {
+        let all_scalars = items.iter().all(is_scalar);
+        let flow = old_inline.is_some_and(|t| t.starts_with('[')) || items.is_empty();
+        if all_scalars && flow {
+            let style = old_inline.and_then(|t| quote_style(t.trim_start_matches('[').trim_start()));
+            let rendered = items
+                .iter()
+                .map(|item| render_scalar(item, style, true))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(vec![format!("{} [{}]{}", key_part, rendered.join(", "), comment)]);
+        }
+        if all_scalars {
+            let child = match old {
+                Some(ValueForm::BlockSeq(child)) => *child,
+                _ => indent + 2,
+            };
+            let mut lines = vec![format!("{}{}", key_part, comment)];
+            for item in items {
+                lines.push(format!("{}- {}", " ".repeat(child), render_scalar(item, None, false)?));
+            }
+            return Ok(lines);
+        }
+    }
+
+    if let Value::Mapping(map) = value {
+        if map.is_empty() {
+            return Ok(vec![format!("{} {{}}{}", key_part, comment)]);
+        }
+    }
+
+    let child = match old {
+        Some(ValueForm::BlockSeq(child)) | Some(ValueForm::BlockMap(child)) if *child > indent => *child,
+        _ => indent + 2,
+    };
+    let yaml = serde_yaml::to_string(value).map_err(|e| e.to_string())?;
+    let mut lines = vec![format!("{}{}", key_part, comment)];
+    lines.extend(yaml.trim_end().lines().map(|l| format!("{}{}", " ".repeat(child), l)));
+    Ok(lines)
+}
+
+fn key_text(key: &str) -> Result<String, String> {
+    render_scalar(&Value::String(key.to_string()), None, false)
+}
+
+/// Rewrite the block mapping in `lines` (at `indent`) from `old` to `new`
+fn edit_block(lines: &[String], indent: usize, old: &Mapping, new: &Mapping) -> Result<Vec<String>, String> {
+    let segments = segments(lines, indent)?;
+    let mut seen = Vec::new();
+    let mut out = Vec::new();
+    let mut last_entry_end = 0;
+
+    for segment in segments {
+        let entry = match segment {
+            Segment::Other(line) => {
+                out.push(line);
+                continue;
+            }
+            Segment::Entry(entry) => entry,
+        };
+        if seen.contains(&entry.key) {
+            return Err(format!("Duplicate key: {}", entry.key));
+        }
+        seen.push(entry.key.clone());
+
+        let old_value = old.get(entry.key.as_str());
+        match new.get(entry.key.as_str()) {
+            None => {}
+            Some(value) if Some(value) == old_value => out.extend(entry.lines),
+            Some(value) => {
+                let form = value_form(&entry, indent);
+                match (&form, old_value, value) {
+                    (ValueForm::BlockMap(child), Some(Value::Mapping(old_map)), Value::Mapping(new_map)) => {
+                        out.push(entry.lines[0].clone());
+                        out.extend(edit_block(&entry.lines[1..], *child, old_map, new_map)?);
+                    }
+                    _ => {
+                        let (key_part, _, comment) = split_key_line(&entry.lines[0], indent);
+                        out.extend(render_entry(&key_part, &comment, indent, value, Some(&form))?);
+                    }
+                }
+            }
+        }
+        last_entry_end = out.len();
+    }
+
+    let mut added = Vec::new();
+    for (key, value) in new {
+        let key = key.as_str().ok_or("Only string keys are supported")?;
+        if seen.iter().any(|k| k == key) {
+            continue;
+        }
+        if old.contains_key(key) {
+            return Err(format!("Key not found in text: {}", key));
+        }
+        let key_part = format!("{}{}:", " ".repeat(indent), key_text(key)?);
+        added.extend(render_entry(&key_part, "", indent, value, None)?);
+    }
+    if seen.len() != old.len() {
+        return Err("Frontmatter text and mapping differ".to_string());
+    }
+    let insert_at = if last_entry_end == 0 { out.len() } else { last_entry_end };
+    out.splice(insert_at..insert_at, added);
+    Ok(out)
+}
+
+/// Rewrite frontmatter `yaml` (the text between the `---` lines) so it holds `new`,
+/// changing only entries whose value differs.
+pub fn update_yaml(yaml: &str, new: &Mapping) -> Result<String, String> {
+    let old: Mapping = match serde_yaml::from_str::<Value>(yaml).map_err(|e| e.to_string())? {
+        Value::Mapping(map) => map,
+        Value::Null => Mapping::new(),
+        _ => return Err("Frontmatter is not a mapping".to_string()),
+    };
+    if &old == new {
+        return Ok(yaml.to_string());
+    }
+
+    let eol = if yaml.contains("\r\n") { "\r\n" } else { "\n" };
+    // With CRLF the text before "\n---" ends in '\r'
+    let (text, tail) = match yaml.strip_suffix('\r') {
+        Some(text) if eol == "\r\n" => (text, "\r"),
+        _ => (yaml, ""),
+    };
+    let lines: Vec<String> = text.split(eol).map(str::to_string).collect();
+    let edited = edit_block(&lines, 0, &old, new)?;
+    let updated = format!("{}{}", edited.join(eol), tail);
+
+    // Never write something that does not read back as `new`
+    match serde_yaml::from_str::<Value>(&updated) {
+        Ok(Value::Mapping(check)) if &check == new => Ok(updated),
+        _ => Err("Edited frontmatter does not round-trip".to_string()),
+    }
+}
+
+/// Replace the frontmatter of `content` with `new`, keeping the text of unchanged entries
+/// and the body. Falls back to full serialization when the text cannot be edited in place.
+pub fn update_frontmatter_content(content: &str, new: &Mapping) -> Result<String, String> {
+    let (yaml, rest) = split_frontmatter_raw(content).ok_or("Note has no frontmatter")?;
+
+    match update_yaml(yaml, new) {
+        Ok(updated) => Ok(format!("---{}{}", updated, rest)),
+        Err(e) => {
+            log::debug!("[yaml_edit] Falling back to full serialization: {}", e);
+            let yaml = serde_yaml::to_string(new).map_err(|e| e.to_string())?;
+            Ok(format!("---\n{}{}", yaml.trim_end(), rest))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YAML: &str = "\n# 회의 메모\nid: \"20260101090000\"\ntitle: '분기 회의'   # 제목\ntype: MTG\nstate:\n  workflow: draft\n  # 성숙도\n  maturity: 1\ntags:\n  domain: [기획, \"예산\"]\n  who:\n    - 김\nparticipants:\n- 김\ncustom: 값";
+
+    fn with(changes: &[(&str, Value)]) -> Mapping {
+        let mut map: Mapping = serde_yaml::from_str(YAML).unwrap();
+        for (path, value) in changes {
+            let mut keys = path.split('.').collect::<Vec<_>>();
+            let last = keys.pop().unwrap();
+            let mut target = &mut map;
+            for key in keys {
+                target = target.get_mut(key).unwrap().as_mapping_mut().unwrap();
+            }
+            if value.is_null() {
+                target.remove(last);
+            } else {
+                target.insert(Value::from(last), value.clone());
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn test_unchanged_is_byte_identical() {
+        assert_eq!(update_yaml(YAML, &with(&[])).unwrap(), YAML);
+    }
+
+    #[test]
+    fn test_only_changed_lines_are_rewritten() {
+        let new = with(&[
+            ("title", Value::from("새 제목")),
+            ("state.workflow", Value::from("in-progress")),
+            ("tags.domain", serde_yaml::from_str("[기획, 예산, 인사]").unwrap()),
+            ("tags.who", serde_yaml::from_str("[김, 이]").unwrap()),
+            ("modified", Value::from("2026-02-01T10:00:00+09:00")),
+        ]);
+        let updated = update_yaml(YAML, &new).unwrap();
+        assert_eq!(
+            updated,
+            "\n# 회의 메모\nid: \"20260101090000\"\ntitle: '새 제목'   # 제목\ntype: MTG\nstate:\n  workflow: in-progress\n  # 성숙도\n  maturity: 1\ntags:\n  domain: [기획, 예산, 인사]\n  who:\n    - 김\n    - 이\nparticipants:\n- 김\ncustom: 값\nmodified: 2026-02-01T10:00:00+09:00"
+        );
+    }
+
+    #[test]
+    fn test_remove_and_quote_when_needed() {
+        let new = with(&[("custom", Value::Null), ("type", Value::from("a: b")), ("id", Value::from("2"))]);
+        let updated = update_yaml(YAML, &new).unwrap();
+        assert!(updated.contains("\ntype: \"a: b\"\n"));
+        assert!(updated.contains("\nid: \"2\"\n"), "기존 따옴표 유지");
+        assert!(!updated.contains("custom"));
+        assert!(updated.ends_with("- 김"));
+    }
+
+    #[test]
+    fn test_content_body_kept_and_crlf() {
+        let content = "---\r\ntitle: a\r\ntags: []\r\n---\r\n본문\r\n";
+        let new: Mapping = serde_yaml::from_str("title: a\ntags: [x]").unwrap();
+        assert_eq!(
+            update_frontmatter_content(content, &new).unwrap(),
+            "---\r\ntitle: a\r\ntags: [x]\r\n---\r\n본문\r\n"
+        );
+    }
+}
@@ -0,0 +1,83 @@
+// Format-preserving frontmatter edit - 주석, 따옴표, 키 순서, 변경 없는 줄 유지 검증
+
+#[cfg(test)]
+mod frontmatter_edit_tests {
+    use crate::bulk_edit::FrontmatterEdit;
+    use crate::frontmatter::types::WorkflowState;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const NOTE: &str = "---\n# 팀 공용 메모\nid: \"20260101090000\"\ntitle: '회의 정리'\ntype: MTG # 회의\nstate:\n  workflow: draft   # 초안\n  confidence: unverified\ntags:\n  domain: [기획]\n  who:\n    - 김\ncreated: 2026-01-01T09:00:00+09:00\nmodified: 2026-01-01T09:00:00+09:00\n---\n\n본문\n";
+
+    #[test]
+    fn test_update_note_frontmatter_keeps_untouched_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let note = temp_dir.path().join("회의 정리.md");
+        fs::write(&note, NOTE).unwrap();
+
+        // 에디터가 보내는 전체 YAML (serde_yaml 직렬화 형태), workflow만 변경
+        let new_yaml = NOTE
+            .trim_start_matches("---\n")
+            .split("\n---")
+            .next()
+            .unwrap()
+            .replace("workflow: draft", "workflow: in-progress");
+        let mut map: serde_yaml::Mapping = serde_yaml::from_str(&new_yaml).unwrap();
+        map.insert("priority".into(), "high".into());
+        let new_yaml = serde_yaml::to_string(&map).unwrap();
+
+        crate::update_note_frontmatter(note.to_string_lossy().to_string(), new_yaml).unwrap();
+        let content = fs::read_to_string(&note).unwrap();
+        let expected = NOTE
+            .replace("workflow: draft   # 초안", "workflow: in-progress   # 초안")
+            .replace("modified: 2026-01-01T09:00:00+09:00\n---\n\n본문\n", "modified: 2026-01-01T09:00:00+09:00\npriority: high\n");
+        let (head, history) = content.split_once("state_history:").expect("상태 변경 이력 추가");
+        assert_eq!(head, expected, "바뀐 값과 새 키 외에는 원문 그대로");
+        assert!(history.contains("from: draft") && history.contains("to: in-progress"));
+        assert!(history.ends_with("---\n\n본문\n"));
+    }
+
+    #[test]
+    fn test_bulk_edit_keeps_comments_and_quoting() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        let note = vault.join("회의 정리.md");
+        fs::write(&note, NOTE).unwrap();
+
+        let edits = vec![
+            FrontmatterEdit::AddTag { facet: "domain".to_string(), tag: "예산".to_string() },
+            FrontmatterEdit::SetWorkflow { workflow: WorkflowState::InProgress },
+        ];
+        let paths = vec![note.to_string_lossy().to_string()];
+        let (result, _) = crate::apply_bulk_frontmatter_edits(&paths, &edits, false, vault).unwrap();
+        assert_eq!(result.changed.len(), 1);
+
+        let content = fs::read_to_string(&note).unwrap();
+        assert!(content.starts_with("---\n# 팀 공용 메모\nid: \"20260101090000\"\ntitle: '회의 정리'\ntype: MTG # 회의\n"));
+        assert!(content.contains("  workflow: in-progress   # 초안\n"));
+        assert!(content.contains("state_history:\n") && content.contains("to: in-progress"), "일괄 변경도 이력 기록");
+        assert!(content.contains("  domain: [기획, 예산]\n  who:\n    - 김\n"));
+        assert!(!content.contains("modified: 2026-01-01T09:00:00+09:00"), "modified 갱신");
+        assert!(content.ends_with("---\n\n본문\n"));
+    }
+
+    #[test]
+    fn test_bulk_workflow_change_follows_transition_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        let draft = vault.join("초안.md");
+        let review = vault.join("검토.md");
+        fs::write(&draft, NOTE).unwrap();
+        fs::write(&review, NOTE.replace("workflow: draft", "workflow: review")).unwrap();
+
+        let edits = vec![FrontmatterEdit::SetWorkflow { workflow: WorkflowState::Final }];
+        let paths = vec![draft.to_string_lossy().to_string(), review.to_string_lossy().to_string()];
+        let (result, written) = crate::apply_bulk_frontmatter_edits(&paths, &edits, false, vault).unwrap();
+        assert!(written.is_empty());
+        assert_eq!(result.failed.len(), 2, "draft -> final 불가, review -> final은 verified 필요");
+        assert!(result.failed.iter().any(|f| f.error.contains("requires confidence: verified")));
+        assert_eq!(fs::read_to_string(&draft).unwrap(), NOTE, "거부된 노트는 그대로");
+    }
+}
@@ -1,7 +1,21 @@
 pub mod search;
 pub mod vault_lock;
+mod autofix;
+mod bulk_edit;
 mod frontmatter;
+mod lint;
 mod memo;
+mod note_merge;
+mod note_split;
+mod path_links;
+mod periodic_notes;
+mod rename_journal;
+mod tag_migration;
+mod templates;
+mod text_diff;
+
+#[cfg(test)]
+mod test_common;
 
 #[cfg(test)]
 mod lib_test;
@@ -48,6 +62,78 @@ mod attachment_wikilink_sync_test;
 #[cfg(test)]
 mod synology_safety_test;
 
+#[cfg(test)]
+mod relations_test;
+
+#[cfg(test)]
+mod local_graph_test;
+
+#[cfg(test)]
+mod link_graph_test;
+
+#[cfg(test)]
+mod mentions_test;
+
+#[cfg(test)]
+mod transclusion_test;
+
+#[cfg(test)]
+mod rename_journal_test;
+
+#[cfg(test)]
+mod rename_preview_test;
+
+#[cfg(test)]
+mod folder_move_test;
+
+#[cfg(test)]
+mod note_merge_test;
+
+#[cfg(test)]
+mod note_split_test;
+
+#[cfg(test)]
+mod bulk_edit_test;
+
+#[cfg(test)]
+mod tag_refactor_test;
+
+#[cfg(test)]
+mod tag_migration_test;
+
+#[cfg(test)]
+mod note_convert_test;
+
+#[cfg(test)]
+mod vault_schema_test;
+
+#[cfg(test)]
+mod lint_vault_test;
+
+#[cfg(test)]
+mod frontmatter_edit_test;
+
+#[cfg(test)]
+mod autofix_test;
+
+#[cfg(test)]
+mod note_id_test;
+
+#[cfg(test)]
+mod note_metrics_test;
+
+#[cfg(test)]
+mod templates_test;
+
+#[cfg(test)]
+mod periodic_notes_test;
+
+#[cfg(test)]
+mod workflow_test;
+
+#[cfg(test)]
+mod suggestions_test;
+
 use rayon::prelude::*;
 use regex::Regex;
 use frontmatter::FrontmatterParser;
@@ -59,8 +145,12 @@ use std::sync::{Arc, Mutex};
 use opener;
 use tauri::Manager;
 
-use search::{SearchIndex, NoteFilter, NoteMetadata, RelationshipData, GraphData, SearchResult as IndexSearchResult};
+use search::{SearchIndex, NoteFilter, NoteMetadata, RelationshipData, GraphData, LocalGraphData, LocalGraphFilter, RelatedNote, RelationDirection, SearchResult as IndexSearchResult};
 use search::watcher::VaultWatcher;
+use search::export::GraphExportFormat;
+use search::link_graph::BrokenLink;
+use search::mentions::{MentionSelection, UnlinkedMention};
+use search::transclusion::TransclusionResult;
 
 /// Atomic file write: write to a temp file in the same directory, then rename.
 /// This prevents Synology Drive (or any file watcher) from syncing a partially-written file.
@@ -87,6 +177,33 @@ fn atomic_write_file(path: &Path, content: &[u8]) -> Result<(), String> {
 }
 use memo::{MemoIndex, MemoQueryFilter, IndexedMemo};
 
+/// Per-vault settings stored as JSON in `.notology/`
+trait VaultJsonConfig: Serialize + serde::de::DeserializeOwned + Default {
+    const FILE_NAME: &'static str;
+
+    fn validate(&self) -> Result<(), String>;
+}
+
+/// Settings of the vault; defaults if none were saved
+fn load_vault_json_config<T: VaultJsonConfig>(vault_path: &Path) -> Result<T, String> {
+    let path = vault_path.join(".notology").join(T::FILE_NAME);
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let json = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let config: T = serde_json::from_str(&json).map_err(|e| format!("Invalid {}: {}", T::FILE_NAME, e))?;
+    config.validate()?;
+    Ok(config)
+}
+
+fn save_vault_json_config<T: VaultJsonConfig>(vault_path: &Path, config: &T) -> Result<(), String> {
+    config.validate()?;
+    let dir = vault_path.join(".notology");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    atomic_write_file(&dir.join(T::FILE_NAME), json.as_bytes())
+}
+
 struct SearchState {
     index: Option<Arc<SearchIndex>>,
     _watcher: Option<VaultWatcher>,
@@ -151,13 +268,47 @@ fn parse_frontmatter(content: String) -> Result<serde_json::Value, String> {
     }))
 }
 
-// UNUSED: Not invoked from frontend
+/// Validate against the note type's schema; with `vault_path`, the vault's
+/// `.notology/schemas/*.json` extensions apply as well
 #[tauri::command]
-fn validate_frontmatter(frontmatter_json: String) -> Result<Vec<frontmatter::types::ValidationError>, String> {
+fn validate_frontmatter(
+    frontmatter_json: String,
+    vault_path: Option<String>,
+) -> Result<Vec<frontmatter::types::ValidationError>, String> {
     let fm: frontmatter::types::Frontmatter = serde_json::from_str(&frontmatter_json)
         .map_err(|e| format!("Invalid frontmatter JSON: {}", e))?;
 
-    FrontmatterParser::validate(&fm)
+    match vault_path {
+        Some(vault_path) => {
+            let schemas = frontmatter::schemas::VaultSchemas::load(Path::new(&vault_path))?;
+            FrontmatterParser::validate_with(&fm, &schemas)
+        }
+        None => FrontmatterParser::validate(&fm),
+    }
+}
+
+/// Check every note's frontmatter (YAML syntax, note type, schema incl. vault schemas,
+/// duplicate ids, relation targets). Issues are grouped by severity with file lines.
+#[tauri::command]
+async fn lint_vault(vault_path: String) -> Result<lint::LintReport, String> {
+    let vault = Path::new(&vault_path);
+    let schemas = frontmatter::schemas::VaultSchemas::load(vault)?;
+    let files = collect_md_files(vault);
+    let report = lint::lint_notes(&files, &schemas);
+    log::info!(
+        "[lint_vault] {} notes: {} errors, {} warnings",
+        report.notes_checked,
+        report.errors.len(),
+        report.warnings.len()
+    );
+    Ok(report)
+}
+
+/// Effective JSON schema of a note type in the vault (built-in schema plus vault schemas)
+#[tauri::command]
+fn get_note_schema(note_type: String, vault_path: String) -> Result<serde_json::Value, String> {
+    let schemas = frontmatter::schemas::VaultSchemas::load(Path::new(&vault_path))?;
+    Ok(schemas.schema_for_type(&note_type.to_uppercase()))
 }
 
 // UNUSED: Not invoked from frontend
@@ -175,20 +326,38 @@ fn yaml_to_frontmatter(yaml_str: String) -> Result<frontmatter::types::Frontmatt
     FrontmatterParser::parse_yaml(&yaml_str)
 }
 
-// UNUSED: Not invoked from frontend
+/// Tag, relation, workflow and quality suggestions for a note, with the evidence behind each.
+/// Content-aware suggestions come from the search index, so only the note path is needed.
 #[tauri::command]
-fn generate_suggestions(
-    frontmatter_json: String,
-    all_notes_json: String,
+async fn generate_suggestions(
+    path: String,
+    limit: Option<usize>,
+    state: tauri::State<'_, Mutex<SearchState>>,
 ) -> Result<Vec<frontmatter::suggestions::Suggestion>, String> {
-    let fm: frontmatter::types::Frontmatter = serde_json::from_str(&frontmatter_json)
-        .map_err(|e| format!("Invalid frontmatter JSON: {}", e))?;
+    let evidence = {
+        let search_state = state.lock().map_err(|e| e.to_string())?;
+        let index = search_state.index.as_ref().ok_or("Search index not initialized")?;
+        index.suggestion_evidence(&path, limit.unwrap_or(5))?
+    };
+    let workflow = match find_vault_root(Path::new(&path)) {
+        Some(vault) => load_vault_json_config(&vault)?,
+        None => Default::default(),
+    };
+    let fm = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| FrontmatterParser::parse(&content).ok())
+        .and_then(|(fm, _)| fm);
+    Ok(frontmatter::suggestions::SuggestionEngine::generate_suggestions(fm.as_ref(), &evidence, &workflow))
+}
 
-    let all_notes: Vec<frontmatter::types::Frontmatter> = serde_json::from_str(&all_notes_json)
-        .map_err(|e| format!("Invalid all_notes JSON: {}", e))?;
+#[tauri::command]
+fn get_workflow_config(vault_path: String) -> Result<frontmatter::workflow::WorkflowConfig, String> {
+    load_vault_json_config(Path::new(&vault_path))
+}
 
-    let suggestions = frontmatter::suggestions::SuggestionEngine::generate_suggestions(&fm, &all_notes);
-    Ok(suggestions)
+#[tauri::command]
+fn save_workflow_config(vault_path: String, config: frontmatter::workflow::WorkflowConfig) -> Result<(), String> {
+    save_vault_json_config(Path::new(&vault_path), &config)
 }
 
 #[tauri::command]
@@ -381,9 +550,11 @@ fn create_note(
     let datetime = now.format("%Y-%m-%dT%H:%M:%S%:z").to_string();
     let ntype = note_type.unwrap_or_else(|| "NOTE".to_string());
 
+    let id = frontmatter::ids::allocate_note_id(|id| is_indexed_note_id(&_state, id));
+
     let content = format!(
-        "---\ncreated: \"{}\"\nmodified: \"{}\"\ntitle: \"{}\"\ntype: \"{}\"\ntags: []\n---\n\n",
-        datetime, datetime, title, ntype
+        "---\nid: \"{}\"\ncreated: \"{}\"\nmodified: \"{}\"\ntitle: \"{}\"\ntype: \"{}\"\ntags: []\n---\n\n",
+        id, datetime, datetime, title, ntype
     );
 
     // Atomic write: write to temp file then rename to prevent partial file sync
@@ -600,17 +771,15 @@ fn search_backlinks_recursive(
     Ok(())
 }
 
+/// Move a file or folder. Inside a vault, path-style links to everything moved are rewritten
+/// in the same journaled transaction as the move.
 #[tauri::command]
-fn move_file(old_path: String, new_path: String) -> Result<(), String> {
+fn move_file(old_path: String, new_path: String) -> Result<PathChangeResult, String> {
     let old = Path::new(&old_path);
     let new = Path::new(&new_path);
-
-    if !old.exists() {
-        return Err("Source file does not exist".to_string());
-    }
-    if new.exists() {
-        return Err("Destination already exists".to_string());
-    }
+    let vault = find_vault_root(old);
+    let plan = plan_move_file(&old_path, &new_path, vault.as_deref())?;
+    plan.ensure_no_collisions()?;
 
     // Ensure parent directory exists
     if let Some(parent) = new.parent() {
@@ -619,6 +788,10 @@ fn move_file(old_path: String, new_path: String) -> Result<(), String> {
         }
     }
 
+    if let Some(vault) = vault {
+        return commit_path_change(plan, &vault, &format!("move {} -> {}", old_path, new_path));
+    }
+
     fs::rename(old, new).map_err(|e| {
         // If rename fails (cross-device), try copy + delete
         if let Ok(_) = fs::copy(old, new) {
@@ -628,7 +801,10 @@ fn move_file(old_path: String, new_path: String) -> Result<(), String> {
         e.to_string()
     })?;
 
-    Ok(())
+    Ok(PathChangeResult {
+        final_path: new_path,
+        skipped: Vec::new(),
+    })
 }
 
 // UNUSED: Not invoked from frontend
@@ -739,10 +915,10 @@ fn import_attachment(source_path: String, note_path: String) -> Result<String, S
     Ok(final_target.to_string_lossy().to_string())
 }
 
-// UNUSED: Not invoked from frontend
-#[tauri::command]
-fn move_note(note_path: String, new_dir: String) -> Result<String, String> {
-    let old = Path::new(&note_path);
+/// Plan a note move: the note, then its `_att` folder, into `new_dir`.
+/// With a vault, path-style links to the note and its attachments are rewritten too.
+fn plan_move_note(note_path: &str, new_dir: &str, vault: Option<&Path>) -> Result<PathChangePlan, String> {
+    let old = Path::new(note_path);
     if !old.exists() {
         return Err("Note does not exist".to_string());
     }
@@ -755,32 +931,145 @@ fn move_note(note_path: String, new_dir: String) -> Result<String, String> {
     let old_dir = old.parent().ok_or("Invalid note path")?;
     let attachments_name = format!("{}_att", stem);
     let old_att = old_dir.join(&attachments_name);
+    let new_parent = Path::new(new_dir);
+
+    let mut renames = Vec::new();
+    let mut collisions = Vec::new();
+
+    let new_note = new_parent.join(old.file_name().unwrap());
+    if new_note.exists() {
+        collisions.push((new_note.clone(), "Destination note already exists".to_string()));
+    }
+    renames.push((old.to_path_buf(), new_note.clone()));
+
+    if old_att.is_dir() {
+        let new_att = new_parent.join(&attachments_name);
+        if new_att.exists() {
+            collisions.push((new_att.clone(), "Destination attachment folder already exists".to_string()));
+        }
+        renames.push((old_att, new_att));
+    }
+
+    let rewrites = plan_path_link_updates(vault, &renames);
+    Ok(PathChangePlan { final_path: new_note, renames, rewrites, collisions })
+}
+
+/// Plan a plain file/folder move (`move_file`)
+fn plan_move_file(old_path: &str, new_path: &str, vault: Option<&Path>) -> Result<PathChangePlan, String> {
+    let old = Path::new(old_path);
+    let new = Path::new(new_path);
+    if !old.exists() {
+        return Err("Source file does not exist".to_string());
+    }
+
+    let mut collisions = Vec::new();
+    if new.exists() {
+        collisions.push((new.to_path_buf(), "Destination already exists".to_string()));
+    }
+    let renames = vec![(old.to_path_buf(), new.to_path_buf())];
+    let rewrites = plan_path_link_updates(vault, &renames);
+    Ok(PathChangePlan {
+        final_path: new.to_path_buf(),
+        renames,
+        rewrites,
+        collisions,
+    })
+}
+
+/// Plan moving a folder (with its folder note and `_att` folders) under `new_parent`
+fn plan_move_folder(folder_path: &str, new_parent: &str, vault_path: &str) -> Result<PathChangePlan, String> {
+    let folder = Path::new(folder_path);
+    if !folder.is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    let new_parent = Path::new(new_parent);
+    if new_parent.starts_with(folder) {
+        return Err("Cannot move a folder into itself".to_string());
+    }
+    let name = folder.file_name().ok_or("Invalid folder path")?;
+    plan_move_file(folder_path, &new_parent.join(name).to_string_lossy(), Some(Path::new(vault_path)))
+}
+
+/// Path-style link rewrites for everything `renames` moves (none outside a vault)
+fn plan_path_link_updates(vault: Option<&Path>, renames: &[(PathBuf, PathBuf)]) -> Vec<(PathBuf, String, String)> {
+    let Some(vault) = vault else { return Vec::new() };
+    let rewriter = path_links::PathLinkRewriter::new(vault, renames);
+    plan_wiki_link_updates(vault, &[], Some(&rewriter))
+}
+
+/// Outcome of a rename/move: where the file ended up, and notes whose links were left
+/// untouched because they changed between planning and applying
+#[derive(Serialize, Debug)]
+pub struct PathChangeResult {
+    final_path: String,
+    skipped: Vec<String>,
+}
+
+/// Apply a plan through the rename journal (rolled back on failure, recovered after a crash)
+fn commit_path_change(plan: PathChangePlan, vault: &Path, description: &str) -> Result<PathChangeResult, String> {
+    plan.ensure_no_collisions()?;
+    let skipped = rename_journal::RenameTransaction::begin(vault, description, plan.rewrites, plan.renames)?.apply()?;
+    Ok(PathChangeResult {
+        final_path: plan.final_path.to_string_lossy().to_string(),
+        skipped: skipped.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+    })
+}
+
+/// Move a note and its `_att` folder into `new_dir`
+#[tauri::command]
+fn move_note(note_path: String, new_dir: String) -> Result<PathChangeResult, String> {
+    let vault = find_vault_root(Path::new(&note_path));
+    let plan = plan_move_note(&note_path, &new_dir, vault.as_deref())?;
+    plan.ensure_no_collisions()?;
 
     let new_parent = Path::new(&new_dir);
     if !new_parent.exists() {
         fs::create_dir_all(new_parent).map_err(|e| e.to_string())?;
     }
 
-    // Move note
-    let new_note = new_parent.join(old.file_name().unwrap());
-    if new_note.exists() {
-        return Err("Destination note already exists".to_string());
+    if let Some(vault) = vault {
+        let description = format!("move {} -> {}", note_path, new_dir);
+        return commit_path_change(plan, &vault, &description);
     }
-    fs::rename(old, &new_note).or_else(|_| {
-        fs::copy(old, &new_note).map_err(|e| e.to_string())?;
-        fs::remove_file(old).map_err(|e| e.to_string())
-    })?;
 
-    // Move attachments folder if exists
-    if old_att.exists() && old_att.is_dir() {
-        let new_att = new_parent.join(&attachments_name);
-        fs::rename(&old_att, &new_att).or_else(|_| {
-            copy_dir_recursive(&old_att, &new_att)?;
-            fs::remove_dir_all(&old_att).map_err(|e| e.to_string())
-        })?;
+    // Outside a vault: plain move (copy fallback for cross-device)
+    for (from, to) in &plan.renames {
+        if from.is_dir() {
+            fs::rename(from, to).or_else(|_| {
+                copy_dir_recursive(from, to)?;
+                fs::remove_dir_all(from).map_err(|e| e.to_string())
+            })?;
+        } else {
+            fs::rename(from, to).or_else(|_| {
+                fs::copy(from, to).map_err(|e| e.to_string())?;
+                fs::remove_file(from).map_err(|e| e.to_string())
+            })?;
+        }
     }
 
-    Ok(new_note.to_string_lossy().to_string())
+    Ok(PathChangeResult {
+        final_path: plan.final_path.to_string_lossy().to_string(),
+        skipped: Vec::new(),
+    })
+}
+
+/// Move a folder under `new_parent`, rewriting path-style links to every note and attachment inside
+#[tauri::command]
+fn move_folder(folder_path: String, new_parent: String, vault_path: String) -> Result<PathChangeResult, String> {
+    let plan = plan_move_folder(&folder_path, &new_parent, &vault_path)?;
+    plan.ensure_no_collisions()?;
+    fs::create_dir_all(&new_parent).map_err(|e| e.to_string())?;
+    let description = format!("move folder {} -> {}", folder_path, new_parent);
+    commit_path_change(plan, Path::new(&vault_path), &description)
+}
+
+/// Rename a folder; its folder note and `_att` folder follow, and links (stem and path-style) are rewritten
+#[tauri::command]
+fn rename_folder(folder_path: String, new_name: String, vault_path: String) -> Result<PathChangeResult, String> {
+    if !Path::new(&folder_path).is_dir() {
+        return Err("Folder does not exist".to_string());
+    }
+    rename_file_with_links(folder_path, new_name, vault_path)
 }
 
 #[tauri::command]
@@ -948,6 +1237,11 @@ async fn init_search_index(
     state: tauri::State<'_, Mutex<SearchState>>,
     app: tauri::AppHandle,
 ) -> Result<(), String> {
+    // Finish or undo a rename interrupted by a crash before the index sees the vault
+    for outcome in rename_journal::recover_pending(Path::new(&vault_path)) {
+        log::info!("[init_search_index] Recovered interrupted rename: {:?}", outcome);
+    }
+
     let index = SearchIndex::new(&vault_path)?;
     let index = Arc::new(index);
 
@@ -1143,6 +1437,149 @@ async fn get_graph_data(
     index.get_graph_data(container_path.as_deref(), include_attachments.unwrap_or(false))
 }
 
+/// Wiki-links that resolve to no note/attachment (single note, or whole vault when path is None)
+#[tauri::command]
+async fn get_broken_links(
+    path: Option<String>,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<Vec<BrokenLink>, String> {
+    let search_state = state.lock().map_err(|e| e.to_string())?;
+    let index = search_state.index.as_ref().ok_or("Search index not initialized")?;
+    index.get_broken_links(path.as_deref())
+}
+
+/// Expand `![[Note]]`, `![[Note#Heading]]` and attachment embeds of a note.
+/// The returned dependency list is what the editor should watch ("embed-dependency-changed" events).
+#[tauri::command]
+async fn resolve_transclusions(
+    path: String,
+    max_depth: Option<usize>,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<TransclusionResult, String> {
+    let search_state = state.lock().map_err(|e| e.to_string())?;
+    let index = search_state.index.as_ref().ok_or("Search index not initialized")?;
+    index.resolve_transclusions(&path, max_depth)
+}
+
+/// Plain-text mentions of a note's title/file name/aliases in notes that don't link to it yet
+#[tauri::command]
+async fn find_unlinked_mentions(
+    path: String,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<Vec<UnlinkedMention>, String> {
+    let search_state = state.lock().map_err(|e| e.to_string())?;
+    let index = search_state.index.as_ref().ok_or("Search index not initialized")?;
+    index.find_unlinked_mentions(&path)
+}
+
+/// Convert selected unlinked mentions into wiki-links to `target_path`.
+/// Each source file is rewritten once (atomic write) and re-indexed. Returns the number of links created.
+#[tauri::command]
+async fn link_unlinked_mentions(
+    target_path: String,
+    mentions: Vec<MentionSelection>,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<usize, String> {
+    let file_name = Path::new(&target_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .ok_or("Invalid target path")?;
+
+    let mut by_source: std::collections::BTreeMap<String, Vec<MentionSelection>> = std::collections::BTreeMap::new();
+    for mention in mentions {
+        by_source.entry(mention.source_path.clone()).or_default().push(mention);
+    }
+
+    let mut total = 0;
+    let mut changed: Vec<PathBuf> = Vec::new();
+    for (source, selections) in &by_source {
+        let source_path = Path::new(source);
+        let content = fs::read_to_string(source_path)
+            .map_err(|e| format!("Failed to read {}: {}", source, e))?;
+        let (updated, applied) = search::mentions::apply_mention_links(&content, selections, &file_name);
+        if applied == 0 {
+            continue;
+        }
+        atomic_write_file(source_path, updated.as_bytes())?;
+        total += applied;
+        changed.push(source_path.to_path_buf());
+    }
+
+    let search_state = state.lock().map_err(|e| e.to_string())?;
+    if let Some(index) = search_state.index.as_ref() {
+        for path in &changed {
+            if let Err(e) = index.index_file(path) {
+                log::warn!("[link_unlinked_mentions] Failed to re-index {:?}: {}", path, e);
+            }
+        }
+    }
+
+    log::info!("[link_unlinked_mentions] Linked {} mentions in {} notes", total, changed.len());
+    Ok(total)
+}
+
+/// N-hop neighborhood of a single note for the per-note mini graph (capped at a node budget)
+#[tauri::command]
+async fn get_local_graph(
+    path: String,
+    depth: Option<usize>,
+    filters: Option<LocalGraphFilter>,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<LocalGraphData, String> {
+    let search_state = state.lock().map_err(|e| e.to_string())?;
+    let index = search_state.index.as_ref().ok_or("Search index not initialized")?;
+    index.get_local_graph(&path, depth.unwrap_or(1), &filters.unwrap_or_default())
+}
+
+/// Query typed semantic relations (e.g., notes that refute X, everything derived from Y)
+#[tauri::command]
+async fn query_relations(
+    path: String,
+    relation_type: Option<String>,
+    direction: Option<RelationDirection>,
+    transitive: Option<bool>,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<Vec<RelatedNote>, String> {
+    let search_state = state.lock().map_err(|e| e.to_string())?;
+    let index = search_state.index.as_ref().ok_or("Search index not initialized")?;
+    index.query_relations(
+        &path,
+        relation_type.as_deref(),
+        direction.unwrap_or(RelationDirection::Incoming),
+        transitive.unwrap_or(false),
+    )
+}
+
+/// Export the (optionally container-scoped) graph to GraphML / GEXF / DOT for external tools
+#[tauri::command]
+async fn export_graph(
+    container_path: Option<String>,
+    include_attachments: Option<bool>,
+    format: GraphExportFormat,
+    output_path: String,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<String, String> {
+    let data = {
+        let search_state = state.lock().map_err(|e| e.to_string())?;
+        let index = search_state.index.as_ref().ok_or("Search index not initialized")?;
+        index.get_graph_data(container_path.as_deref(), include_attachments.unwrap_or(false))?
+    };
+
+    let mut output = PathBuf::from(&output_path);
+    if output.extension().is_none() {
+        output.set_extension(format.extension());
+    }
+    if let Some(parent) = output.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            return Err(format!("Output directory does not exist: {}", parent.display()));
+        }
+    }
+
+    let content = search::export::export_graph(&data, format);
+    atomic_write_file(&output, content.as_bytes())?;
+    Ok(output.to_string_lossy().to_string())
+}
+
 #[tauri::command]
 async fn reindex_vault(
     state: tauri::State<'_, Mutex<SearchState>>,
@@ -1678,6 +2115,37 @@ async fn remove_note_from_index(
     index.remove_file(std::path::Path::new(&path))
 }
 
+/// Whether a note in the search index already uses `id`
+fn is_indexed_note_id(state: &tauri::State<'_, Mutex<SearchState>>, id: &str) -> bool {
+    state
+        .lock()
+        .ok()
+        .and_then(|s| s.index.as_ref().and_then(|index| index.resolve_note(&format!("id:{}", id))))
+        .is_some()
+}
+
+/// Frontmatter YAML with its `id` replaced by a collision-free one if it is missing, malformed,
+/// already used in the vault or already handed out in this session
+fn with_unique_note_id(frontmatter_yaml: &str, taken: impl Fn(&str) -> bool) -> String {
+    let Ok(serde_yaml::Value::Mapping(mut map)) = serde_yaml::from_str::<serde_yaml::Value>(frontmatter_yaml) else {
+        return frontmatter_yaml.to_string();
+    };
+    let current = match map.get("id") {
+        Some(serde_yaml::Value::String(id)) => id.clone(),
+        Some(serde_yaml::Value::Number(id)) => id.to_string(),
+        _ => String::new(),
+    };
+    let id = frontmatter::ids::claim_note_id(&current, taken);
+    if id == current {
+        return frontmatter_yaml.to_string();
+    }
+    log::info!("[create_note_with_template] Note id {:?} replaced with {}", current, id);
+    map.insert("id".into(), serde_yaml::Value::String(id));
+    frontmatter::yaml_edit::update_yaml(frontmatter_yaml, &map)
+        .or_else(|_| serde_yaml::to_string(&map).map(|y| y.trim_end().to_string()).map_err(|e| e.to_string()))
+        .unwrap_or_else(|_| frontmatter_yaml.to_string())
+}
+
 // UNUSED: Not invoked from frontend
 #[tauri::command]
 fn create_note_with_template(
@@ -1685,6 +2153,7 @@ fn create_note_with_template(
     file_name: String,
     frontmatter_yaml: String,
     body: String,
+    state: tauri::State<'_, Mutex<SearchState>>,
 ) -> Result<String, String> {
     let dir = Path::new(&dir_path);
     if !dir.exists() {
@@ -1693,6 +2162,7 @@ fn create_note_with_template(
 
     let target = dir.join(format!("{}.md", file_name));
     let final_path = resolve_collision(&target);
+    let frontmatter_yaml = with_unique_note_id(&frontmatter_yaml, |id| is_indexed_note_id(&state, id));
 
     let content = format!("---\n{}\n---\n\n{}", frontmatter_yaml, body);
 
@@ -1703,41 +2173,175 @@ fn create_note_with_template(
     Ok(final_path.to_string_lossy().to_string())
 }
 
-// UNUSED: Not invoked from frontend
+/// Templates from `.notology/templates/`, with the prompts to ask before rendering
 #[tauri::command]
-fn rename_file_with_links(
-    file_path: String,
-    new_name: String,
+fn list_note_templates(vault_path: String) -> Vec<templates::TemplateInfo> {
+    templates::list_templates(Path::new(&vault_path))
+}
+
+fn template_context(dir: &Path, title: String, answers: std::collections::HashMap<String, String>) -> templates::TemplateContext {
+    templates::TemplateContext {
+        title,
+        container: dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        answers,
+        now: chrono::Local::now(),
+    }
+}
+
+/// Render a template without writing it (preview before creating the note)
+#[tauri::command]
+fn render_note_template(
     vault_path: String,
-) -> Result<String, String> {
-    println!("[DEBUG] rename_file_with_links called:");
-    println!("  file_path: {}", file_path);
-    println!("  new_name: {}", new_name);
-    println!("  vault_path: {}", vault_path);
+    template: String,
+    dir_path: String,
+    title: String,
+    answers: std::collections::HashMap<String, String>,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<templates::RenderedNote, String> {
+    let context = template_context(Path::new(&dir_path), title, answers);
+    templates::preview_template(Path::new(&vault_path), &template, &context, |id| is_indexed_note_id(&state, id))
+}
 
-    let old_path = Path::new(&file_path);
-    if !old_path.exists() {
-        return Err("File does not exist".to_string());
+/// Create a note in `dir_path` from a vault template. Returns the new note's path.
+#[tauri::command]
+fn create_note_from_template(
+    vault_path: String,
+    template: String,
+    dir_path: String,
+    title: String,
+    answers: std::collections::HashMap<String, String>,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<String, String> {
+    let dir = Path::new(&dir_path);
+    if !dir.exists() {
+        return Err("Directory does not exist".to_string());
     }
+    let context = template_context(dir, title, answers);
+    let note = templates::render_template(Path::new(&vault_path), &template, &context, |id| is_indexed_note_id(&state, id))?;
 
-    let parent = old_path.parent().ok_or("Cannot determine parent directory")?;
-    let old_stem = old_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
-    let old_name_full = old_path.file_name().unwrap_or_default().to_string_lossy().to_string();
-    let parent_name = parent.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let final_path = resolve_collision(&dir.join(format!("{}.md", note.file_name)));
+    atomic_write_file(&final_path, note.content.as_bytes())?;
 
-    let new_stem = Path::new(&new_name)
+    log::info!("[create_note_from_template] {} -> {:?}", template, final_path);
+    Ok(final_path.to_string_lossy().to_string())
+}
+
+/// A planned rename/move: ordered path renames plus the link rewrites they require
+struct PathChangePlan {
+    final_path: PathBuf,
+    renames: Vec<(PathBuf, PathBuf)>,
+    /// (note path, original content, updated content)
+    rewrites: Vec<(PathBuf, String, String)>,
+    /// Targets that already exist, with the error the operation would fail with
+    collisions: Vec<(PathBuf, String)>,
+}
+
+impl PathChangePlan {
+    fn ensure_no_collisions(&self) -> Result<(), String> {
+        match self.collisions.first() {
+            Some((_, reason)) => Err(reason.clone()),
+            None => Ok(()),
+        }
+    }
+
+    /// Dry-run view of the plan; paths in diffs are shown relative to the vault
+    fn to_preview(&self, vault_path: &Path) -> PathChangePreview {
+        let relative = |p: &Path| {
+            p.strip_prefix(vault_path)
+                .unwrap_or(p)
+                .to_string_lossy()
+                .replace('\\', "/")
+        };
+        PathChangePreview {
+            final_path: self.final_path.to_string_lossy().to_string(),
+            moves: self
+                .renames
+                .iter()
+                .map(|(from, to)| PlannedMove {
+                    from: from.to_string_lossy().to_string(),
+                    to: to.to_string_lossy().to_string(),
+                    is_dir: from.is_dir(),
+                })
+                .collect(),
+            diffs: self
+                .rewrites
+                .iter()
+                .map(|(path, original, updated)| FileDiffPreview {
+                    path: path.to_string_lossy().to_string(),
+                    diff: text_diff::unified_diff(&relative(path), original, updated),
+                })
+                .collect(),
+            collisions: self
+                .collisions
+                .iter()
+                .map(|(path, reason)| PathCollision {
+                    path: path.to_string_lossy().to_string(),
+                    resolved_path: resolve_collision(path).to_string_lossy().to_string(),
+                    reason: reason.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PlannedMove {
+    from: String,
+    to: String,
+    is_dir: bool,
+}
+
+#[derive(Serialize)]
+pub struct FileDiffPreview {
+    path: String,
+    /// Unified diff of the link rewrites
+    diff: String,
+}
+
+#[derive(Serialize)]
+pub struct PathCollision {
+    /// Target that already exists
+    path: String,
+    /// Free name `resolve_collision` would pick instead
+    resolved_path: String,
+    reason: String,
+}
+
+/// Everything a rename/move would change, computed without writing
+#[derive(Serialize)]
+pub struct PathChangePreview {
+    final_path: String,
+    moves: Vec<PlannedMove>,
+    diffs: Vec<FileDiffPreview>,
+    collisions: Vec<PathCollision>,
+}
+
+/// Plan a rename: renames (note, `_att` folder, folder note's parent folder) and vault-wide link rewrites
+fn plan_rename_with_links(file_path: &str, new_name: &str, vault_path: &str) -> Result<PathChangePlan, String> {
+    let old_path = Path::new(file_path);
+    if !old_path.exists() {
+        return Err("File does not exist".to_string());
+    }
+
+    let parent = old_path.parent().ok_or("Cannot determine parent directory")?;
+    let old_stem = old_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let old_name_full = old_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let parent_name = parent.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let new_name = new_name.to_string();
+
+    let new_stem = Path::new(&new_name)
         .file_stem()
         .unwrap_or_default()
         .to_string_lossy()
         .to_string();
 
-    println!("[DEBUG] Extracted names:");
-    println!("  old_stem: {}, old_name_full: {}", old_stem, old_name_full);
-    println!("  new_stem: {}, new_name: {}", new_stem, new_name);
-
     // Check if this is a folder note (file stem matches parent folder name)
     let is_folder_note = old_stem.eq_ignore_ascii_case(&parent_name);
 
+    // (old_stem, old_full, new_stem, new_full) link rewrites and ordered renames
+    let mut link_renames: Vec<(String, String, String, String)> = Vec::new();
+    let mut renames: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut collisions: Vec<(PathBuf, String)> = Vec::new();
     let final_path: PathBuf;
 
     if is_folder_note {
@@ -1746,80 +2350,47 @@ fn rename_file_with_links(
         let new_folder_path = grandparent.join(&new_stem);
 
         if new_folder_path.exists() && new_folder_path != parent {
-            return Err("A folder with that name already exists".to_string());
+            collisions.push((new_folder_path.clone(), "A folder with that name already exists".to_string()));
         }
 
-        // First rename the file inside the folder
-        let temp_file_path = parent.join(&new_name);
-        fs::rename(old_path, &temp_file_path).map_err(|e| format!("Failed to rename file: {}", e))?;
-
-        // Rename _att folder if exists
+        // File inside the folder, then its _att folder, then the folder itself
+        renames.push((old_path.to_path_buf(), parent.join(&new_name)));
         let old_att = parent.join(format!("{}_att", old_stem));
-        if old_att.exists() && old_att.is_dir() {
-            let new_att = parent.join(format!("{}_att", new_stem));
-            fs::rename(&old_att, &new_att).map_err(|e| format!("Failed to rename attachment folder: {}", e))?;
+        if old_att.is_dir() {
+            renames.push((old_att, parent.join(format!("{}_att", new_stem))));
         }
-
-        // Then rename the parent folder
-        fs::rename(parent, &new_folder_path).map_err(|e| format!("Failed to rename folder: {}", e))?;
+        renames.push((parent.to_path_buf(), new_folder_path.clone()));
 
         final_path = new_folder_path.join(&new_name);
 
         // Update wiki-links for both the old folder name and old file name
-        update_wiki_links_recursive(
-            Path::new(&vault_path),
-            &old_stem,
-            &old_name_full,
-            &new_stem,
-            &new_name,
-        );
+        link_renames.push((old_stem.clone(), old_name_full.clone(), new_stem.clone(), new_name.clone()));
         // Also update links that might reference the folder name without extension
         if parent_name != old_stem {
-            update_wiki_links_recursive(
-                Path::new(&vault_path),
-                &parent_name,
-                &format!("{}.md", parent_name),
-                &new_stem,
-                &new_name,
-            );
+            link_renames.push((parent_name.clone(), format!("{}.md", parent_name), new_stem.clone(), new_name.clone()));
         }
     } else if old_path.is_dir() {
         // Folder rename (container)
         let new_path = parent.join(&new_name);
         if new_path.exists() && new_path != old_path {
-            return Err("A folder with that name already exists".to_string());
+            collisions.push((new_path.clone(), "A folder with that name already exists".to_string()));
         }
 
-        // Check if this folder has a folder note (FolderName/FolderName.md)
-        let folder_note_path = old_path.join(format!("{}.md", old_stem));
-        let has_folder_note = folder_note_path.exists();
-
-        // First rename the folder
-        fs::rename(old_path, &new_path).map_err(|e| format!("Failed to rename folder: {}", e))?;
-
-        // If folder had a folder note, rename it and its attachments
-        if has_folder_note {
-            let old_note_in_new_folder = new_path.join(format!("{}.md", old_stem));
-            let new_note_path = new_path.join(format!("{}.md", new_stem));
-            fs::rename(&old_note_in_new_folder, &new_note_path)
-                .map_err(|e| format!("Failed to rename folder note: {}", e))?;
-
-            // Rename _att folder if exists
-            let old_att = new_path.join(format!("{}_att", old_stem));
-            if old_att.exists() && old_att.is_dir() {
-                let new_att = new_path.join(format!("{}_att", new_stem));
-                fs::rename(&old_att, &new_att)
-                    .map_err(|e| format!("Failed to rename attachment folder: {}", e))?;
+        renames.push((old_path.to_path_buf(), new_path.clone()));
+
+        // If folder has a folder note (FolderName/FolderName.md), rename it and its attachments too
+        if old_path.join(format!("{}.md", old_stem)).exists() {
+            renames.push((
+                new_path.join(format!("{}.md", old_stem)),
+                new_path.join(format!("{}.md", new_stem)),
+            ));
+            if old_path.join(format!("{}_att", old_stem)).is_dir() {
+                renames.push((
+                    new_path.join(format!("{}_att", old_stem)),
+                    new_path.join(format!("{}_att", new_stem)),
+                ));
             }
-
-            // Update wiki-links for the folder note
-            update_wiki_links_recursive(
-                Path::new(&vault_path),
-                &old_stem,
-                &format!("{}.md", old_stem),
-                &new_stem,
-                &format!("{}.md", new_stem),
-            );
+            link_renames.push((old_stem.clone(), format!("{}.md", old_stem), new_stem.clone(), format!("{}.md", new_stem)));
         }
 
         final_path = new_path;
@@ -1827,72 +2398,505 @@ fn rename_file_with_links(
         // Regular file rename
         let new_path = parent.join(&new_name);
         if new_path.exists() && new_path != old_path {
-            return Err("A file with that name already exists".to_string());
+            collisions.push((new_path.clone(), "A file with that name already exists".to_string()));
         }
 
-        // Rename the file
-        fs::rename(old_path, &new_path).map_err(|e| e.to_string())?;
+        renames.push((old_path.to_path_buf(), new_path.clone()));
 
         // Rename _att folder if this is a .md file
         let is_md = new_name.ends_with(".md") || old_name_full.ends_with(".md");
-        if is_md {
-            let old_att = parent.join(format!("{}_att", old_stem));
-            if old_att.exists() && old_att.is_dir() {
-                let new_att = parent.join(format!("{}_att", new_stem));
-                fs::rename(&old_att, &new_att).map_err(|e| e.to_string())?;
-            }
+        let old_att = parent.join(format!("{}_att", old_stem));
+        if is_md && old_att.is_dir() {
+            renames.push((old_att, parent.join(format!("{}_att", new_stem))));
         }
 
         final_path = new_path;
+        link_renames.push((old_stem.clone(), old_name_full.clone(), new_stem.clone(), new_name.clone()));
+    }
 
-        // Update wiki-links across the vault
-        update_wiki_links_recursive(
-            Path::new(&vault_path),
-            &old_stem,
-            &old_name_full,
-            &new_stem,
-            &new_name,
-        );
+    let patterns: Vec<WikiLinkPatterns> = link_renames
+        .iter()
+        .map(|(os, of, ns, nf)| WikiLinkPatterns::new(os, of, ns, nf))
+        .collect();
+    let path_links = path_links::PathLinkRewriter::new(Path::new(vault_path), &renames);
+    let rewrites = plan_wiki_link_updates(Path::new(vault_path), &patterns, Some(&path_links));
+
+    Ok(PathChangePlan { final_path, renames, rewrites, collisions })
+}
+
+/// Rename a note/file/folder and rewrite wiki-links across the vault as one transaction.
+/// The whole plan is journaled under `.notology/journal` before anything is touched;
+/// a failure rolls back, and a crash is resumed or rolled back when the vault is next opened.
+#[tauri::command]
+fn rename_file_with_links(
+    file_path: String,
+    new_name: String,
+    vault_path: String,
+) -> Result<PathChangeResult, String> {
+    log::info!("[rename_file_with_links] {} -> {} (vault: {})", file_path, new_name, vault_path);
+
+    let plan = plan_rename_with_links(&file_path, &new_name, &vault_path)?;
+
+    let description = format!("rename {} -> {}", file_path, new_name);
+    commit_path_change(plan, Path::new(&vault_path), &description)
+}
+
+/// Dry run of `rename_file_with_links`: moves, per-file link diffs and collisions. Writes nothing.
+#[tauri::command]
+fn preview_rename_file_with_links(
+    file_path: String,
+    new_name: String,
+    vault_path: String,
+) -> Result<PathChangePreview, String> {
+    let plan = plan_rename_with_links(&file_path, &new_name, &vault_path)?;
+    Ok(plan.to_preview(Path::new(&vault_path)))
+}
+
+/// Dry run of `move_note` (note + `_att` folder). Writes nothing.
+#[tauri::command]
+fn preview_move_note(note_path: String, new_dir: String, vault_path: String) -> Result<PathChangePreview, String> {
+    let plan = plan_move_note(&note_path, &new_dir, Some(Path::new(&vault_path)))?;
+    Ok(plan.to_preview(Path::new(&vault_path)))
+}
+
+/// Dry run of `move_file` for a file or folder. Writes nothing.
+#[tauri::command]
+fn preview_move_file(old_path: String, new_path: String, vault_path: String) -> Result<PathChangePreview, String> {
+    let plan = plan_move_file(&old_path, &new_path, Some(Path::new(&vault_path)))?;
+    Ok(plan.to_preview(Path::new(&vault_path)))
+}
+
+/// Dry run of `move_folder`. Writes nothing.
+#[tauri::command]
+fn preview_move_folder(folder_path: String, new_parent: String, vault_path: String) -> Result<PathChangePreview, String> {
+    let plan = plan_move_folder(&folder_path, &new_parent, &vault_path)?;
+    Ok(plan.to_preview(Path::new(&vault_path)))
+}
+
+/// Plan merging `source_path` into `target_path`: the rewritten target (frontmatter union,
+/// source body appended or placed under `heading`), redirected links, attachment moves
+/// and the source note moved to `.notology/backups/`
+fn plan_merge_notes(
+    source_path: &str,
+    target_path: &str,
+    heading: Option<&str>,
+    vault_path: &str,
+) -> Result<PathChangePlan, String> {
+    let source = Path::new(source_path);
+    let target = Path::new(target_path);
+    let vault = Path::new(vault_path);
+    for note in [source, target] {
+        if !note.is_file() || note.extension().map_or(true, |e| e != "md") {
+            return Err(format!("Not a note: {}", note.display()));
+        }
+    }
+    if source == target {
+        return Err("Cannot merge a note into itself".to_string());
     }
 
-    Ok(final_path.to_string_lossy().to_string())
+    let stem_of = |p: &Path| p.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let name_of = |p: &Path| p.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let (source_stem, target_stem) = (stem_of(source), stem_of(target));
+    let (source_name, target_name) = (name_of(source), name_of(target));
+
+    let source_content = fs::read_to_string(source).map_err(|e| e.to_string())?;
+    let target_content = fs::read_to_string(target).map_err(|e| e.to_string())?;
+    let (source_fm, mut source_body) = FrontmatterParser::parse(&source_content)?;
+    let (target_fm, target_body) = FrontmatterParser::parse(&target_content)?;
+
+    let mut renames: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut rewrites: Vec<(PathBuf, String, String)> = Vec::new();
+    let timestamp = chrono::Local::now().format("%Y-%m-%dT%H-%M-%S");
+    let backup_dir = vault.join(".notology").join("backups");
+
+    // Attachments: the whole folder moves if the target has none, otherwise entry by entry
+    let source_att = source.with_file_name(format!("{}_att", source_stem));
+    let target_att = target.with_file_name(format!("{}_att", target_stem));
+    if source_att.is_dir() && !target_att.exists() {
+        renames.push((source_att.clone(), target_att.clone()));
+    } else if source_att.is_dir() {
+        for (from, to) in note_merge::plan_attachment_moves(&source_att, &target_att) {
+            let (old_name, new_name) = (name_of(&from), name_of(&to));
+            if old_name != new_name {
+                if let Some(updated) = WikiLinkPatterns::new(&old_name, &old_name, &new_name, &new_name).apply(&source_body) {
+                    source_body = updated;
+                }
+            }
+            renames.push((from, to));
+        }
+
+        let source_comments = source_att.join("comments.json");
+        let target_comments = target_att.join("comments.json");
+        if source_comments.is_file() && target_comments.is_file() {
+            let original = fs::read_to_string(&target_comments).map_err(|e| e.to_string())?;
+            let source_json = fs::read_to_string(&source_comments).map_err(|e| e.to_string())?;
+            let merged = note_merge::merge_comments(&original, &source_json)?;
+            rewrites.push((target_comments, original, merged));
+            let backup = backup_dir.join(format!("{}_att.comments.json.{}.bak", source_stem, timestamp));
+            renames.push((source_comments, resolve_collision(&backup)));
+        } else if source_comments.is_file() {
+            renames.push((source_comments, target_comments));
+        }
+    }
+
+    let body = note_merge::insert_body(&target_body, &source_body, heading);
+    let merged_fm = match (target_fm, source_fm) {
+        (Some(mut fm), Some(source_fm)) => {
+            note_merge::merge_frontmatter(&mut fm, &source_fm, &source_stem, &target_stem);
+            Some(fm)
+        }
+        (Some(fm), None) => Some(fm),
+        (None, Some(mut fm)) => {
+            fm.title = target_stem.clone();
+            Some(fm)
+        }
+        (None, None) => None,
+    };
+    let merged_fm = merged_fm.map(|mut fm| {
+        fm.modified = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%:z").to_string();
+        fm
+    });
+    let mut merged = FrontmatterParser::combine(merged_fm.as_ref(), &body)?;
+
+    // Redirect links to the source (and path-style links into its _att folder) to the target
+    let mut link_moves = vec![(source.to_path_buf(), target.to_path_buf())];
+    link_moves.extend(renames.iter().filter(|(_, to)| !to.starts_with(&backup_dir)).cloned());
+    let path_links = path_links::PathLinkRewriter::new(vault, &link_moves);
+    let patterns = [WikiLinkPatterns::new(&source_stem, &source_name, &target_stem, &target_name)];
+    for update in plan_wiki_link_updates(vault, &patterns, Some(&path_links)) {
+        if update.0 != source && update.0 != target {
+            rewrites.push(update);
+        }
+    }
+    if let Some(updated) = patterns[0].apply(&merged) {
+        merged = updated;
+    }
+    if let Some(updated) = path_links.apply(&merged) {
+        merged = updated;
+    }
+    rewrites.push((target.to_path_buf(), target_content, merged));
+
+    let backup = backup_dir.join(format!("{}.{}.bak", source_name, timestamp));
+    renames.push((source.to_path_buf(), resolve_collision(&backup)));
+
+    Ok(PathChangePlan {
+        final_path: target.to_path_buf(),
+        renames,
+        rewrites,
+        collisions: Vec::new(),
+    })
 }
 
-/// Parallel wiki link update across vault
-fn update_wiki_links_recursive(
-    dir: &Path,
-    old_stem: &str,
-    old_full: &str,
-    new_stem: &str,
-    new_full: &str,
-) {
-    // Collect all .md files first (single-threaded)
-    let md_files = collect_md_files(dir);
+/// Merge `source_path` into `target_path` as one journaled transaction.
+/// The source body is appended (or placed under `heading`), frontmatter tags/relations and
+/// type-specific fields are unioned, attachments and comments move over, links are
+/// redirected, and the source note is moved to `.notology/backups/`.
+#[tauri::command]
+fn merge_notes(
+    source_path: String,
+    target_path: String,
+    heading: Option<String>,
+    vault_path: String,
+) -> Result<PathChangeResult, String> {
+    log::info!("[merge_notes] {} -> {} (vault: {})", source_path, target_path, vault_path);
+
+    let plan = plan_merge_notes(&source_path, &target_path, heading.as_deref(), &vault_path)?;
+    let vault = Path::new(&vault_path);
+    fs::create_dir_all(vault.join(".notology").join("backups")).map_err(|e| e.to_string())?;
+
+    let source = Path::new(&source_path);
+    let source_att = source.with_file_name(format!("{}_att", source.file_stem().unwrap_or_default().to_string_lossy()));
+    let description = format!("merge {} -> {}", source_path, target_path);
+    let result = commit_path_change(plan, vault, &description)?;
+
+    // Emptied once its attachments and comments have moved
+    if source_att.is_dir() {
+        let _ = fs::remove_dir(&source_att);
+    }
+    Ok(result)
+}
+
+/// Dry run of `merge_notes`. Writes nothing.
+#[tauri::command]
+fn preview_merge_notes(
+    source_path: String,
+    target_path: String,
+    heading: Option<String>,
+    vault_path: String,
+) -> Result<PathChangePreview, String> {
+    let plan = plan_merge_notes(&source_path, &target_path, heading.as_deref(), &vault_path)?;
+    Ok(plan.to_preview(Path::new(&vault_path)))
+}
+
+/// A planned `split_note`. New notes (and their `_att` folders) are staged under
+/// `.notology/staging/` and moved into place by the same journaled transaction that
+/// rewrites the original, its comments and incoming heading links.
+struct SplitPlan {
+    /// (final note path, content)
+    new_notes: Vec<(PathBuf, String)>,
+    /// (final `_att` folder, comments.json content) for new notes that get attachments or memos
+    new_att_dirs: Vec<(PathBuf, Option<String>)>,
+    attachment_moves: Vec<(PathBuf, PathBuf)>,
+    /// (note path, original content, updated content)
+    rewrites: Vec<(PathBuf, String, String)>,
+}
+
+fn plan_split_note(note_path: &str, heading_level: usize, vault_path: &str) -> Result<SplitPlan, String> {
+    use std::collections::HashSet;
+
+    if !(1..=6).contains(&heading_level) {
+        return Err("Heading level must be between 1 and 6".to_string());
+    }
+    let note = Path::new(note_path);
+    let vault = Path::new(vault_path);
+    if !note.is_file() || note.extension().map_or(true, |e| e != "md") {
+        return Err(format!("Not a note: {}", note.display()));
+    }
+    let dir = note.parent().ok_or("Cannot determine parent directory")?;
+    let stem = note.file_stem().unwrap_or_default().to_string_lossy().to_string();
+
+    let content = fs::read_to_string(note).map_err(|e| e.to_string())?;
+    let (frontmatter, body) = FrontmatterParser::parse(&content)?;
+    // Original frontmatter text is kept as written
+    let prefix = &content[..content.len() - body.len()];
+
+    let blocks = note_split::split_sections(&body, heading_level);
+    let mut retarget = note_split::HeadingRetarget::new(&stem);
+    let mut reserved: HashSet<PathBuf> = HashSet::new();
+    // (new note path, heading, section content)
+    let mut sections: Vec<(PathBuf, String, String)> = Vec::new();
+    let mut kept_parts: Vec<&str> = Vec::new();
+    let mut original_body = String::new();
+    let mut last_was_link = false;
+
+    for block in &blocks {
+        let (part, is_link) = match block {
+            note_split::BodyBlock::Kept(text) => {
+                kept_parts.push(text);
+                (text.trim_matches('\n').to_string(), false)
+            }
+            note_split::BodyBlock::Section { heading, content, subheadings } => {
+                let mut name = note_split::note_name_for_heading(heading);
+                if name.is_empty() {
+                    name = format!("{} {}", stem, sections.len() + 1);
+                }
+                let mut path = dir.join(format!("{}.md", name));
+                let mut counter = 1;
+                while path.exists() || reserved.contains(&path) {
+                    path = dir.join(format!("{}_{}.md", name, counter));
+                    counter += 1;
+                }
+                reserved.insert(path.clone());
+                let new_stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                retarget.add_section(heading, subheadings, &new_stem);
+                sections.push((path, heading.clone(), content.clone()));
+                (format!("- [[{}]]", new_stem), true)
+            }
+        };
+        if part.is_empty() {
+            continue;
+        }
+        if !original_body.is_empty() {
+            original_body.push_str(if is_link && last_was_link { "\n" } else { "\n\n" });
+        }
+        original_body.push_str(&part);
+        last_was_link = is_link;
+    }
+    if sections.is_empty() {
+        return Err(format!("No level {} headings to split on", heading_level));
+    }
+    original_body.push('\n');
+
+    let new_att = |path: &Path| {
+        path.with_file_name(format!("{}_att", path.file_stem().unwrap_or_default().to_string_lossy()))
+    };
+
+    // Attachments referenced only by one section move with it
+    let att = dir.join(format!("{}_att", stem));
+    let kept_text = kept_parts.join("\n");
+    let kept_refs = note_split::referenced_names(&kept_text);
+    let section_refs: Vec<HashSet<String>> =
+        sections.iter().map(|(_, _, text)| note_split::referenced_names(text)).collect();
+    let mut attachment_moves = Vec::new();
+    let mut needs_att = vec![false; sections.len()];
+    if let Ok(entries) = fs::read_dir(&att) {
+        let mut files: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        files.sort();
+        for file in files {
+            let name = file.file_name().unwrap_or_default().to_string_lossy().to_string();
+            if name == "comments.json" || kept_refs.contains(&name) {
+                continue;
+            }
+            let owners: Vec<usize> = (0..sections.len()).filter(|i| section_refs[*i].contains(&name)).collect();
+            if let [owner] = owners[..] {
+                needs_att[owner] = true;
+                attachment_moves.push((file, new_att(&sections[owner].0).join(&name)));
+            }
+        }
+    }
 
-    if md_files.is_empty() {
-        return;
+    // Memos move with the section holding their anchor text
+    let mut rewrites: Vec<(PathBuf, String, String)> = Vec::new();
+    let mut section_comments: Vec<Option<String>> = vec![None; sections.len()];
+    let comments_path = att.join("comments.json");
+    if comments_path.is_file() {
+        let original_json = fs::read_to_string(&comments_path).map_err(|e| e.to_string())?;
+        let comments: Vec<serde_json::Value> =
+            serde_json::from_str(&original_json).map_err(|e| format!("Invalid comments.json: {}", e))?;
+        let texts: Vec<&str> = std::iter::once(kept_text.as_str())
+            .chain(sections.iter().map(|(_, _, text)| text.as_str()))
+            .collect();
+        let mut parts = note_split::partition_comments(comments, &texts).into_iter();
+        let kept_comments = parts.next().unwrap_or_default();
+        for (i, part) in parts.enumerate() {
+            if !part.is_empty() {
+                needs_att[i] = true;
+                section_comments[i] = Some(serde_json::to_string_pretty(&part).map_err(|e| e.to_string())?);
+            }
+        }
+        if section_comments.iter().any(Option::is_some) {
+            let kept_json = serde_json::to_string_pretty(&kept_comments).map_err(|e| e.to_string())?;
+            rewrites.push((comments_path, original_json, kept_json));
+        }
     }
 
-    // Pre-compute all patterns once
-    let patterns = WikiLinkPatterns::new(old_stem, old_full, new_stem, new_full);
+    // [[Note#Section]] links anywhere (including the split notes) follow the section
+    let path_links = path_links::PathLinkRewriter::new(vault, &attachment_moves);
+    let fix_links = |text: &str| -> String {
+        let text = retarget.apply(text).unwrap_or_else(|| text.to_string());
+        path_links.apply(&text).unwrap_or(text)
+    };
+
+    let now = chrono::Local::now();
+    let timestamp = now.format("%Y-%m-%dT%H:%M:%S%:z").to_string();
+    let taken_ids: std::collections::HashSet<String> =
+        read_note_ids(&collect_md_files(vault)).into_iter().map(|note| note.id).collect();
+    let mut new_notes = Vec::with_capacity(sections.len());
+    let mut new_att_dirs = Vec::new();
+    for (i, (path, heading, text)) in sections.iter().enumerate() {
+        let fm = frontmatter.as_ref().map(|fm| {
+            let mut fm = fm.clone();
+            fm.id = frontmatter::ids::allocate_note_id(|id| taken_ids.contains(id));
+            fm.title = heading.clone();
+            fm.created = timestamp.clone();
+            fm.modified = timestamp.clone();
+            fm.state = Default::default();
+            fm.relations.clear();
+            fm
+        });
+        let note_content = FrontmatterParser::combine(fm.as_ref(), &format!("{}\n", text))?;
+        new_notes.push((path.clone(), fix_links(&note_content)));
+        if needs_att[i] {
+            new_att_dirs.push((new_att(path), section_comments[i].take()));
+        }
+    }
+
+    let updated_original = format!("{}{}", prefix, fix_links(&original_body));
+    rewrites.push((note.to_path_buf(), content.clone(), updated_original));
+
+    let mut link_updates: Vec<(PathBuf, String, String)> = collect_md_files(vault)
+        .par_iter()
+        .filter(|path| path.as_path() != note)
+        .filter_map(|path| {
+            let original = fs::read_to_string(path).ok()?;
+            let updated = fix_links(&original);
+            (updated != original).then(|| (path.clone(), original, updated))
+        })
+        .collect();
+    link_updates.sort_by(|a, b| a.0.cmp(&b.0));
+    rewrites.extend(link_updates);
+
+    Ok(SplitPlan { new_notes, new_att_dirs, attachment_moves, rewrites })
+}
+
+/// Split a note into one note per `heading_level` section (journaled).
+/// New notes inherit the frontmatter (new `id`, heading as title) and take the attachments
+/// and memos anchored only in their section; the original keeps a link list in their place
+/// and `[[Note#Section]]` links are retargeted. Returns the new note paths.
+#[tauri::command]
+fn split_note(note_path: String, heading_level: usize, vault_path: String) -> Result<Vec<String>, String> {
+    log::info!("[split_note] {} at level {} (vault: {})", note_path, heading_level, vault_path);
+
+    let plan = plan_split_note(&note_path, heading_level, &vault_path)?;
+    let vault = Path::new(&vault_path);
+    let staging = vault
+        .join(".notology")
+        .join("staging")
+        .join(format!("{}-{}", chrono::Utc::now().format("%Y%m%d%H%M%S%3f"), std::process::id()));
+    fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+
+    let stage_and_commit = || -> Result<(), String> {
+        let mut renames = Vec::new();
+        for (n, (path, content)) in plan.new_notes.iter().enumerate() {
+            let staged = staging.join(format!("{}.md", n));
+            fs::write(&staged, content).map_err(|e| e.to_string())?;
+            renames.push((staged, path.clone()));
+        }
+        for (n, (att_dir, comments)) in plan.new_att_dirs.iter().enumerate() {
+            let staged = staging.join(format!("{}_att", n));
+            fs::create_dir_all(&staged).map_err(|e| e.to_string())?;
+            if let Some(json) = comments {
+                fs::write(staged.join("comments.json"), json).map_err(|e| e.to_string())?;
+            }
+            renames.push((staged, att_dir.clone()));
+        }
+        renames.extend(plan.attachment_moves.iter().cloned());
+
+        let change = PathChangePlan {
+            final_path: PathBuf::from(&note_path),
+            renames,
+            rewrites: plan.rewrites.clone(),
+            collisions: Vec::new(),
+        };
+        let result = commit_path_change(change, vault, &format!("split {}", note_path))?;
+        if !result.skipped.is_empty() {
+            log::warn!("[split_note] Links not updated in notes changed since planning: {:?}", result.skipped);
+        }
+        Ok(())
+    };
+    let result = stage_and_commit();
+    let _ = fs::remove_dir_all(&staging);
+    result?;
+
+    Ok(plan.new_notes.iter().map(|(p, _)| p.to_string_lossy().to_string()).collect())
+}
+
+/// Parallel wiki link update planning across the vault.
+/// Returns (note path, original content, updated content) for every note the patterns
+/// (and path-style link rewrites) change; nothing is written.
+fn plan_wiki_link_updates(
+    dir: &Path,
+    patterns: &[WikiLinkPatterns],
+    path_links: Option<&path_links::PathLinkRewriter>,
+) -> Vec<(PathBuf, String, String)> {
+    let path_links = path_links.filter(|r| !r.is_empty());
+    if patterns.is_empty() && path_links.is_none() {
+        return Vec::new();
+    }
+
+    // Collect all .md files first (single-threaded)
+    let md_files = collect_md_files(dir);
 
     // Process files in parallel
-    let updates: Vec<(PathBuf, String)> = md_files
+    let mut updates: Vec<(PathBuf, String, String)> = md_files
         .par_iter()
         .filter_map(|path| {
-            if let Ok(content) = fs::read_to_string(path) {
-                if let Some(updated) = patterns.apply(&content) {
-                    return Some((path.clone(), updated));
+            let original = fs::read_to_string(path).ok()?;
+            let mut updated: Option<String> = None;
+            for p in patterns {
+                if let Some(next) = p.apply(updated.as_deref().unwrap_or(&original)) {
+                    updated = Some(next);
                 }
             }
-            None
+            if let Some(next) = path_links.and_then(|r| r.apply(updated.as_deref().unwrap_or(&original))) {
+                updated = Some(next);
+            }
+            updated.map(|u| (path.clone(), original, u))
         })
         .collect();
 
-    // Write updates (can be parallelized too, but file I/O often bottlenecks)
-    for (path, content) in updates {
-        let _ = fs::write(&path, &content);
-    }
+    updates.sort_by(|a, b| a.0.cmp(&b.0));
+    updates
 }
 
 /// Collect all .md files in directory recursively
@@ -2077,6 +3081,41 @@ fn update_note_frontmatter(
 
     let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
 
+    let mut new_mapping = match serde_yaml::from_str::<serde_yaml::Value>(&new_frontmatter_yaml) {
+        Ok(serde_yaml::Value::Mapping(map)) => Some(map),
+        _ => None,
+    };
+    // Workflow changes must follow the vault's transition rules and are appended to
+    // `state_history`, which is always carried over from the file
+    let mut new_frontmatter_yaml = new_frontmatter_yaml;
+    if let Some(new_mapping) = new_mapping.as_mut() {
+        let old_mapping = match search::parser::split_frontmatter_body(&content).0.map(|raw| serde_yaml::from_str::<serde_yaml::Value>(&raw)) {
+            Some(Ok(serde_yaml::Value::Mapping(map))) => map,
+            _ => serde_yaml::Mapping::new(),
+        };
+        let workflow = match find_vault_root(path) {
+            Some(vault) => load_vault_json_config(&vault)?,
+            None => Default::default(),
+        };
+        let at = chrono::Local::now().format(autofix::DEFAULT_DATE_FORMAT).to_string();
+        let change = frontmatter::workflow::record_transition(&old_mapping, new_mapping, &workflow, &vault_lock::get_hostname(), &at)?;
+        if let Some(change) = change {
+            log::info!("[update_note_frontmatter] {}: {} -> {}", note_path, change.from, change.to);
+        }
+        if old_mapping.contains_key(frontmatter::workflow::HISTORY_KEY) || new_mapping.contains_key(frontmatter::workflow::HISTORY_KEY) {
+            new_frontmatter_yaml = serde_yaml::to_string(new_mapping).map_err(|e| e.to_string())?.trim_end().to_string();
+        }
+    }
+
+    // Existing frontmatter that parses: rewrite only the changed entries so comments,
+    // quoting and key order survive
+    if let Some(new_mapping) = new_mapping {
+        if let Ok(new_content) = frontmatter::yaml_edit::update_frontmatter_content(&content, &new_mapping) {
+            atomic_write_file(path, new_content.as_bytes())?;
+            return Ok(());
+        }
+    }
+
     // Split into frontmatter and body
     let new_content = if content.starts_with("---") {
         // Find the closing ---
@@ -2096,6 +3135,578 @@ fn update_note_frontmatter(
     Ok(())
 }
 
+#[derive(Serialize)]
+pub struct TypeConversion {
+    from: String,
+    to: String,
+    /// Type-specific fields that moved to another field, a tag facet or `extra`
+    moves: Vec<frontmatter::convert::FieldMove>,
+}
+
+/// Rewrite the note at `path` as `new_type`; the body is kept and the old file backed up
+fn convert_note_file(path: &Path, new_type: &str) -> Result<TypeConversion, String> {
+    let to: frontmatter::types::NoteType = serde_yaml::from_value(serde_yaml::Value::String(new_type.to_string()))
+        .map_err(|e| format!("Unknown note type: {}", e))?;
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let fm = FrontmatterParser::parse(&content)?.0.ok_or("Note has no frontmatter")?;
+
+    let vault = find_vault_root(path);
+    let schemas = match &vault {
+        Some(vault) => frontmatter::schemas::VaultSchemas::load(vault)?,
+        None => Default::default(),
+    };
+    let (mut converted, moves) = frontmatter::convert::convert_frontmatter(&fm, to, &schemas)?;
+    converted.modified = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%:z").to_string();
+    let updated = FrontmatterParser::update(&content, &fm, &converted)?;
+
+    if let Some(vault) = &vault {
+        backup_before_save(path, vault)?;
+    }
+    atomic_write_file(path, updated.as_bytes())?;
+
+    Ok(TypeConversion {
+        from: format!("{:?}", fm.note_type),
+        to: format!("{:?}", converted.note_type),
+        moves,
+    })
+}
+
+/// Change a note's type, mapping type-specific fields per type pair (e.g. TASK `due` ->
+/// EVENT `date`, MTG `participants` -> `who` tags). Unmapped fields are kept under `extra`;
+/// the result must validate against the target type's schema.
+#[tauri::command]
+async fn convert_note_type(
+    path: String,
+    new_type: String,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<TypeConversion, String> {
+    let note_path = PathBuf::from(&path);
+    let conversion = convert_note_file(&note_path, &new_type)?;
+
+    let search_state = state.lock().map_err(|e| e.to_string())?;
+    if let Some(index) = search_state.index.as_ref() {
+        index.index_files(&[note_path])?;
+    }
+    Ok(conversion)
+}
+
+#[derive(Serialize)]
+pub struct BulkEditFailure {
+    path: String,
+    error: String,
+}
+
+#[derive(Serialize)]
+pub struct BulkEditResult {
+    dry_run: bool,
+    /// Per-note frontmatter diffs (written unless dry_run)
+    changed: Vec<FileDiffPreview>,
+    unchanged: usize,
+    failed: Vec<BulkEditFailure>,
+}
+
+/// Apply frontmatter edits to `paths`. Changed notes are backed up and written unless
+/// `dry_run`; returns the result and the paths actually written.
+fn apply_bulk_frontmatter_edits(
+    paths: &[String],
+    edits: &[bulk_edit::FrontmatterEdit],
+    dry_run: bool,
+    vault_path: &Path,
+) -> Result<(BulkEditResult, Vec<PathBuf>), String> {
+    let modified = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%:z").to_string();
+    // Workflow changes follow the same transition rules and history as single-note edits;
+    // notes whose transition is not allowed are reported as failed
+    let workflow: frontmatter::workflow::WorkflowConfig = load_vault_json_config(vault_path)?;
+    let host = vault_lock::get_hostname();
+    Ok(apply_bulk_content_edits(paths, dry_run, vault_path, |_, content| {
+        bulk_edit::edit_frontmatter_content(content, &modified, |fm| {
+            let old = fm.clone();
+            let changed = bulk_edit::apply_edits(fm, edits)?;
+            if changed {
+                frontmatter::workflow::record_transition(&old, fm, &workflow, &host, &modified)?;
+            }
+            Ok(changed)
+        })
+    }))
+}
+
+/// Shared write path of bulk edits: `edit` maps (path, note content) to its updated content
+/// (None = unchanged); notes are planned in parallel, then backed up and written in order.
+fn apply_bulk_content_edits(
+    paths: &[String],
+    dry_run: bool,
+    vault_path: &Path,
+    edit: impl Fn(&str, &str) -> Result<Option<String>, String> + Sync,
+) -> (BulkEditResult, Vec<PathBuf>) {
+    let planned: Vec<_> = paths
+        .par_iter()
+        .map(|path| {
+            let outcome = fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| Ok(edit(path, &content)?.map(|u| (content, u))));
+            (path.clone(), outcome)
+        })
+        .collect();
+
+    let mut result = BulkEditResult { dry_run, changed: Vec::new(), unchanged: 0, failed: Vec::new() };
+    let mut written = Vec::new();
+    for (path, outcome) in planned {
+        let (original, updated) = match outcome {
+            Ok(Some(change)) => change,
+            Ok(None) => {
+                result.unchanged += 1;
+                continue;
+            }
+            Err(error) => {
+                result.failed.push(BulkEditFailure { path, error });
+                continue;
+            }
+        };
+        if !dry_run {
+            let file = Path::new(&path);
+            if let Err(error) = backup_before_save(file, vault_path).and_then(|_| atomic_write_file(file, updated.as_bytes())) {
+                result.failed.push(BulkEditFailure { path, error });
+                continue;
+            }
+            written.push(file.to_path_buf());
+        }
+        let relative = Path::new(&path)
+            .strip_prefix(vault_path)
+            .unwrap_or(Path::new(&path))
+            .to_string_lossy()
+            .replace('\\', "/");
+        result.changed.push(FileDiffPreview {
+            diff: text_diff::unified_diff(&relative, &original, &updated),
+            path,
+        });
+    }
+    (result, written)
+}
+
+/// Apply frontmatter edits (tags, workflow, priority, extra keys) to every note matching
+/// `filter` or listed in `paths`. Written notes are backed up first and re-indexed with a
+/// single commit. `dry_run` returns the diffs without writing.
+#[tauri::command]
+async fn bulk_update_frontmatter(
+    filter: Option<NoteFilter>,
+    paths: Option<Vec<String>>,
+    edits: Vec<bulk_edit::FrontmatterEdit>,
+    dry_run: bool,
+    vault_path: String,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<BulkEditResult, String> {
+    bulk_edit::validate_edits(&edits)?;
+
+    let targets = match (paths, filter) {
+        (Some(paths), _) => paths,
+        (None, Some(filter)) => {
+            let search_state = state.lock().map_err(|e| e.to_string())?;
+            let index = search_state.index.as_ref().ok_or("Search index not initialized")?;
+            index.query_notes(&filter)?.into_iter().map(|m| m.path).collect()
+        }
+        (None, None) => return Err("Either a filter or a path list is required".to_string()),
+    };
+
+    let (result, written) = apply_bulk_frontmatter_edits(&targets, &edits, dry_run, Path::new(&vault_path))?;
+
+    if !written.is_empty() {
+        let search_state = state.lock().map_err(|e| e.to_string())?;
+        if let Some(index) = search_state.index.as_ref() {
+            index.index_files(&written)?;
+        }
+    }
+
+    log::info!(
+        "[bulk_update_frontmatter] {} changed, {} unchanged, {} failed (dry_run: {})",
+        result.changed.len(),
+        result.unchanged,
+        result.failed.len(),
+        dry_run
+    );
+    Ok(result)
+}
+
+/// Notes carrying any tag `refactor` rewrites, found through the index `tags` field
+fn tag_refactor_targets(index: &SearchIndex, refactor: &bulk_edit::TagRefactor) -> Result<Vec<String>, String> {
+    let source_tags: Vec<String> = refactor
+        .replacements()?
+        .iter()
+        .map(|((facet, name), _)| format!("{}/{}", facet, name))
+        .collect();
+    index.notes_with_tags(&source_tags)
+}
+
+/// Rewrite the tags of `targets` (backed up first). Returns the report and the paths actually
+/// written; the caller re-indexes those. Does not touch the index.
+fn retag_notes(
+    targets: &[String],
+    refactor: &bulk_edit::TagRefactor,
+    dry_run: bool,
+    vault_path: &Path,
+) -> Result<(BulkEditResult, Vec<PathBuf>), String> {
+    let replacements = refactor.replacements()?;
+    let modified = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%:z").to_string();
+    let (result, written) = apply_bulk_content_edits(targets, dry_run, vault_path, |_, content| {
+        bulk_edit::edit_frontmatter_content(content, &modified, |fm| {
+            let mut changed = false;
+            for (from, to) in &replacements {
+                changed |= bulk_edit::retag(fm, from, to.as_ref())?;
+            }
+            Ok(changed)
+        })
+    });
+
+    log::info!(
+        "[refactor_tags] {:?}: {} changed, {} failed (dry_run: {})",
+        refactor,
+        result.changed.len(),
+        result.failed.len(),
+        dry_run
+    );
+    Ok((result, written))
+}
+
+/// Rewrite tags across the vault: rename, merge, move to another facet, or delete.
+/// Updates the index incrementally (no full reindex). `dry_run` returns the diffs only.
+#[tauri::command]
+async fn refactor_tags(
+    refactor: bulk_edit::TagRefactor,
+    dry_run: bool,
+    vault_path: String,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<BulkEditResult, String> {
+    let targets = {
+        let search_state = state.lock().map_err(|e| e.to_string())?;
+        let index = search_state.index.as_ref().ok_or("Search index not initialized")?;
+        tag_refactor_targets(index, &refactor)?
+    };
+
+    // Files are rewritten without holding the search state, so search and the watcher keep running
+    let (result, written) = retag_notes(&targets, &refactor, dry_run, Path::new(&vault_path))?;
+
+    if !written.is_empty() {
+        let search_state = state.lock().map_err(|e| e.to_string())?;
+        if let Some(index) = search_state.index.as_ref() {
+            index.index_files(&written)?;
+        }
+    }
+    Ok(result)
+}
+
+#[derive(Serialize)]
+pub struct LegacyTagCount {
+    tag: String,
+    notes: usize,
+}
+
+#[derive(Serialize)]
+pub struct LegacyTagMigration {
+    result: BulkEditResult,
+    /// Legacy tags no rule resolves; they stay under `tags.legacy` until mapped
+    unresolved: Vec<LegacyTagCount>,
+}
+
+/// Resolve legacy list-style tags in every note of the vault with `mapping`.
+/// Returns the migration report and the paths actually written.
+fn migrate_legacy_tags_in_vault(
+    vault_path: &Path,
+    mapping: &tag_migration::TagMapping,
+    dry_run: bool,
+) -> (LegacyTagMigration, Vec<PathBuf>) {
+    let paths: Vec<String> = collect_md_files(vault_path)
+        .iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect();
+    let unresolved_counts = Mutex::new(std::collections::BTreeMap::<String, usize>::new());
+
+    let modified = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%:z").to_string();
+    let (result, written) = apply_bulk_content_edits(&paths, dry_run, vault_path, |_, content| {
+        if !content.starts_with("---") {
+            return Ok(None);
+        }
+        let mut unresolved = Vec::new();
+        let updated = bulk_edit::edit_frontmatter_content(content, &modified, |fm| {
+            tag_migration::migrate_legacy_tags(fm, mapping, &mut unresolved)
+        })?;
+        unresolved.sort();
+        unresolved.dedup();
+        if let Ok(mut counts) = unresolved_counts.lock() {
+            for tag in unresolved {
+                *counts.entry(tag).or_insert(0) += 1;
+            }
+        }
+        Ok(updated)
+    });
+
+    let unresolved = unresolved_counts
+        .into_inner()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(tag, notes)| LegacyTagCount { tag, notes })
+        .collect();
+    log::info!(
+        "[migrate_legacy_tags] {} changed, {} failed (dry_run: {})",
+        result.changed.len(),
+        result.failed.len(),
+        dry_run
+    );
+    (LegacyTagMigration { result, unresolved }, written)
+}
+
+/// Legacy tag mapping table of the vault (`.notology/tag-mapping.json`)
+#[tauri::command]
+fn get_tag_mapping(vault_path: String) -> Result<tag_migration::TagMapping, String> {
+    load_vault_json_config(Path::new(&vault_path))
+}
+
+#[tauri::command]
+fn save_tag_mapping(vault_path: String, mapping: tag_migration::TagMapping) -> Result<(), String> {
+    save_vault_json_config(Path::new(&vault_path), &mapping)
+}
+
+/// Move legacy `tags: [a, b]` lists into facets using the mapping table (or `mapping`, to
+/// preview unsaved rules). Unresolved tags are kept under `tags.legacy`, never dropped.
+#[tauri::command]
+async fn migrate_legacy_tags(
+    mapping: Option<tag_migration::TagMapping>,
+    dry_run: bool,
+    vault_path: String,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<LegacyTagMigration, String> {
+    let vault = Path::new(&vault_path);
+    let mapping = match mapping {
+        Some(mapping) => {
+            mapping.validate()?;
+            mapping
+        }
+        None => load_vault_json_config(vault)?,
+    };
+    let (migration, written) = migrate_legacy_tags_in_vault(vault, &mapping, dry_run);
+    if !written.is_empty() {
+        let search_state = state.lock().map_err(|e| e.to_string())?;
+        if let Some(index) = search_state.index.as_ref() {
+            index.index_files(&written)?;
+        }
+    }
+    Ok(migration)
+}
+
+#[derive(Serialize)]
+pub struct FrontmatterFixReport {
+    result: BulkEditResult,
+    /// What was fixed per note, and validator errors that need a manual edit
+    notes: Vec<autofix::NoteFix>,
+}
+
+/// Auto-fix the frontmatter of `paths`, normalizing dates to `date_format`.
+/// Returns the report and the paths actually written.
+fn fix_frontmatter_in_files(
+    paths: &[String],
+    date_format: &str,
+    dry_run: bool,
+    vault_path: &Path,
+) -> Result<(FrontmatterFixReport, Vec<PathBuf>), String> {
+    autofix::validate_date_format(date_format)?;
+    let schemas = frontmatter::schemas::VaultSchemas::load(vault_path)?;
+    let notes = Mutex::new(Vec::new());
+    // Ids in the vault plus those given out in this run; a dry run only peeks at the allocator
+    let taken: Mutex<std::collections::HashSet<String>> =
+        Mutex::new(read_note_ids(&collect_md_files(vault_path)).into_iter().map(|note| note.id).collect());
+    let new_id = |seed: &str| -> String {
+        let mut taken = taken.lock().unwrap_or_else(|e| e.into_inner());
+        let id = if dry_run {
+            frontmatter::ids::peek_note_id(seed, |id| taken.contains(id))
+        } else {
+            frontmatter::ids::claim_note_id(seed, |id| taken.contains(id))
+        };
+        taken.insert(id.clone());
+        id
+    };
+
+    let (result, written) = apply_bulk_content_edits(paths, dry_run, vault_path, |path, content| {
+        let (updated, mut fix) = autofix::fix_note_content(content, date_format, &schemas, &new_id)?;
+        if !fix.fixes.is_empty() || !fix.remaining.is_empty() {
+            fix.path = path.to_string();
+            if let Ok(mut notes) = notes.lock() {
+                notes.push(fix);
+            }
+        }
+        Ok(updated)
+    });
+
+    let mut notes = notes.into_inner().unwrap_or_default();
+    notes.sort_by(|a, b| a.path.cmp(&b.path));
+    log::info!(
+        "[fix_frontmatter] {} changed, {} failed (dry_run: {})",
+        result.changed.len(),
+        result.failed.len(),
+        dry_run
+    );
+    Ok((FrontmatterFixReport { result, notes }, written))
+}
+
+/// Normalize `created`/`modified` to one ISO format (default `%Y-%m-%dT%H:%M:%S%:z`), add
+/// missing ids and state defaults, clamp maturity to 1-5, fix enum casing and quoting that
+/// breaks YAML. `paths` limits the fix to some notes; otherwise the whole vault is fixed.
+#[tauri::command]
+async fn fix_frontmatter(
+    paths: Option<Vec<String>>,
+    date_format: Option<String>,
+    dry_run: bool,
+    vault_path: String,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<FrontmatterFixReport, String> {
+    let vault = Path::new(&vault_path);
+    let paths = paths.unwrap_or_else(|| {
+        collect_md_files(vault).iter().map(|p| p.to_string_lossy().to_string()).collect()
+    });
+    let date_format = date_format.as_deref().unwrap_or(autofix::DEFAULT_DATE_FORMAT);
+    let (report, written) = fix_frontmatter_in_files(&paths, date_format, dry_run, vault)?;
+    if !written.is_empty() {
+        let search_state = state.lock().map_err(|e| e.to_string())?;
+        if let Some(index) = search_state.index.as_ref() {
+            index.index_files(&written)?;
+        }
+    }
+    Ok(report)
+}
+
+/// Frontmatter id of a note, with its `created` for ordering duplicates
+struct NoteIdEntry {
+    path: PathBuf,
+    id: String,
+    created: String,
+}
+
+/// Ids of the notes in `files` that have one (read in parallel)
+fn read_note_ids(files: &[PathBuf]) -> Vec<NoteIdEntry> {
+    use search::parser::{extract_date_field, extract_note_id, parse_frontmatter, split_frontmatter_body};
+    files
+        .par_iter()
+        .filter_map(|path| {
+            let content = fs::read_to_string(path).ok()?;
+            let frontmatter = parse_frontmatter(&split_frontmatter_body(&content).0?);
+            let id = extract_note_id(&frontmatter);
+            (!id.is_empty()).then(|| NoteIdEntry {
+                path: path.clone(),
+                id,
+                created: extract_date_field(&frontmatter, "created"),
+            })
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct DuplicateNoteId {
+    id: String,
+    /// Oldest note (by `created`) first; it keeps the id when duplicates are fixed
+    paths: Vec<String>,
+}
+
+fn find_duplicate_ids(files: &[PathBuf]) -> Vec<DuplicateNoteId> {
+    let mut by_id: std::collections::BTreeMap<String, Vec<NoteIdEntry>> = std::collections::BTreeMap::new();
+    for entry in read_note_ids(files) {
+        by_id.entry(entry.id.clone()).or_default().push(entry);
+    }
+    by_id
+        .into_iter()
+        .filter(|(_, notes)| notes.len() > 1)
+        .map(|(id, mut notes)| {
+            notes.sort_by_cached_key(|n| {
+                let created = autofix::parse_datetime(&n.created);
+                (created.is_none(), created, n.path.clone())
+            });
+            DuplicateNoteId { id, paths: notes.into_iter().map(|n| n.path.to_string_lossy().to_string()).collect() }
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct NoteIdReassignment {
+    path: String,
+    old_id: String,
+    new_id: String,
+}
+
+#[derive(Serialize)]
+pub struct DuplicateIdFix {
+    result: BulkEditResult,
+    reassigned: Vec<NoteIdReassignment>,
+}
+
+/// Give every note that shares its id with an older note a new collision-free id.
+/// `[[id:...]]` links to a duplicated id keep pointing at the oldest note.
+fn fix_duplicate_ids_in_vault(vault_path: &Path, dry_run: bool) -> (DuplicateIdFix, Vec<PathBuf>) {
+    let files = collect_md_files(vault_path);
+    // Ids in the vault plus those given out in this run; a dry run only peeks at the allocator
+    // so the preview shows the ids a real run assigns
+    let mut taken: std::collections::HashSet<String> = read_note_ids(&files).into_iter().map(|n| n.id).collect();
+    let mut new_ids = std::collections::HashMap::new();
+    let mut reassigned = Vec::new();
+    for duplicate in find_duplicate_ids(&files) {
+        for path in duplicate.paths.into_iter().skip(1) {
+            let new_id = if dry_run {
+                frontmatter::ids::peek_note_id(&duplicate.id, |id| taken.contains(id))
+            } else {
+                frontmatter::ids::claim_note_id(&duplicate.id, |id| taken.contains(id))
+            };
+            taken.insert(new_id.clone());
+            new_ids.insert(path.clone(), new_id.clone());
+            reassigned.push(NoteIdReassignment { path, old_id: duplicate.id.clone(), new_id });
+        }
+    }
+
+    let paths: Vec<String> = reassigned.iter().map(|r| r.path.clone()).collect();
+    let modified = chrono::Local::now().format(autofix::DEFAULT_DATE_FORMAT).to_string();
+    let (result, written) = apply_bulk_content_edits(&paths, dry_run, vault_path, |path, content| {
+        let new_id = new_ids.get(path).ok_or("No new id planned")?;
+        bulk_edit::edit_frontmatter_content(content, &modified, |fm| {
+            fm.insert("id".into(), serde_yaml::Value::String(new_id.clone()));
+            Ok(true)
+        })
+    });
+    log::info!(
+        "[fix_duplicate_note_ids] {} changed, {} failed (dry_run: {})",
+        result.changed.len(),
+        result.failed.len(),
+        dry_run
+    );
+    (DuplicateIdFix { result, reassigned }, written)
+}
+
+/// Notes that share a frontmatter id, grouped by id
+#[tauri::command]
+async fn find_duplicate_note_ids(vault_path: String) -> Result<Vec<DuplicateNoteId>, String> {
+    Ok(find_duplicate_ids(&collect_md_files(Path::new(&vault_path))))
+}
+
+/// Reassign duplicated ids (the oldest note keeps its id), with backups and a dry-run preview
+#[tauri::command]
+async fn fix_duplicate_note_ids(
+    dry_run: bool,
+    vault_path: String,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<DuplicateIdFix, String> {
+    let (fix, written) = fix_duplicate_ids_in_vault(Path::new(&vault_path), dry_run);
+    if !written.is_empty() {
+        let search_state = state.lock().map_err(|e| e.to_string())?;
+        if let Some(index) = search_state.index.as_ref() {
+            index.index_files(&written)?;
+        }
+    }
+    Ok(fix)
+}
+
+/// Frontmatter id -> note path, for resolving `[[id:...]]` links in the editor
+#[tauri::command]
+async fn get_note_ids(
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<std::collections::HashMap<String, String>, String> {
+    let search_state = state.lock().map_err(|e| e.to_string())?;
+    let index = search_state.index.as_ref().ok_or("Search index not initialized")?;
+    index.note_ids()
+}
+
 #[cfg(feature = "devtools")]
 #[tauri::command]
 fn toggle_devtools(webview_window: tauri::WebviewWindow) {
@@ -2292,6 +3903,52 @@ async fn reindex_memos(
     memo_index.full_reindex()
 }
 
+#[tauri::command]
+fn get_periodic_notes_config(vault_path: String) -> Result<periodic_notes::PeriodicNotesConfig, String> {
+    load_vault_json_config(Path::new(&vault_path))
+}
+
+#[tauri::command]
+fn save_periodic_notes_config(vault_path: String, config: periodic_notes::PeriodicNotesConfig) -> Result<(), String> {
+    save_vault_json_config(Path::new(&vault_path), &config)
+}
+
+/// Daily/weekly/monthly note for `date` (YYYY-MM-DD, default today). With `create` a missing
+/// note is created, rolling over open tasks; an existing note is returned untouched.
+#[tauri::command]
+fn open_periodic_note(
+    vault_path: String,
+    period: periodic_notes::Period,
+    date: Option<String>,
+    create: bool,
+    state: tauri::State<'_, Mutex<SearchState>>,
+) -> Result<periodic_notes::PeriodicNote, String> {
+    let vault = Path::new(&vault_path);
+    let date = match date {
+        Some(date) => chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(|e| format!("Invalid date {}: {}", date, e))?,
+        None => chrono::Local::now().date_naive(),
+    };
+    let config: periodic_notes::PeriodicNotesConfig = load_vault_json_config(vault)?;
+
+    let memo_index = state.lock().map_err(|e| e.to_string())?.memo_index.clone();
+    let memos = match memo_index {
+        Some(memo_index) if create => memo_index.query_memos(&MemoQueryFilter {
+            start_date: None,
+            end_date: None,
+            tasks_only: true,
+            completed: Some(false),
+            note_path: None,
+        })?,
+        _ => Vec::new(),
+    };
+
+    let note = periodic_notes::open_periodic_note(vault, &config, period, date, create, &memos, |id| is_indexed_note_id(&state, id))?;
+    if note.created {
+        log::info!("[open_periodic_note] Created {} ({} tasks rolled over)", note.path, note.rolled_over.len());
+    }
+    Ok(note)
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct CalendarMemo {
@@ -3067,6 +4724,8 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             parse_frontmatter,
             validate_frontmatter,
+            get_note_schema,
+            lint_vault,
             frontmatter_to_yaml,
             yaml_to_frontmatter,
             read_directory,
@@ -3087,9 +4746,36 @@ pub fn run() {
             open_in_default_app,
             reveal_in_explorer,
             create_note_with_template,
+            list_note_templates,
+            render_note_template,
+            create_note_from_template,
+            get_periodic_notes_config,
+            save_periodic_notes_config,
+            open_periodic_note,
+            get_workflow_config,
+            save_workflow_config,
             rename_file_with_links,
+            preview_rename_file_with_links,
+            preview_move_note,
+            preview_move_file,
+            preview_move_folder,
+            move_folder,
+            rename_folder,
+            merge_notes,
+            preview_merge_notes,
+            split_note,
             delete_note,
             update_note_frontmatter,
+            convert_note_type,
+            bulk_update_frontmatter,
+            refactor_tags,
+            get_tag_mapping,
+            save_tag_mapping,
+            migrate_legacy_tags,
+            fix_frontmatter,
+            find_duplicate_note_ids,
+            fix_duplicate_note_ids,
+            get_note_ids,
             toggle_devtools,
             set_window_icon,
             create_hover_window,
@@ -3099,6 +4785,14 @@ pub fn run() {
             query_notes,
             get_relationships,
             get_graph_data,
+            get_local_graph,
+            get_broken_links,
+            resolve_transclusions,
+            find_unlinked_mentions,
+            generate_suggestions,
+            link_unlinked_mentions,
+            export_graph,
+            query_relations,
             reindex_vault,
             get_all_used_tags,
             search_attachments,
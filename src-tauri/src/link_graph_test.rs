@@ -0,0 +1,123 @@
+// In-memory link graph - SearchIndex 연동 (index_file/remove_file/remove_dir) 검증
+
+#[cfg(test)]
+mod link_graph_tests {
+    use crate::search::SearchIndex;
+    use crate::test_common::{open_index, path_str, write_note, IndexDirGuard};
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// 인덱스 노트의 역링크 150개가 모두 반환되어야 함 (기존 100개 제한 제거)
+    #[test]
+    fn test_backlinks_exceed_previous_cap() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        write_note(&vault.join("색인.md"), "모든 노트의 색인");
+        for i in 0..150 {
+            write_note(&vault.join(format!("노트{}.md", i)), "[[색인]]");
+        }
+
+        let (index, _guard) = open_index(vault);
+        let rel = index
+            .get_relationships(&vault.join("색인.md").to_string_lossy())
+            .unwrap();
+
+        assert_eq!(rel.incoming_links.len(), 150);
+        assert!(rel.incoming_links.iter().all(|l| l.context == "[[색인]]"));
+    }
+
+    #[test]
+    fn test_graph_follows_index_file_and_remove_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let a = vault.join("A.md");
+        let b = vault.join("B.md");
+        write_note(&a, "[[B]] [[없는노트]]");
+        write_note(&b, "B 본문");
+
+        let (index, _guard) = open_index(vault);
+        let b_str = b.to_string_lossy().to_string();
+
+        let rel = index.get_relationships(&a.to_string_lossy()).unwrap();
+        assert_eq!(rel.outgoing_links.len(), 1, "해결되지 않는 링크는 나가는 링크에서 제외");
+        assert_eq!(rel.outgoing_links[0].path, b_str);
+
+        let broken = index.get_broken_links(None).unwrap();
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].link, "없는노트");
+
+        // 링크 제거 후 재인덱싱 → 역링크 사라짐
+        write_note(&a, "링크 없음");
+        index.index_file(&a).unwrap();
+        assert!(index.get_relationships(&b_str).unwrap().incoming_links.is_empty());
+        assert!(index.get_broken_links(None).unwrap().is_empty());
+
+        // 링크 복원 후 대상 노트 삭제 → 깨진 링크로 보고
+        write_note(&a, "[[B]]");
+        index.index_file(&a).unwrap();
+        fs::remove_file(&b).unwrap();
+        index.remove_file(&b).unwrap();
+        let broken = index.get_broken_links(Some(&a.to_string_lossy())).unwrap();
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].link, "B");
+    }
+
+    #[test]
+    fn test_attachment_links_are_not_broken_when_file_exists() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let att = vault.join("보고서_att");
+        fs::create_dir_all(&att).unwrap();
+        fs::write(att.join("자료.pdf"), b"%PDF").unwrap();
+        write_note(&vault.join("보고서.md"), "[[자료.pdf]] [[없는파일.xlsx]]");
+
+        let (index, _guard) = open_index(vault);
+        let broken = index.get_broken_links(None).unwrap();
+
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].link, "없는파일.xlsx");
+    }
+
+    #[test]
+    fn test_remove_dir_drops_contained_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let folder = vault.join("프로젝트");
+        fs::create_dir_all(&folder).unwrap();
+        write_note(&folder.join("하위1.md"), "[[루트]]");
+        write_note(&folder.join("하위2.md"), "[[루트]]");
+        write_note(&vault.join("루트.md"), "루트");
+
+        let (index, _guard) = open_index(vault);
+        let root = vault.join("루트.md").to_string_lossy().to_string();
+        assert_eq!(index.get_relationships(&root).unwrap().incoming_links.len(), 2);
+
+        fs::remove_dir_all(&folder).unwrap();
+        assert_eq!(index.remove_dir(&folder).unwrap(), 2);
+
+        assert!(index.get_relationships(&root).unwrap().incoming_links.is_empty());
+        assert!(index.search("하위1", 10).unwrap().is_empty(), "tantivy 인덱스에서도 제거되어야 함");
+    }
+
+    #[test]
+    fn test_link_graph_rebuilt_on_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        write_note(&vault.join("A.md"), "[[B]]");
+        write_note(&vault.join("B.md"), "B");
+
+        let vault_str = path_str(vault);
+        let _guard = IndexDirGuard(vault_str.clone());
+        {
+            let index = SearchIndex::new(&vault_str).unwrap();
+            index.full_reindex().unwrap();
+        }
+
+        // 재시작: 파일을 다시 읽지 않고 기존 인덱스에서 그래프 복원
+        let reopened = SearchIndex::new(&vault_str).unwrap();
+        let rel = reopened
+            .get_relationships(&vault.join("B.md").to_string_lossy())
+            .unwrap();
+        assert_eq!(rel.incoming_links.len(), 1);
+    }
+}
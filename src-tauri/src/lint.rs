@@ -0,0 +1,235 @@
+//! Vault-wide frontmatter lint
+//!
+//! Every note is checked in parallel (YAML syntax, note type, schema); duplicate ids and
+//! relation targets are checked afterwards across the whole vault. Issues carry the file
+//! line they refer to so the UI can jump to them.
+
+use crate::frontmatter::schemas::VaultSchemas;
+use crate::frontmatter::types::Frontmatter;
+use crate::frontmatter::FrontmatterParser;
+use crate::search::parser::{extract_relations, try_parse_frontmatter};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+pub(crate) const NOTE_TYPES: [&str; 15] = [
+    "NOTE", "MTG", "PAPER", "THEO", "TASK", "LIT", "EVENT", "CONTACT", "CONTAINER", "ADM", "OFA", "SEM", "DATA",
+    "SETUP", "SKETCH",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LintIssue {
+    pub path: String,
+    /// 1-based line in the file
+    pub line: usize,
+    /// "yaml", "unclosed-frontmatter", "unknown-type", "schema", "duplicate-id", "missing-relation-target"
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LintReport {
+    pub notes_checked: usize,
+    pub errors: Vec<LintIssue>,
+    pub warnings: Vec<LintIssue>,
+}
+
+impl LintReport {
+    fn push(&mut self, severity: Severity, issue: LintIssue) {
+        match severity {
+            Severity::Error => self.errors.push(issue),
+            Severity::Warning => self.warnings.push(issue),
+        }
+    }
+}
+
+/// What the cross-note checks need from one note
+#[derive(Debug, Default)]
+struct NoteFacts {
+    id: Option<(String, usize)>,
+    /// (target, line)
+    relation_targets: Vec<(String, usize)>,
+}
+
+/// Frontmatter text after the opening `---` (line 1 of the file), Err if it is never closed
+fn frontmatter_of(content: &str) -> Option<Result<&str, ()>> {
+    if !content.starts_with("---") {
+        return None;
+    }
+    Some(content[3..].find("\n---").map(|end| &content[3..3 + end]).ok_or(()))
+}
+
+/// File line of top-level `key:` in the frontmatter (the opening `---` is line 1)
+fn key_line(yaml: &str, key: &str) -> Option<usize> {
+    yaml.lines()
+        .position(|line| line.strip_prefix(key).is_some_and(|rest| rest.trim_start().starts_with(':')))
+        .map(|idx| idx + 1)
+}
+
+/// Line of the top-level key a schema error path ("/state/workflow") points into
+fn instance_path_line(yaml: &str, instance_path: &str) -> usize {
+    instance_path
+        .trim_start_matches('/')
+        .split('/')
+        .next()
+        .filter(|key| !key.is_empty())
+        .and_then(|key| key_line(yaml, key))
+        .unwrap_or(1)
+}
+
+fn lint_note(path: &Path, schemas: &VaultSchemas) -> (Vec<(Severity, LintIssue)>, NoteFacts) {
+    let path_str = path.to_string_lossy().to_string();
+    let issue = |line: usize, code: &str, message: String| LintIssue {
+        path: path_str.clone(),
+        line,
+        code: code.to_string(),
+        message,
+    };
+    let mut issues = Vec::new();
+    let mut facts = NoteFacts::default();
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return (vec![(Severity::Error, issue(1, "read", e.to_string()))], facts),
+    };
+    let yaml = match frontmatter_of(&content) {
+        None => return (issues, facts),
+        Some(Err(())) => {
+            issues.push((Severity::Error, issue(1, "unclosed-frontmatter", "Frontmatter has no closing ---".to_string())));
+            return (issues, facts);
+        }
+        Some(Ok(yaml)) => yaml,
+    };
+
+    let map = match try_parse_frontmatter(yaml) {
+        Ok(map) => map,
+        Err(e) => {
+            let line = e.location().map(|l| l.line()).unwrap_or(1);
+            issues.push((Severity::Error, issue(line, "yaml", format!("Invalid YAML: {}", e))));
+            return (issues, facts);
+        }
+    };
+
+    let id = match map.get("id") {
+        Some(serde_yaml::Value::String(s)) => Some(s.clone()),
+        Some(serde_yaml::Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    };
+    facts.id = id.map(|id| (id, key_line(yaml, "id").unwrap_or(1)));
+    let relations_line = key_line(yaml, "relations").unwrap_or(1);
+    facts.relation_targets = extract_relations(&map)
+        .into_iter()
+        .map(|relation| {
+            let line = yaml
+                .lines()
+                .enumerate()
+                .skip(relations_line)
+                .find(|(_, l)| l.contains(relation.target.as_str()))
+                .map(|(idx, _)| idx + 1)
+                .unwrap_or(relations_line);
+            (relation.target, line)
+        })
+        .collect();
+
+    if let Some(note_type) = map.get("type").and_then(|t| t.as_str()) {
+        if !NOTE_TYPES.contains(&note_type.to_uppercase().as_str()) {
+            let line = key_line(yaml, "type").unwrap_or(1);
+            issues.push((Severity::Error, issue(line, "unknown-type", format!("Unknown note type: {}", note_type))));
+            return (issues, facts);
+        }
+    }
+
+    match serde_yaml::from_str::<Frontmatter>(yaml) {
+        Ok(fm) => match FrontmatterParser::validate_with(&fm, schemas) {
+            Ok(errors) => issues.extend(errors.into_iter().map(|e| {
+                let line = instance_path_line(yaml, &e.path);
+                (Severity::Error, issue(line, "schema", e.message))
+            })),
+            Err(e) => issues.push((Severity::Error, issue(1, "schema", e))),
+        },
+        Err(e) => {
+            let line = e.location().map(|l| l.line()).unwrap_or(1);
+            issues.push((Severity::Error, issue(line, "schema", e.to_string())));
+        }
+    }
+    (issues, facts)
+}
+
+/// Lint `files` (notes of one vault) against the vault's schemas
+pub fn lint_notes(files: &[PathBuf], schemas: &VaultSchemas) -> LintReport {
+    let results: Vec<_> = files.par_iter().map(|path| (path, lint_note(path, schemas))).collect();
+
+    let mut report = LintReport { notes_checked: files.len(), ..Default::default() };
+    let mut ids: HashMap<String, Vec<(String, usize)>> = HashMap::new();
+    let note_names: HashSet<String> = files
+        .iter()
+        .filter_map(|p| p.file_stem())
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .collect();
+
+    for (path, (issues, facts)) in results {
+        let path_str = path.to_string_lossy().to_string();
+        for (severity, issue) in issues {
+            report.push(severity, issue);
+        }
+        if let Some((id, line)) = facts.id {
+            ids.entry(id).or_default().push((path_str.clone(), line));
+        }
+        for (target, line) in facts.relation_targets {
+            let name = target.rsplit(['/', '\\']).next().unwrap_or_default().to_lowercase();
+            if !note_names.contains(&name) {
+                report.push(
+                    Severity::Warning,
+                    LintIssue {
+                        path: path_str.clone(),
+                        line,
+                        code: "missing-relation-target".to_string(),
+                        message: format!("Relation target does not exist: {}", target),
+                    },
+                );
+            }
+        }
+    }
+
+    for (id, notes) in ids.into_iter().filter(|(_, notes)| notes.len() > 1) {
+        for (path, line) in &notes {
+            let others: Vec<&str> = notes.iter().filter(|(p, _)| p != path).map(|(p, _)| p.as_str()).collect();
+            report.push(
+                Severity::Error,
+                LintIssue {
+                    path: path.clone(),
+                    line: *line,
+                    code: "duplicate-id".to_string(),
+                    message: format!("Duplicate id {} (also in {})", id, others.join(", ")),
+                },
+            );
+        }
+    }
+
+    for list in [&mut report.errors, &mut report.warnings] {
+        list.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_and_schema_error_lines() {
+        let yaml = "\nid: \"1\"\ntitle: t\nstate:\n  workflow: bad\n";
+        assert_eq!(key_line(yaml, "title"), Some(3));
+        assert_eq!(key_line(yaml, "tit"), None);
+        assert_eq!(instance_path_line(yaml, "/state/workflow"), 4);
+        assert_eq!(instance_path_line(yaml, ""), 1);
+    }
+}
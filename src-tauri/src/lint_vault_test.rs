@@ -0,0 +1,72 @@
+// Vault lint - YAML 오류, 알 수 없는 타입, 스키마 위반, 중복 id, 없는 관계 대상을 줄 번호와 함께 보고하는지 검증
+
+#[cfg(test)]
+mod lint_vault_tests {
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn note(id: &str, extra: &str) -> String {
+        format!(
+            "---\nid: \"{}\"\ntitle: t\ntype: NOTE\ncreated: \"2026-01-01\"\nmodified: \"2026-01-01\"\n{}---\n\n본문\n",
+            id, extra
+        )
+    }
+
+    fn setup_vault(vault: &Path) {
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        fs::write(vault.join("정상.md"), note("20260101000001", "relations:\n  - relation_type: supports\n    target: \"[[중복1]]\"\n")).unwrap();
+        fs::write(vault.join("깨진.md"), "---\ntitle: t\ntags: [a, b\n---\n본문\n").unwrap();
+        fs::write(vault.join("타입.md"), note("20260101000002", "").replace("type: NOTE", "type: MEMO")).unwrap();
+        fs::write(vault.join("상태.md"), note("20260101000003", "state:\n  workflow: done\n  confidence: verified\n  maturity: 1\n")).unwrap();
+        fs::write(vault.join("중복1.md"), note("20260101000009", "")).unwrap();
+        fs::write(vault.join("중복2.md"), note("20260101000009", "relations:\n  - relation_type: extends\n    target: 없는노트\n")).unwrap();
+        fs::write(vault.join("일반.md"), "frontmatter 없는 노트\n").unwrap();
+    }
+
+    fn lint(vault: &Path) -> crate::lint::LintReport {
+        let schemas = crate::frontmatter::schemas::VaultSchemas::load(vault).unwrap();
+        crate::lint::lint_notes(&crate::collect_md_files(vault), &schemas)
+    }
+
+    fn issue<'a>(issues: &'a [crate::lint::LintIssue], file: &str, code: &str) -> Option<&'a crate::lint::LintIssue> {
+        issues.iter().find(|i| i.path.ends_with(file) && i.code == code)
+    }
+
+    #[test]
+    fn test_lint_vault_reports_each_problem_with_line() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+
+        let report = lint(vault);
+        assert_eq!(report.notes_checked, 7);
+
+        let yaml = issue(&report.errors, "깨진.md", "yaml").expect("YAML 오류 보고");
+        assert!(yaml.line >= 3, "오류 위치: {}", yaml.line);
+        assert_eq!(issue(&report.errors, "타입.md", "unknown-type").unwrap().line, 4);
+        assert_eq!(issue(&report.errors, "상태.md", "schema").unwrap().line, 8, "workflow 값 줄");
+        assert_eq!(issue(&report.errors, "중복1.md", "duplicate-id").unwrap().line, 2);
+        assert!(issue(&report.errors, "중복2.md", "duplicate-id").unwrap().message.contains("중복1.md"));
+
+        assert_eq!(report.warnings.len(), 1, "존재하는 관계 대상은 경고 없음");
+        let missing = issue(&report.warnings, "중복2.md", "missing-relation-target").unwrap();
+        assert_eq!(missing.line, 9);
+        assert!(report.errors.iter().all(|i| !i.path.ends_with("정상.md") && !i.path.ends_with("일반.md")));
+    }
+
+    #[test]
+    fn test_lint_uses_vault_schemas() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join(".notology").join("schemas")).unwrap();
+        fs::write(vault.join(".notology").join("schemas").join("team.json"), r#"{"required": ["project_code"]}"#).unwrap();
+        fs::write(vault.join("a.md"), note("20260101000001", "")).unwrap();
+        fs::write(vault.join("b.md"), note("20260101000002", "project_code: P-1\n")).unwrap();
+
+        let report = lint(vault);
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].path.ends_with("a.md"));
+        assert!(report.errors[0].message.contains("project_code"));
+    }
+}
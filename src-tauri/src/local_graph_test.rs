@@ -0,0 +1,156 @@
+// Local neighborhood graph - N-hop 이웃, hop 거리, 노드 예산 검증
+
+#[cfg(test)]
+mod local_graph_tests {
+    use crate::search::{LocalGraphData, LocalGraphFilter, SearchIndex};
+    use crate::test_common::{open_index, IndexDirGuard};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn write_note(path: &Path, title: &str, tags: &[&str], body: &str) {
+        let tag_yaml = if tags.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "tags:\n  domain:\n{}",
+                tags.iter().map(|t| format!("    - {}\n", t)).collect::<String>()
+            )
+        };
+        fs::write(path, format!("---\ntitle: {}\ntype: NOTE\n{}---\n\n{}\n", title, tag_yaml, body)).unwrap();
+    }
+
+    /// 프로젝트/프로젝트.md (폴더 노트)
+    ///   ├─ A.md  -> [[B]], tag domain/AI
+    ///   └─ B.md  -> [[C]]
+    /// C.md       -> [[D]]
+    /// D.md
+    /// E.md       tag domain/AI
+    fn setup_vault() -> (TempDir, SearchIndex, IndexDirGuard) {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let project = vault.join("프로젝트");
+        fs::create_dir_all(&project).unwrap();
+
+        write_note(&project.join("프로젝트.md"), "프로젝트", &[], "폴더 노트");
+        write_note(&project.join("A.md"), "A", &["AI"], "[[B]]");
+        write_note(&project.join("B.md"), "B", &[], "[[C]]");
+        write_note(&vault.join("C.md"), "C", &[], "[[D]]");
+        write_note(&vault.join("D.md"), "D", &[], "끝");
+        write_note(&vault.join("E.md"), "E", &["AI"], "태그만 공유");
+
+        let (index, guard) = open_index(vault);
+        (temp_dir, index, guard)
+    }
+
+    fn hop_of(graph: &LocalGraphData, id: &str) -> Option<usize> {
+        graph.nodes.iter().find(|n| n.node.id == id).map(|n| n.hop)
+    }
+
+    fn path_of(temp_dir: &TempDir, rel: &str) -> String {
+        temp_dir.path().join(rel).to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_one_hop_includes_links_folder_and_tags() {
+        let (temp_dir, index, _guard) = setup_vault();
+        let b = path_of(&temp_dir, "프로젝트/B.md");
+
+        let graph = index.get_local_graph(&b, 1, &LocalGraphFilter::default()).unwrap();
+
+        assert_eq!(hop_of(&graph, &b), Some(0), "중심 노트는 hop 0");
+        assert_eq!(hop_of(&graph, &path_of(&temp_dir, "C.md")), Some(1), "나가는 링크");
+        assert_eq!(hop_of(&graph, &path_of(&temp_dir, "프로젝트/A.md")), Some(1), "들어오는 링크");
+        assert_eq!(
+            hop_of(&graph, &path_of(&temp_dir, "프로젝트/프로젝트.md")),
+            Some(1),
+            "폴더 노트 부모"
+        );
+        assert_eq!(hop_of(&graph, &path_of(&temp_dir, "D.md")), None, "2-hop 노드는 포함되지 않아야 함");
+        assert!(!graph.truncated);
+    }
+
+    #[test]
+    fn test_hop_distance_increases_with_depth() {
+        let (temp_dir, index, _guard) = setup_vault();
+        let a = path_of(&temp_dir, "프로젝트/A.md");
+        let filter = LocalGraphFilter {
+            include_tags: Some(false),
+            include_folders: Some(false),
+            ..Default::default()
+        };
+
+        let graph = index.get_local_graph(&a, 3, &filter).unwrap();
+
+        assert_eq!(hop_of(&graph, &path_of(&temp_dir, "프로젝트/B.md")), Some(1));
+        assert_eq!(hop_of(&graph, &path_of(&temp_dir, "C.md")), Some(2));
+        assert_eq!(hop_of(&graph, &path_of(&temp_dir, "D.md")), Some(3));
+        assert_eq!(hop_of(&graph, &path_of(&temp_dir, "E.md")), None, "태그 비활성화 시 E는 제외");
+    }
+
+    #[test]
+    fn test_tag_co_membership() {
+        let (temp_dir, index, _guard) = setup_vault();
+        let e = path_of(&temp_dir, "E.md");
+        let filter = LocalGraphFilter {
+            include_links: Some(false),
+            include_folders: Some(false),
+            ..Default::default()
+        };
+
+        let graph = index.get_local_graph(&e, 2, &filter).unwrap();
+
+        assert_eq!(hop_of(&graph, "tag:domain/AI"), Some(1), "태그 노드는 1-hop");
+        assert_eq!(hop_of(&graph, &path_of(&temp_dir, "프로젝트/A.md")), Some(2), "같은 태그 노트는 2-hop");
+        assert!(graph
+            .edges
+            .iter()
+            .any(|edge| edge.edge_type == "tag" && edge.target == "tag:domain/AI" && edge.source == e));
+    }
+
+    #[test]
+    fn test_folder_note_children() {
+        let (temp_dir, index, _guard) = setup_vault();
+        let folder_note = path_of(&temp_dir, "프로젝트/프로젝트.md");
+        let filter = LocalGraphFilter {
+            include_links: Some(false),
+            include_tags: Some(false),
+            ..Default::default()
+        };
+
+        let graph = index.get_local_graph(&folder_note, 1, &filter).unwrap();
+
+        assert_eq!(graph.nodes.len(), 3, "폴더 노트 + 자식 2개");
+        assert!(graph
+            .edges
+            .iter()
+            .all(|edge| edge.edge_type == "contains" && edge.source == folder_note));
+    }
+
+    #[test]
+    fn test_node_budget_truncates() {
+        let (temp_dir, index, _guard) = setup_vault();
+        let b = path_of(&temp_dir, "프로젝트/B.md");
+        let filter = LocalGraphFilter {
+            max_nodes: Some(2),
+            ..Default::default()
+        };
+
+        let graph = index.get_local_graph(&b, 3, &filter).unwrap();
+
+        assert_eq!(graph.nodes.len(), 2, "노드 예산을 초과하면 안 됨");
+        assert!(graph.truncated);
+        let ids: std::collections::HashSet<&str> = graph.nodes.iter().map(|n| n.node.id.as_str()).collect();
+        assert!(
+            graph.edges.iter().all(|e| ids.contains(e.source.as_str()) && ids.contains(e.target.as_str())),
+            "잘린 노드를 가리키는 엣지가 없어야 함"
+        );
+    }
+
+    #[test]
+    fn test_unknown_note_is_error() {
+        let (temp_dir, index, _guard) = setup_vault();
+        let missing = path_of(&temp_dir, "없는노트.md");
+        assert!(index.get_local_graph(&missing, 1, &LocalGraphFilter::default()).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+// Unlinked mentions - SearchIndex 후보 검색 + 링크 변환 후 역링크 반영 검증
+
+#[cfg(test)]
+mod mentions_tests {
+    use crate::search::mentions::{apply_mention_links, MentionSelection};
+    use crate::test_common::open_index;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_finds_title_and_alias_mentions_excluding_linked_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let target = vault.join("가설검증.md");
+        fs::write(&target, "---\ntype: NOTE\ntitle: 가설검증\naliases: [Hypothesis Test]\n---\n\n본문\n").unwrap();
+        fs::write(vault.join("언급.md"), "---\ntype: NOTE\n---\n\n오늘 가설검증을 했다.\n`가설검증` 코드\nThe hypothesis test failed.\n").unwrap();
+        fs::write(vault.join("링크됨.md"), "---\ntype: NOTE\n---\n\n[[가설검증]] 그리고 가설검증\n").unwrap();
+
+        let (index, _guard) = open_index(vault);
+        let mentions = index.find_unlinked_mentions(&target.to_string_lossy()).unwrap();
+
+        assert_eq!(mentions.len(), 2, "이미 링크한 노트와 코드 영역은 제외: {:?}", mentions);
+        assert!(mentions.iter().all(|m| m.source_path.ends_with("언급.md")));
+        assert_eq!(mentions[0].matched_text, "가설검증");
+        assert_eq!(mentions[0].line, 5);
+        assert_eq!(mentions[1].matched_text, "hypothesis test");
+    }
+
+    #[test]
+    fn test_linked_mentions_become_backlinks() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let target = vault.join("회의록.md");
+        fs::write(&target, "---\ntype: NOTE\n---\n\n회의\n").unwrap();
+        let source = vault.join("일지.md");
+        fs::write(&source, "---\ntype: NOTE\n---\n\n회의록 정리\n").unwrap();
+
+        let (index, _guard) = open_index(vault);
+        let target_str = target.to_string_lossy().to_string();
+        let mentions = index.find_unlinked_mentions(&target_str).unwrap();
+        assert_eq!(mentions.len(), 1);
+
+        let selections: Vec<MentionSelection> = mentions
+            .iter()
+            .map(|m| MentionSelection {
+                source_path: m.source_path.clone(),
+                start: m.start,
+                end: m.end,
+                matched_text: m.matched_text.clone(),
+            })
+            .collect();
+        let content = fs::read_to_string(&source).unwrap();
+        let (updated, applied) = apply_mention_links(&content, &selections, "회의록");
+        assert_eq!(applied, 1);
+        fs::write(&source, &updated).unwrap();
+        index.index_file(&source).unwrap();
+
+        let rel = index.get_relationships(&target_str).unwrap();
+        assert_eq!(rel.incoming_links.len(), 1, "span 링크도 역링크로 인식되어야 함");
+        assert!(index.find_unlinked_mentions(&target_str).unwrap().is_empty());
+    }
+}
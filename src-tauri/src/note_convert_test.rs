@@ -0,0 +1,48 @@
+// Note type conversion - 필드 매핑, extra 보존, 본문 유지, 스키마 검증 실패 시 무변경 검증
+
+#[cfg(test)]
+mod note_convert_tests {
+    use crate::frontmatter::FrontmatterParser;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const TASK: &str = "---\nid: \"20260101090000\"\ntitle: 보고서 작성\ntype: TASK\ncreated: \"2026-01-01T09:00:00+09:00\"\nmodified: \"2026-01-01T09:00:00+09:00\"\ndue: \"2026-03-01\"\nassignee: 김\npriority: high\n---\n\n할 일 본문\n";
+
+    #[test]
+    fn test_convert_task_to_event() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        let note = vault.join("보고서 작성.md");
+        fs::write(&note, TASK).unwrap();
+
+        let conversion = crate::convert_note_file(&note, "event").unwrap();
+        assert_eq!(conversion.from, "TASK");
+        assert_eq!(conversion.to, "EVENT");
+        assert_eq!(conversion.moves.len(), 3);
+
+        let content = fs::read_to_string(&note).unwrap();
+        assert!(content.ends_with("할 일 본문\n"));
+        assert!(!content.contains("due:") && !content.contains("assignee:"), "이전 타입 필드가 남지 않음");
+        let (fm, _) = FrontmatterParser::parse(&content).unwrap();
+        let fm = fm.unwrap();
+        assert_eq!(fm.date.as_deref(), Some("2026-03-01"));
+        assert_eq!(fm.tags.who, vec!["김"]);
+        assert!(FrontmatterParser::validate(&fm).unwrap().is_empty());
+        assert_eq!(fs::read_dir(vault.join(".notology").join("backups")).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_invalid_conversion_leaves_note_untouched() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        let note = vault.join("메모.md");
+        let content = TASK.replace("type: TASK", "type: NOTE").replace("priority: high", "priority: p1");
+        fs::write(&note, &content).unwrap();
+
+        assert!(crate::convert_note_file(&note, "TASK").is_err());
+        assert!(crate::convert_note_file(&note, "UNKNOWN").is_err());
+        assert_eq!(fs::read_to_string(&note).unwrap(), content);
+    }
+}
@@ -0,0 +1,110 @@
+// Note ids - 중복 id 탐지/수정, 같은 초 생성 충돌 방지, [[id:...]] 링크가 이름 변경 후에도 유지되는지 검증
+
+#[cfg(test)]
+mod note_id_tests {
+    use crate::frontmatter::ids::is_note_id;
+    use crate::test_common::open_index;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn note(id: &str, created: &str) -> String {
+        format!("---\nid: \"{}\"\ntitle: t\ntype: NOTE\ncreated: {}\nmodified: {}\n---\n\n본문\n", id, created, created)
+    }
+
+    #[test]
+    fn test_duplicate_ids_detected_and_fixed() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        fs::write(vault.join("c.md"), note("20250101120000", "2025-01-01T12:00:09+09:00")).unwrap();
+        fs::write(vault.join("a.md"), note("20250101120000", "2025-01-01T12:00:00+09:00")).unwrap();
+        fs::write(vault.join("b.md"), note("20250101120000", "2025-01-01T12:00:05+09:00")).unwrap();
+        fs::write(vault.join("d.md"), note("20250101120001", "2025-01-01T12:00:01+09:00")).unwrap();
+
+        let files = crate::collect_md_files(vault);
+        let duplicates = crate::find_duplicate_ids(&files);
+        assert_eq!(duplicates.len(), 1);
+        assert!(duplicates[0].paths[0].ends_with("a.md"), "가장 오래된 노트가 먼저");
+
+        let (preview, written) = crate::fix_duplicate_ids_in_vault(vault, true);
+        assert!(written.is_empty());
+        assert_eq!(preview.reassigned.len(), 2);
+        assert!(fs::read_to_string(vault.join("b.md")).unwrap().contains("20250101120000"), "dry-run은 파일 유지");
+
+        let (fix, written) = crate::fix_duplicate_ids_in_vault(vault, false);
+        assert_eq!(written.len(), 2);
+        assert!(fs::read_to_string(vault.join("a.md")).unwrap().contains("id: \"20250101120000\""));
+        for reassigned in &fix.reassigned {
+            assert!(is_note_id(&reassigned.new_id));
+            assert_ne!(reassigned.new_id, "20250101120001", "기존 id와 겹치지 않음");
+            assert!(fs::read_to_string(&reassigned.path).unwrap().contains(&format!("id: \"{}\"", reassigned.new_id)));
+        }
+        assert!(crate::find_duplicate_ids(&crate::collect_md_files(vault)).is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_previews_assigned_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        for (name, created) in [("a.md", "2025-01-01T12:00:00+09:00"), ("b.md", "2025-01-01T12:00:05+09:00"), ("c.md", "2025-01-01T12:00:09+09:00")] {
+            fs::write(vault.join(name), note("20250101120000", created)).unwrap();
+        }
+
+        let (preview, _) = crate::fix_duplicate_ids_in_vault(vault, true);
+        let (fix, _) = crate::fix_duplicate_ids_in_vault(vault, false);
+        let ids = |fix: &crate::DuplicateIdFix| fix.reassigned.iter().map(|r| r.new_id.clone()).collect::<Vec<_>>();
+        assert_eq!(preview.reassigned.len(), 2);
+        assert_ne!(preview.reassigned[0].new_id, preview.reassigned[1].new_id, "미리보기 안에서도 id가 겹치지 않음");
+        assert_eq!(ids(&preview), ids(&fix), "dry-run이 보여준 id가 실제로 기록됨");
+    }
+
+    #[test]
+    fn test_parse_does_not_allocate_id() {
+        let yaml = "title: t\ntype: NOTE\ncreated: 2025-01-01T12:00:00+09:00\nmodified: 2025-01-01T12:00:00+09:00\n";
+        let fm = crate::frontmatter::FrontmatterParser::parse_yaml(yaml).unwrap();
+        assert!(fm.id.is_empty(), "파싱만으로 id를 발급하지 않음");
+        let yaml = crate::frontmatter::FrontmatterParser::to_yaml(&fm).unwrap();
+        assert!(!yaml.contains("id:"), "빈 id는 쓰지 않음");
+    }
+
+    #[test]
+    fn test_template_id_is_made_unique() {
+        let taken = |id: &str| id == "20250101120000";
+        let yaml = "id: \"20250101120000\"  # 템플릿\ntitle: 새 노트";
+        let unique = crate::with_unique_note_id(yaml, taken);
+        assert!(!unique.contains("20250101120000"));
+        assert!(unique.ends_with("  # 템플릿\ntitle: 새 노트"), "{}", unique);
+
+        let first = crate::with_unique_note_id("id: \"20300101000000\"\ntitle: a", |_| false);
+        let second = crate::with_unique_note_id("id: \"20300101000000\"\ntitle: b", |_| false);
+        assert_eq!(first, "id: \"20300101000000\"\ntitle: a");
+        assert_ne!(second, "id: \"20300101000000\"\ntitle: b", "같은 초에 만든 두 번째 노트는 다른 id");
+    }
+
+    #[test]
+    fn test_id_links_survive_rename() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        fs::create_dir_all(vault.join("보관")).unwrap();
+        let target = vault.join("회의.md");
+        let source = vault.join("일지.md");
+        fs::write(&target, note("20250101120000", "2025-01-01T12:00:00+09:00")).unwrap();
+        fs::write(&source, "---\nid: \"20250102090000\"\ntitle: 일지\n---\n\n[[id:20250101120000|회의]] 참고\n").unwrap();
+
+        let (index, _guard) = open_index(vault);
+        assert_eq!(index.resolve_note("id:20250101120000"), Some(target.to_string_lossy().to_string()));
+
+        let moved = vault.join("보관").join("정기 회의.md");
+        fs::rename(&target, &moved).unwrap();
+        index.remove_file(&target).unwrap();
+        index.index_file(&moved).unwrap();
+
+        assert_eq!(index.resolve_note("id:20250101120000"), Some(moved.to_string_lossy().to_string()));
+        assert!(index.get_broken_links(None).unwrap().is_empty());
+        let relations = index.get_relationships(&moved.to_string_lossy()).unwrap();
+        assert_eq!(relations.incoming_links.len(), 1);
+        assert_eq!(index.note_ids().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,251 @@
+//! Merging one note into another
+//!
+//! Pure helpers for `merge_notes`: frontmatter union, body placement, comment merging and
+//! attachment destination planning. The command in lib.rs turns these into a journaled
+//! transaction (target rewrite, link redirects, attachment moves, source moved to backups).
+
+use crate::frontmatter::types::{FacetedTags, Frontmatter, Relation};
+use crate::search::parser::normalize_relation_target;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Append values from `source` that `target` does not contain yet (order preserved)
+fn union_into(target: &mut Vec<String>, source: &[String]) {
+    for value in source {
+        if !target.contains(value) {
+            target.push(value.clone());
+        }
+    }
+}
+
+fn union_option(target: &mut Option<Vec<String>>, source: &Option<Vec<String>>) {
+    if let Some(source) = source {
+        union_into(target.get_or_insert_with(Vec::new), source);
+    }
+}
+
+fn fill_option<T: Clone>(target: &mut Option<T>, source: &Option<T>) {
+    if target.is_none() {
+        target.clone_from(source);
+    }
+}
+
+fn merge_tags(target: &mut FacetedTags, source: &FacetedTags) {
+    union_into(&mut target.domain, &source.domain);
+    union_into(&mut target.who, &source.who);
+    union_into(&mut target.org, &source.org);
+    union_into(&mut target.ctx, &source.ctx);
+    union_into(&mut target.source, &source.source);
+    union_into(&mut target.method, &source.method);
+    union_into(&mut target.status, &source.status);
+    union_into(&mut target.legacy, &source.legacy);
+}
+
+fn relation_key(relation: &Relation) -> (String, String) {
+    (
+        format!("{:?}", relation.relation_type),
+        normalize_relation_target(&relation.target).to_lowercase(),
+    )
+}
+
+/// Union `source` into `target`. Target values win for scalar fields; lists are unioned.
+/// Relations between the two notes would become self-relations and are dropped.
+pub fn merge_frontmatter(target: &mut Frontmatter, source: &Frontmatter, source_stem: &str, target_stem: &str) {
+    merge_tags(&mut target.tags, &source.tags);
+    union_into(&mut target.cssclasses, &source.cssclasses);
+
+    let merged_stems = [source_stem.to_lowercase(), target_stem.to_lowercase()];
+    let mut seen: HashSet<(String, String)> = HashSet::new();
+    let relations = std::mem::take(&mut target.relations);
+    for relation in relations.into_iter().chain(source.relations.iter().cloned()) {
+        let key = relation_key(&relation);
+        if merged_stems.contains(&key.1) || !seen.insert(key) {
+            continue;
+        }
+        target.relations.push(relation);
+    }
+
+    union_option(&mut target.participants, &source.participants);
+    union_option(&mut target.authors, &source.authors);
+    fill_option(&mut target.date, &source.date);
+    fill_option(&mut target.venue, &source.venue);
+    fill_option(&mut target.year, &source.year);
+    fill_option(&mut target.doi, &source.doi);
+    fill_option(&mut target.url, &source.url);
+    fill_option(&mut target.due, &source.due);
+    fill_option(&mut target.priority, &source.priority);
+    fill_option(&mut target.assignee, &source.assignee);
+    fill_option(&mut target.email, &source.email);
+    fill_option(&mut target.phone, &source.phone);
+    fill_option(&mut target.organization, &source.organization);
+    fill_option(&mut target.role, &source.role);
+
+    for (key, value) in &source.extra {
+        target.extra.entry(key.clone()).or_insert_with(|| value.clone());
+    }
+}
+
+/// ATX heading level and text of a line ("## Notes" -> (2, "Notes"))
+fn heading_of(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_end();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((level, rest.trim()))
+}
+
+/// Place `source_body` into `target_body`: appended at the end, or at the end of the
+/// section under `heading` (created as a `##` section when the target has no such heading)
+pub fn insert_body(target_body: &str, source_body: &str, heading: Option<&str>) -> String {
+    let source_body = source_body.trim_matches('\n');
+    let target_trimmed = target_body.trim_end();
+    let separator = if target_trimmed.is_empty() { "" } else { "\n\n" };
+
+    let Some(heading) = heading.map(str::trim).filter(|h| !h.is_empty()) else {
+        return format!("{}{}{}\n", target_trimmed, separator, source_body);
+    };
+
+    let lines: Vec<&str> = target_body.lines().collect();
+    let mut in_fence = false;
+    let mut section: Option<(usize, usize)> = None; // (heading line, level)
+    let mut section_end = lines.len();
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let Some((level, text)) = heading_of(line) else { continue };
+        match section {
+            None if text == heading => section = Some((i, level)),
+            Some((_, open_level)) if level <= open_level => {
+                section_end = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    if section.is_none() {
+        return format!("{}{}## {}\n\n{}\n", target_trimmed, separator, heading, source_body);
+    }
+
+    let before = lines[..section_end].join("\n");
+    let mut merged = format!("{}\n\n{}\n", before.trim_end(), source_body);
+    if section_end < lines.len() {
+        merged.push('\n');
+        merged.push_str(&lines[section_end..].join("\n"));
+        if target_body.ends_with('\n') {
+            merged.push('\n');
+        }
+    }
+    merged
+}
+
+/// Append source comments whose `id` is not already present in the target
+pub fn merge_comments(target_json: &str, source_json: &str) -> Result<String, String> {
+    let mut target: Vec<serde_json::Value> =
+        serde_json::from_str(target_json).map_err(|e| format!("Invalid target comments.json: {}", e))?;
+    let source: Vec<serde_json::Value> =
+        serde_json::from_str(source_json).map_err(|e| format!("Invalid source comments.json: {}", e))?;
+
+    let ids: HashSet<String> = target
+        .iter()
+        .filter_map(|c| c.get("id").and_then(|v| v.as_str()).map(str::to_string))
+        .collect();
+    for comment in source {
+        let duplicate = comment.get("id").and_then(|v| v.as_str()).is_some_and(|id| ids.contains(id));
+        if !duplicate {
+            target.push(comment);
+        }
+    }
+    serde_json::to_string_pretty(&target).map_err(|e| e.to_string())
+}
+
+/// Destinations for the entries of `source_att` inside `target_att`.
+/// comments.json is excluded (merged separately); name collisions get a `_N` suffix.
+pub fn plan_attachment_moves(source_att: &Path, target_att: &Path) -> Vec<(PathBuf, PathBuf)> {
+    let Ok(entries) = fs::read_dir(source_att) else {
+        return Vec::new();
+    };
+    let mut sources: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.file_name().is_some_and(|n| n != "comments.json"))
+        .collect();
+    sources.sort();
+
+    let mut reserved: HashSet<PathBuf> = HashSet::new();
+    let mut moves = Vec::with_capacity(sources.len());
+    for from in sources {
+        let name = from.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let mut to = target_att.join(&name);
+        if to.exists() || reserved.contains(&to) {
+            let stem = Path::new(&name).file_stem().unwrap_or_default().to_string_lossy().to_string();
+            let ext = Path::new(&name).extension().map(|e| e.to_string_lossy().to_string());
+            let mut counter = 1;
+            loop {
+                let candidate = match &ext {
+                    Some(e) if from.is_file() => format!("{}_{}.{}", stem, counter, e),
+                    _ => format!("{}_{}", name, counter),
+                };
+                to = target_att.join(candidate);
+                if !to.exists() && !reserved.contains(&to) {
+                    break;
+                }
+                counter += 1;
+            }
+        }
+        reserved.insert(to.clone());
+        moves.push((from, to));
+    }
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_body_append_and_existing_heading() {
+        assert_eq!(insert_body("대상 본문\n", "\n원본 본문\n", None), "대상 본문\n\n원본 본문\n");
+
+        let target = "# 회의\n\n## 결정\n- A\n\n## 후속\n- B\n";
+        assert_eq!(
+            insert_body(target, "- C", Some("결정")),
+            "# 회의\n\n## 결정\n- A\n\n- C\n\n## 후속\n- B\n",
+            "같은 수준의 다음 제목 앞에 삽입"
+        );
+        assert_eq!(
+            insert_body("본문", "원본", Some("병합됨")),
+            "본문\n\n## 병합됨\n\n원본\n",
+            "제목이 없으면 새 섹션"
+        );
+    }
+
+    #[test]
+    fn test_heading_inside_code_fence_is_ignored() {
+        let target = "```\n## 결정\n```\n";
+        assert!(insert_body(target, "X", Some("결정")).ends_with("```\n\n## 결정\n\nX\n"));
+    }
+
+    #[test]
+    fn test_merge_comments_skips_duplicate_ids() {
+        let merged = merge_comments(
+            r#"[{"id":"a","content":"1"}]"#,
+            r#"[{"id":"a","content":"1"},{"id":"b","content":"2"}]"#,
+        )
+        .unwrap();
+        let merged: Vec<serde_json::Value> = serde_json::from_str(&merged).unwrap();
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[1]["id"], "b");
+    }
+}
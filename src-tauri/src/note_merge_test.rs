@@ -0,0 +1,153 @@
+// Note merge - 본문·frontmatter 병합, 첨부/댓글 이동, 링크 리다이렉트, 원본 백업 검증
+
+#[cfg(test)]
+mod note_merge_tests {
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn s(p: &Path) -> String {
+        p.to_string_lossy().to_string()
+    }
+
+    fn note(title: &str, tags: &str, extra: &str, body: &str) -> String {
+        format!(
+            "---\nid: \"{0}\"\ntitle: \"{0}\"\ntype: MTG\ncreated: \"2026-01-01T09:00:00+09:00\"\nmodified: \"2026-01-01T09:00:00+09:00\"\ntags:\n  domain: [{1}]\n{2}---\n\n{3}",
+            title, tags, extra, body
+        )
+    }
+
+    /// vault/
+    ///   .notology/
+    ///   주간회의.md (+ 주간회의_att/안건.pdf, comments.json)
+    ///   주간회의 사본.md (+ 주간회의 사본_att/안건.pdf, 사진.png, comments.json)
+    ///   일지.md (사본을 링크)
+    fn setup_vault(vault: &Path) {
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        fs::create_dir_all(vault.join("주간회의_att")).unwrap();
+        fs::create_dir_all(vault.join("주간회의 사본_att")).unwrap();
+        fs::write(
+            vault.join("주간회의.md"),
+            note("주간회의", "회의, 기획", "participants: [김]\n", "## 결정\n- 일정 확정\n\n## 후속\n- 공유\n"),
+        )
+        .unwrap();
+        fs::write(
+            vault.join("주간회의 사본.md"),
+            note(
+                "주간회의 사본",
+                "기획, 예산",
+                "participants: [이, 김]\nrelations:\n  - relation_type: extends\n    target: \"[[주간회의]]\"\n",
+                "- 예산 승인 [[안건.pdf]]\n",
+            ),
+        )
+        .unwrap();
+        fs::write(vault.join("주간회의_att").join("안건.pdf"), b"target").unwrap();
+        fs::write(vault.join("주간회의 사본_att").join("안건.pdf"), b"source").unwrap();
+        fs::write(vault.join("주간회의 사본_att").join("사진.png"), b"png").unwrap();
+        fs::write(vault.join("주간회의_att").join("comments.json"), r#"[{"id":"c1","content":"대상"}]"#).unwrap();
+        fs::write(
+            vault.join("주간회의 사본_att").join("comments.json"),
+            r#"[{"id":"c2","content":"원본"}]"#,
+        )
+        .unwrap();
+        fs::write(vault.join("일지.md"), "[[주간회의 사본]] 참고\n").unwrap();
+    }
+
+    #[test]
+    fn test_merge_notes_under_heading() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+
+        let merged_path = crate::merge_notes(
+            s(&vault.join("주간회의 사본.md")),
+            s(&vault.join("주간회의.md")),
+            Some("결정".to_string()),
+            s(vault),
+        )
+        .unwrap();
+        assert_eq!(Path::new(&merged_path.final_path), vault.join("주간회의.md"));
+
+        let merged = fs::read_to_string(vault.join("주간회의.md")).unwrap();
+        let (fm, body) = crate::frontmatter::FrontmatterParser::parse(&merged).unwrap();
+        let fm = fm.unwrap();
+        assert_eq!(fm.title, "주간회의", "대상의 값이 우선");
+        assert_eq!(fm.tags.domain, vec!["회의", "기획", "예산"]);
+        assert_eq!(fm.participants.unwrap(), vec!["김", "이"]);
+        assert!(fm.relations.is_empty(), "병합 대상 간 관계는 자기 참조가 되므로 제거");
+        assert_eq!(body, "## 결정\n- 일정 확정\n\n- 예산 승인 [[안건_1.pdf]]\n\n## 후속\n- 공유\n");
+
+        // 첨부: 이름 충돌은 _1 접미사, 댓글은 합침
+        let att = vault.join("주간회의_att");
+        assert_eq!(fs::read(att.join("안건.pdf")).unwrap(), b"target");
+        assert_eq!(fs::read(att.join("안건_1.pdf")).unwrap(), b"source");
+        assert!(att.join("사진.png").exists());
+        let comments: Vec<serde_json::Value> =
+            serde_json::from_str(&fs::read_to_string(att.join("comments.json")).unwrap()).unwrap();
+        assert_eq!(comments.len(), 2);
+
+        // 링크 리다이렉트, 원본은 백업으로
+        assert_eq!(fs::read_to_string(vault.join("일지.md")).unwrap(), "[[주간회의]] 참고\n");
+        assert!(!vault.join("주간회의 사본.md").exists());
+        assert!(!vault.join("주간회의 사본_att").exists());
+        let backups: Vec<String> = fs::read_dir(vault.join(".notology").join("backups"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert!(backups.iter().any(|n| n.starts_with("주간회의 사본.md.") && n.ends_with(".bak")));
+    }
+
+    #[test]
+    fn test_merge_moves_whole_att_folder_and_appends() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+        fs::remove_dir_all(vault.join("주간회의_att")).unwrap();
+        fs::write(vault.join("색인.md"), "[[주간회의 사본_att/사진.png]]").unwrap();
+
+        let preview = crate::preview_merge_notes(
+            s(&vault.join("주간회의 사본.md")),
+            s(&vault.join("주간회의.md")),
+            None,
+            s(vault),
+        )
+        .unwrap();
+        assert!(preview.moves.iter().any(|m| m.is_dir));
+        assert!(vault.join("주간회의 사본.md").exists(), "미리보기는 아무것도 바꾸지 않음");
+
+        crate::merge_notes(s(&vault.join("주간회의 사본.md")), s(&vault.join("주간회의.md")), None, s(vault)).unwrap();
+
+        let merged = fs::read_to_string(vault.join("주간회의.md")).unwrap();
+        assert!(merged.ends_with("## 후속\n- 공유\n\n- 예산 승인 [[안건.pdf]]\n"));
+        assert!(vault.join("주간회의_att").join("comments.json").exists());
+        assert_eq!(fs::read_to_string(vault.join("색인.md")).unwrap(), "[[주간회의_att/사진.png]]");
+    }
+
+    #[test]
+    fn test_merge_keeps_legacy_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+        // 원본은 패싯 없이 목록형 태그만 가진 옛 형식
+        let source = note("주간회의 사본", "", "", "- 예산 승인\n")
+            .replace("tags:\n  domain: []\n", "tags: [옛태그, 기획]\n");
+        fs::write(vault.join("주간회의 사본.md"), source).unwrap();
+
+        crate::merge_notes(s(&vault.join("주간회의 사본.md")), s(&vault.join("주간회의.md")), None, s(vault)).unwrap();
+
+        let merged = fs::read_to_string(vault.join("주간회의.md")).unwrap();
+        let (fm, _) = crate::frontmatter::FrontmatterParser::parse(&merged).unwrap();
+        let fm = fm.unwrap();
+        assert_eq!(fm.tags.domain, vec!["회의", "기획"]);
+        assert_eq!(fm.tags.legacy, vec!["옛태그", "기획"], "원본의 미분류 태그도 유지");
+    }
+
+    #[test]
+    fn test_merge_into_itself_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+        let path = s(&vault.join("주간회의.md"));
+        assert!(crate::merge_notes(path.clone(), path, None, s(vault)).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+// Note metrics - 인덱싱 시 계산된 단어 수/링크/할 일 수로 노트 필터링과 정렬 검증
+
+#[cfg(test)]
+mod note_metrics_tests {
+    use crate::search::{MetricRange, NoteFilter};
+    use crate::test_common::open_index;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn note(title: &str, body: &str) -> String {
+        format!("---\ntitle: {}\ntype: NOTE\ncreated: 2025-01-01T00:00:00+09:00\nmodified: 2025-01-01T00:00:00+09:00\n---\n\n{}\n", title, body)
+    }
+
+    fn filter(sort_by: Option<&str>, metrics: Option<Vec<MetricRange>>) -> NoteFilter {
+        NoteFilter {
+            note_type: None,
+            tags: None,
+            created_after: None,
+            created_before: None,
+            modified_after: None,
+            modified_before: None,
+            sort_by: sort_by.map(|s| s.to_string()),
+            sort_order: Some("desc".to_string()),
+            metrics,
+        }
+    }
+
+    fn range(metric: &str, min: Option<u64>, max: Option<u64>) -> MetricRange {
+        MetricRange { metric: metric.to_string(), min, max }
+    }
+
+    #[test]
+    fn test_filter_and_sort_by_metrics() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let long_body = format!("# 개요\n\n{}\n\n## 할 일\n- [ ] 검토\n- [ ] 배포\n- [x] 작성\n\n[[짧은 메모]] [[허브]]", "단어 ".repeat(450));
+        fs::write(vault.join("긴 글.md"), note("긴 글", &long_body)).unwrap();
+        fs::write(vault.join("짧은 메모.md"), note("짧은 메모", "한 줄 메모 [[허브]] ![[도표.png]]")).unwrap();
+        fs::write(vault.join("허브.md"), note("허브", "東京 회의 기록")).unwrap();
+
+        let (index, _guard) = open_index(vault);
+
+        let all = index.query_notes(&filter(Some("word_count"), None)).unwrap();
+        let titles: Vec<&str> = all.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["긴 글", "짧은 메모", "허브"]);
+        let long = &all[0];
+        assert_eq!(long.metrics.heading_count, 2);
+        assert_eq!(long.metrics.open_task_count, 2);
+        assert_eq!(long.metrics.outgoing_link_count, 2);
+        assert_eq!(long.metrics.reading_time, 3);
+        let short = &all[1];
+        assert_eq!(short.metrics.attachment_count, 1);
+        assert_eq!(short.incoming_link_count, None, "정렬/필터에 쓰이지 않으면 계산하지 않음");
+        assert_eq!(all[2].metrics.word_count, 4, "한자는 글자 단위");
+
+        let stubs = index.query_notes(&filter(None, Some(vec![range("word_count", None, Some(50))]))).unwrap();
+        assert_eq!(stubs.len(), 2);
+        let with_tasks = index.query_notes(&filter(None, Some(vec![range("open_task_count", Some(1), None)]))).unwrap();
+        assert_eq!(with_tasks.len(), 1);
+        let hubs = index.query_notes(&filter(Some("incoming_link_count"), Some(vec![range("incoming_link_count", Some(1), None)]))).unwrap();
+        assert_eq!(hubs.iter().map(|m| m.title.as_str()).collect::<Vec<_>>(), vec!["허브", "짧은 메모"]);
+        assert_eq!(hubs.iter().map(|m| m.incoming_link_count).collect::<Vec<_>>(), vec![Some(2), Some(1)]);
+
+        assert!(index.query_notes(&filter(None, Some(vec![range("page_count", Some(1), None)]))).is_err(), "모르는 지표는 오류");
+    }
+}
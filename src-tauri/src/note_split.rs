@@ -0,0 +1,239 @@
+//! Splitting a note into one note per heading section
+//!
+//! Pure helpers for `split_note`: section parsing, note names, memo/attachment assignment
+//! and retargeting of `[[Note#Heading]]` links. The command in lib.rs stages the new notes
+//! and applies everything as one journaled transaction.
+
+use crate::search::parser::extract_wiki_links;
+use regex::{Captures, Regex};
+use std::collections::{HashMap, HashSet};
+
+lazy_static::lazy_static! {
+    static ref HEADING_LINK_RE: Regex = Regex::new(r"\[\[([^\]|#]+)#([^\]|]+)((?:\|[^\]]*)?)\]\]").unwrap();
+    static ref HEADING_ATTR_RE: Regex = Regex::new(r##"data-wiki-link="([^"#|]+)#([^"|]+)""##).unwrap();
+}
+
+/// Part of a note body: kept in the original, or a section that becomes its own note
+#[derive(Debug, Clone, PartialEq)]
+pub enum BodyBlock {
+    Kept(String),
+    Section {
+        heading: String,
+        /// Section text without the heading line
+        content: String,
+        /// Deeper headings inside the section
+        subheadings: Vec<String>,
+    },
+}
+
+/// ATX heading level and text ("## Notes ##" -> (2, "Notes"))
+fn heading_of(line: &str) -> Option<(usize, String)> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((level, rest.trim().trim_end_matches('#').trim().to_string()))
+}
+
+/// Cut `body` at every heading of exactly `level`. A section runs until the next heading of
+/// the same or a higher level; text before the first section or after a higher heading is kept.
+pub fn split_sections(body: &str, level: usize) -> Vec<BodyBlock> {
+    let mut blocks = Vec::new();
+    let mut kept: Vec<&str> = Vec::new();
+    let mut section: Option<(String, Vec<&str>, Vec<String>)> = None;
+    let mut in_fence = false;
+
+    let flush_section = |section: &mut Option<(String, Vec<&str>, Vec<String>)>, blocks: &mut Vec<BodyBlock>| {
+        if let Some((heading, lines, subheadings)) = section.take() {
+            blocks.push(BodyBlock::Section {
+                heading,
+                content: lines.join("\n").trim_matches('\n').to_string(),
+                subheadings,
+            });
+        }
+    };
+
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let heading = if in_fence || line.trim_start().starts_with("```") { None } else { heading_of(line) };
+
+        match heading {
+            Some((l, text)) if l == level => {
+                flush_section(&mut section, &mut blocks);
+                if !kept.is_empty() {
+                    blocks.push(BodyBlock::Kept(kept.join("\n")));
+                    kept.clear();
+                }
+                section = Some((text, Vec::new(), Vec::new()));
+            }
+            Some((l, _)) if l < level && section.is_some() => {
+                flush_section(&mut section, &mut blocks);
+                kept.push(line);
+            }
+            Some((_, text)) if section.is_some() => {
+                let (_, lines, subheadings) = section.as_mut().unwrap();
+                subheadings.push(text);
+                lines.push(line);
+            }
+            _ => match section.as_mut() {
+                Some((_, lines, _)) => lines.push(line),
+                None => kept.push(line),
+            },
+        }
+    }
+    flush_section(&mut section, &mut blocks);
+    if !kept.is_empty() {
+        blocks.push(BodyBlock::Kept(kept.join("\n")));
+    }
+    blocks
+}
+
+/// File stem for a section note: heading text without characters invalid in file names or links
+pub fn note_name_for_heading(heading: &str) -> String {
+    let cleaned: String = heading
+        .chars()
+        .filter(|c| !matches!(c, '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']'))
+        .collect();
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ").trim_matches('.').to_string()
+}
+
+/// Attachment file names referenced by wiki links in `text` ("[[a_att/b.pdf]]" -> "b.pdf")
+pub fn referenced_names(text: &str) -> HashSet<String> {
+    extract_wiki_links(text)
+        .into_iter()
+        .map(|link| {
+            let link = link.split('#').next().unwrap_or_default();
+            link.rsplit(['/', '\\']).next().unwrap_or_default().trim().to_string()
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Assign memos to the single text (original or section) containing their anchor text.
+/// Ambiguous or unmatched memos stay with the original (`texts[0]`).
+pub fn partition_comments(comments: Vec<serde_json::Value>, texts: &[&str]) -> Vec<Vec<serde_json::Value>> {
+    let mut parts = vec![Vec::new(); texts.len()];
+    for comment in comments {
+        let anchor = comment.get("anchorText").and_then(|v| v.as_str()).unwrap_or_default();
+        let on_canvas = comment.get("canvasNodeId").is_some();
+        let mut holders = texts.iter().enumerate().filter(|(_, t)| !anchor.is_empty() && t.contains(anchor));
+        let owner = match (holders.next(), holders.next()) {
+            (Some((i, _)), None) if !on_canvas => i,
+            _ => 0,
+        };
+        parts[owner].push(comment);
+    }
+    parts
+}
+
+/// Rewrites links into split sections: `[[Note#Section]]` -> `[[New]]`,
+/// `[[Note#Subheading]]` -> `[[New#Subheading]]` (aliases kept)
+pub struct HeadingRetarget {
+    note_stem: String,
+    /// lowercase heading -> (new note stem, heading is the section itself)
+    targets: HashMap<String, (String, bool)>,
+}
+
+impl HeadingRetarget {
+    pub fn new(note_stem: &str) -> Self {
+        Self { note_stem: note_stem.to_lowercase(), targets: HashMap::new() }
+    }
+
+    pub fn add_section(&mut self, heading: &str, subheadings: &[String], new_stem: &str) {
+        self.targets
+            .entry(heading.trim().to_lowercase())
+            .or_insert_with(|| (new_stem.to_string(), true));
+        for sub in subheadings {
+            self.targets
+                .entry(sub.trim().to_lowercase())
+                .or_insert_with(|| (new_stem.to_string(), false));
+        }
+    }
+
+    fn is_note(&self, file: &str) -> bool {
+        let name = file.trim().rsplit(['/', '\\']).next().unwrap_or_default();
+        name.trim_end_matches(".md").to_lowercase() == self.note_stem
+    }
+
+    fn retarget(&self, file: &str, heading: &str) -> Option<String> {
+        if !self.is_note(file) {
+            return None;
+        }
+        let (new_stem, is_section) = self.targets.get(&heading.trim().to_lowercase())?;
+        Some(if *is_section { new_stem.clone() } else { format!("{}#{}", new_stem, heading) })
+    }
+
+    /// Some(updated) if any link was retargeted
+    pub fn apply(&self, content: &str) -> Option<String> {
+        let mut changed = false;
+        let updated = HEADING_LINK_RE.replace_all(content, |caps: &Captures| match self.retarget(&caps[1], &caps[2]) {
+            Some(target) => {
+                changed = true;
+                format!("[[{}{}]]", target, &caps[3])
+            }
+            None => caps[0].to_string(),
+        });
+        let updated = HEADING_ATTR_RE.replace_all(&updated, |caps: &Captures| match self.retarget(&caps[1], &caps[2]) {
+            Some(target) => {
+                changed = true;
+                format!("data-wiki-link=\"{}\"", target)
+            }
+            None => caps[0].to_string(),
+        });
+        changed.then(|| updated.into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_sections_keeps_preamble_and_higher_headings() {
+        let body = "머리말\n\n## 하나\n본문1\n### 세부\n내용\n## 둘\n```\n## 코드 속\n```\n# 부록\n끝";
+        let blocks = split_sections(body, 2);
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(blocks[0], BodyBlock::Kept("머리말\n".to_string()));
+        assert_eq!(
+            blocks[1],
+            BodyBlock::Section {
+                heading: "하나".to_string(),
+                content: "본문1\n### 세부\n내용".to_string(),
+                subheadings: vec!["세부".to_string()],
+            }
+        );
+        assert!(matches!(&blocks[2], BodyBlock::Section { heading, content, .. } if heading == "둘" && content.contains("## 코드 속")));
+        assert_eq!(blocks[3], BodyBlock::Kept("# 부록\n끝".to_string()));
+    }
+
+    #[test]
+    fn test_heading_links_are_retargeted() {
+        let mut retarget = HeadingRetarget::new("회의록");
+        retarget.add_section("결정 사항", &["예산".to_string()], "결정 사항");
+        let content = "[[회의록#결정 사항|결정]] [[폴더/회의록#예산]] [[회의록#기타]] [[다른#결정 사항]] <span data-wiki-link=\"회의록#결정 사항\">x</span>";
+        assert_eq!(
+            retarget.apply(content).unwrap(),
+            "[[결정 사항|결정]] [[결정 사항#예산]] [[회의록#기타]] [[다른#결정 사항]] <span data-wiki-link=\"결정 사항\">x</span>"
+        );
+    }
+
+    #[test]
+    fn test_note_name_and_comment_partition() {
+        assert_eq!(note_name_for_heading(" 1/2 분기: [[계획]]? "), "12 분기 계획");
+
+        let comments = vec![
+            serde_json::json!({"id": "a", "anchorText": "고유 문장"}),
+            serde_json::json!({"id": "b", "anchorText": "공통"}),
+        ];
+        let parts = partition_comments(comments, &["공통 머리말", "공통 고유 문장"]);
+        assert_eq!(parts[0][0]["id"], "b", "여러 곳에 있으면 원본에 남김");
+        assert_eq!(parts[1][0]["id"], "a");
+    }
+}
@@ -0,0 +1,100 @@
+// Note split - 제목 단위 분할, frontmatter 상속, 첨부·메모 이동, 제목 링크 재지정 검증
+
+#[cfg(test)]
+mod note_split_tests {
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn s(p: &Path) -> String {
+        p.to_string_lossy().to_string()
+    }
+
+    const NOTE: &str = "---\nid: \"20260101090000\"\ntitle: \"분기 회의\"\ntype: MTG\ncreated: \"2026-01-01T09:00:00+09:00\"\nmodified: \"2026-01-01T09:00:00+09:00\"\ntags:\n  domain: [기획]\nparticipants: [김, 이]\n---\n\n참석: 김, 이\n\n## 예산\n예산 초안 [[예산표.xlsx]]\n### 세부\n인건비\n\n## 일정\n출시일 확정 [[공통.pdf]]\n\n# 부록\n[[공통.pdf]]\n";
+
+    fn setup_vault(vault: &Path) {
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        fs::create_dir_all(vault.join("분기 회의_att")).unwrap();
+        fs::write(vault.join("분기 회의.md"), NOTE).unwrap();
+        fs::write(vault.join("분기 회의_att").join("예산표.xlsx"), b"xlsx").unwrap();
+        fs::write(vault.join("분기 회의_att").join("공통.pdf"), b"pdf").unwrap();
+        fs::write(
+            vault.join("분기 회의_att").join("comments.json"),
+            r#"[{"id":"m1","anchorText":"인건비"},{"id":"m2","anchorText":"참석"}]"#,
+        )
+        .unwrap();
+        fs::write(
+            vault.join("보고.md"),
+            "[[분기 회의#예산|예산 논의]] [[분기 회의#세부]] [[분기 회의]]\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_split_note_by_level_two_headings() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+
+        let created = crate::split_note(s(&vault.join("분기 회의.md")), 2, s(vault)).unwrap();
+        assert_eq!(created, vec![s(&vault.join("예산.md")), s(&vault.join("일정.md"))]);
+
+        // 원본: 머리말 + 링크 목록 + 상위 제목 이하 유지, frontmatter 원문 그대로
+        let original = fs::read_to_string(vault.join("분기 회의.md")).unwrap();
+        assert!(original.starts_with(&NOTE[..NOTE.find("참석").unwrap()]));
+        assert!(original.ends_with("참석: 김, 이\n\n- [[예산]]\n- [[일정]]\n\n# 부록\n[[공통.pdf]]\n"), "{}", original);
+
+        // 새 노트: frontmatter 상속, 새 id
+        let budget = fs::read_to_string(vault.join("예산.md")).unwrap();
+        let (fm, body) = crate::frontmatter::FrontmatterParser::parse(&budget).unwrap();
+        let fm = fm.unwrap();
+        assert_eq!(fm.title, "예산");
+        assert_ne!(fm.id, "20260101090000");
+        assert_eq!(fm.tags.domain, vec!["기획"]);
+        assert_eq!(fm.participants.unwrap(), vec!["김", "이"]);
+        assert_eq!(body, "예산 초안 [[예산표.xlsx]]\n### 세부\n인건비\n");
+
+        // 첨부: 한 섹션에서만 쓰는 파일만 이동
+        assert!(vault.join("예산_att").join("예산표.xlsx").exists());
+        assert!(vault.join("분기 회의_att").join("공통.pdf").exists());
+        assert!(!vault.join("일정_att").exists());
+
+        // 메모: 앵커 텍스트가 있는 섹션으로
+        let moved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(vault.join("예산_att").join("comments.json")).unwrap()).unwrap();
+        assert_eq!(moved[0]["id"], "m1");
+        let kept: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(vault.join("분기 회의_att").join("comments.json")).unwrap()).unwrap();
+        assert_eq!(kept.as_array().unwrap().len(), 1);
+
+        // 제목 링크 재지정
+        assert_eq!(
+            fs::read_to_string(vault.join("보고.md")).unwrap(),
+            "[[예산|예산 논의]] [[예산#세부]] [[분기 회의]]\n"
+        );
+        assert!(!vault.join(".notology").join("staging").read_dir().unwrap().any(|_| true));
+    }
+
+    #[test]
+    fn test_split_without_matching_headings_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+
+        assert!(crate::split_note(s(&vault.join("분기 회의.md")), 4, s(vault)).is_err());
+        assert_eq!(fs::read_to_string(vault.join("분기 회의.md")).unwrap(), NOTE);
+    }
+
+    #[test]
+    fn test_split_names_avoid_existing_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+        fs::write(vault.join("일정.md"), "기존 노트").unwrap();
+
+        let created = crate::split_note(s(&vault.join("분기 회의.md")), 2, s(vault)).unwrap();
+        assert_eq!(created[1], s(&vault.join("일정_1.md")));
+        assert_eq!(fs::read_to_string(vault.join("일정.md")).unwrap(), "기존 노트");
+        assert!(fs::read_to_string(vault.join("분기 회의.md")).unwrap().contains("- [[일정_1]]"));
+    }
+}
@@ -0,0 +1,219 @@
+//! Path-style link rewriting for moved files and folders
+//!
+//! Stem links (`[[Note]]`) survive a move, but path-style links (`[[folder/Note]]`,
+//! `[[folder/Note_att/file.pdf]]`, `data-wiki-link="folder/Note"`) name the old location.
+//! Links are matched by path suffix (as the link graph resolves them) and rewritten to
+//! the same number of trailing components of the new path.
+
+use regex::{Captures, Regex};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+lazy_static::lazy_static! {
+    static ref WIKI_TARGET_RE: Regex = Regex::new(r"(\[\[)([^\]|#]+)").unwrap();
+    static ref ATTR_TARGET_RE: Regex = Regex::new(r##"(data-wiki-link=")([^"#|]+)"##).unwrap();
+}
+
+/// Where `path` ends up after applying `renames` in order (component-wise prefix replacement)
+pub fn map_through_renames(path: &Path, renames: &[(PathBuf, PathBuf)]) -> PathBuf {
+    let mut current = path.to_path_buf();
+    for (from, to) in renames {
+        if let Ok(rest) = current.strip_prefix(from) {
+            current = if rest.as_os_str().is_empty() { to.clone() } else { to.join(rest) };
+        }
+    }
+    current
+}
+
+/// Vault-relative link path with "/" separators; notes lose their ".md"
+fn link_path(vault: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(vault).ok()?.to_string_lossy().replace('\\', "/");
+    Some(rel.strip_suffix(".md").map(str::to_string).unwrap_or(rel))
+}
+
+pub struct PathLinkRewriter {
+    /// lowercase old path suffix (2+ components) -> new vault-relative link path
+    by_suffix: HashMap<String, String>,
+}
+
+/// Lowercase path suffixes of 2+ components, longest (the full link path) first
+fn suffixes(rel: &str) -> Vec<String> {
+    let parts: Vec<&str> = rel.split('/').collect();
+    (2..=parts.len()).rev().map(|k| parts[parts.len() - k..].join("/").to_lowercase()).collect()
+}
+
+impl PathLinkRewriter {
+    /// Index every file that `renames` would move. Call before anything is mutated:
+    /// sources are walked as they exist now, then mapped through the whole rename chain.
+    /// A shorter suffix shared with any other file in the vault is ambiguous and is not
+    /// rewritten; links using a moved file's full vault-relative path always are.
+    pub fn new(vault: &Path, renames: &[(PathBuf, PathBuf)]) -> Self {
+        let mut files: BTreeSet<PathBuf> = BTreeSet::new();
+        for (from, _) in renames {
+            if from.is_dir() {
+                files.extend(
+                    WalkDir::new(from)
+                        .into_iter()
+                        .flatten()
+                        .filter(|e| e.file_type().is_file())
+                        .map(|e| e.into_path()),
+                );
+            } else if from.is_file() {
+                files.insert(from.clone());
+            }
+        }
+
+        let mut moved: Vec<(Vec<String>, String)> = Vec::new();
+        for file in files {
+            let new_file = map_through_renames(&file, renames);
+            let (Some(old_rel), Some(new_rel)) = (link_path(vault, &file), link_path(vault, &new_file)) else {
+                continue;
+            };
+            if old_rel != new_rel {
+                moved.push((suffixes(&old_rel), new_rel));
+            }
+        }
+        if moved.is_empty() {
+            return Self { by_suffix: HashMap::new() };
+        }
+
+        // How many files in the vault (moved or not) each candidate suffix names
+        let mut counts: HashMap<String, usize> =
+            moved.iter().flat_map(|(s, _)| s.iter().skip(1)).map(|s| (s.clone(), 0)).collect();
+        let visible = |e: &walkdir::DirEntry| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.');
+        for entry in WalkDir::new(vault).into_iter().filter_entry(visible).flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Some(rel) = link_path(vault, entry.path()) else { continue };
+            for suffix in suffixes(&rel) {
+                if let Some(count) = counts.get_mut(&suffix) {
+                    *count += 1;
+                }
+            }
+        }
+
+        let mut by_suffix = HashMap::new();
+        for (old_suffixes, new_rel) in moved {
+            let mut old_suffixes = old_suffixes.into_iter();
+            if let Some(full) = old_suffixes.next() {
+                by_suffix.insert(full, new_rel.clone());
+            }
+            for suffix in old_suffixes.filter(|s| counts.get(s) == Some(&1)) {
+                by_suffix.entry(suffix).or_insert_with(|| new_rel.clone());
+            }
+        }
+        Self { by_suffix }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_suffix.is_empty()
+    }
+
+    /// New link text for a path-style link target, if it points at a moved file
+    pub fn rewrite_target(&self, target: &str) -> Option<String> {
+        let trimmed = target.trim();
+        if !trimmed.contains('/') && !trimmed.contains('\\') {
+            return None;
+        }
+        let normalized = trimmed.trim_start_matches('/').replace('\\', "/");
+        let (name, had_md) = match normalized.strip_suffix(".md") {
+            Some(n) => (n.to_string(), true),
+            None => (normalized.clone(), false),
+        };
+        let new_rel = self.by_suffix.get(&name.to_lowercase())?;
+
+        let k = name.split('/').count();
+        let new_parts: Vec<&str> = new_rel.split('/').collect();
+        let mut rewritten = new_parts[new_parts.len().saturating_sub(k)..].join("/");
+        if had_md {
+            rewritten.push_str(".md");
+        }
+        (rewritten != trimmed).then_some(rewritten)
+    }
+
+    /// Rewrite `[[path/...]]` and `data-wiki-link="path/..."` targets; Some(updated) if changed
+    pub fn apply(&self, content: &str) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut changed = false;
+        let mut replace = |caps: &Captures| -> String {
+            match self.rewrite_target(&caps[2]) {
+                Some(new_target) => {
+                    changed = true;
+                    format!("{}{}", &caps[1], new_target)
+                }
+                None => caps[0].to_string(),
+            }
+        };
+        let updated = WIKI_TARGET_RE.replace_all(content, &mut replace).into_owned();
+        let updated = ATTR_TARGET_RE.replace_all(&updated, &mut replace).into_owned();
+        changed.then_some(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_through_rename_chain() {
+        let renames = vec![
+            (PathBuf::from("/v/a"), PathBuf::from("/v/b")),
+            (PathBuf::from("/v/b/a.md"), PathBuf::from("/v/b/b.md")),
+        ];
+        assert_eq!(map_through_renames(Path::new("/v/a/a.md"), &renames), PathBuf::from("/v/b/b.md"));
+        assert_eq!(map_through_renames(Path::new("/v/a/x/n.md"), &renames), PathBuf::from("/v/b/x/n.md"));
+        assert_eq!(map_through_renames(Path::new("/v/ab/n.md"), &renames), PathBuf::from("/v/ab/n.md"));
+    }
+
+    #[test]
+    fn test_rewrite_keeps_component_count_and_extension() {
+        let rewriter = PathLinkRewriter {
+            by_suffix: HashMap::from([
+                ("alpha/note".to_string(), "archive/alpha/Note".to_string()),
+                ("projects/alpha/note".to_string(), "archive/alpha/Note".to_string()),
+            ]),
+        };
+        assert_eq!(rewriter.rewrite_target("alpha/Note"), None, "접미사가 그대로면 변경 없음");
+        assert_eq!(rewriter.rewrite_target("projects/alpha/Note.md").as_deref(), Some("archive/alpha/Note.md"));
+        assert_eq!(rewriter.rewrite_target("Note"), None);
+
+        let content = "[[projects/alpha/Note#요약|별칭]] <span data-wiki-link=\"projects/alpha/Note\">x</span>";
+        assert_eq!(
+            rewriter.apply(content).unwrap(),
+            "[[archive/alpha/Note#요약|별칭]] <span data-wiki-link=\"archive/alpha/Note\">x</span>"
+        );
+    }
+
+    #[test]
+    fn test_shared_suffix_is_left_alone() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        for dir in ["projects", "other/projects", "archive", "unique"] {
+            std::fs::create_dir_all(vault.join(dir)).unwrap();
+        }
+        std::fs::write(vault.join("projects/Note.md"), "").unwrap();
+        std::fs::write(vault.join("other/projects/Note.md"), "").unwrap();
+        std::fs::write(vault.join("unique/Solo.md"), "").unwrap();
+
+        let renames = vec![
+            (vault.join("projects/Note.md"), vault.join("archive/Note.md")),
+            (vault.join("unique/Solo.md"), vault.join("archive/Solo.md")),
+        ];
+        let rewriter = PathLinkRewriter::new(vault, &renames);
+
+        assert_eq!(rewriter.rewrite_target("projects/Note").as_deref(), Some("archive/Note"), "전체 경로는 이동한 노트");
+        assert_eq!(rewriter.rewrite_target("other/projects/Note"), None, "이동하지 않은 노트의 링크 유지");
+        assert_eq!(rewriter.rewrite_target("unique/Solo").as_deref(), Some("archive/Solo"));
+
+        std::fs::create_dir_all(vault.join("deep/projects")).unwrap();
+        std::fs::rename(vault.join("projects/Note.md"), vault.join("deep/projects/Note.md")).unwrap();
+        let renames = vec![(vault.join("deep/projects/Note.md"), vault.join("archive/Note.md"))];
+        let rewriter = PathLinkRewriter::new(vault, &renames);
+        assert_eq!(rewriter.rewrite_target("projects/Note"), None, "두 노트가 공유하는 접미사는 모호하므로 유지");
+        assert_eq!(rewriter.rewrite_target("deep/projects/Note").as_deref(), Some("archive/Note"));
+    }
+}
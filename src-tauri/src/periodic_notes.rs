@@ -0,0 +1,307 @@
+//! Daily, weekly and monthly notes
+//!
+//! Each period has a folder, a file name pattern (moment-style, applied to the period's first
+//! day; weeks start on Monday and use ISO week numbers) and an optional vault template, saved
+//! in `.notology/periodic-notes.json`. Opening a period's note creates it only if the file
+//! does not exist yet.
+//!
+//! A new note carries over the open checkbox tasks of the latest earlier note of the same
+//! period and the unresolved memo tasks due in the previous period, each linking back to the
+//! note it came from. Templates place them with `{{rollover}}`; otherwise they are appended.
+
+use crate::memo::IndexedMemo;
+use crate::search::parser::split_frontmatter_body;
+use crate::templates::{self, date_format, TemplateContext};
+use crate::VaultJsonConfig;
+use chrono::{Datelike, Duration, Local, Months, NaiveDate, TimeZone};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// How many earlier periods are searched for the note to roll tasks over from
+const LOOKBACK_PERIODS: u32 = 60;
+const ROLLOVER_HEADING: &str = "## Rolled over";
+/// Layout used when a period has no template
+const DEFAULT_TEMPLATE: &str = "---\ntype: NOTE\n---\n# {{title}}\n\n{{#if rollover}}\n## Rolled over\n{{rollover}}\n{{/if}}\n";
+
+lazy_static! {
+    static ref OPEN_TASK: Regex = Regex::new(r"^\s*[-*+]\s+\[ \]\s+(.*\S)\s*$").unwrap();
+    static ref OPEN_TASK_HTML: Regex =
+        Regex::new(r#"(?s)<li[^>]*data-type="taskItem"[^>]*data-checked="false"[^>]*>(.*?)</li>"#).unwrap();
+    static ref HTML_TAG: Regex = Regex::new(r"<[^>]+>").unwrap();
+    /// Origin link added by an earlier rollover
+    static ref ORIGIN: Regex = Regex::new(r"\s*← \[\[[^\]]*\]\]$").unwrap();
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Period {
+    /// First day of the period containing `date`
+    pub fn start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Daily => date,
+            Period::Weekly => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            Period::Monthly => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// First day of the period before the one starting at `start`
+    fn previous(self, start: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Daily => start - Duration::days(1),
+            Period::Weekly => start - Duration::days(7),
+            Period::Monthly => start.checked_sub_months(Months::new(1)).unwrap_or(start),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PeriodConfig {
+    /// Vault-relative folder
+    pub folder: String,
+    /// File name pattern, e.g. `YYYY-MM-DD`, `GGGG-[W]WW` or `YYYY-MM`
+    pub name_format: String,
+    /// Vault template name; the built-in layout when unset
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Carry open tasks of the previous period into new notes
+    #[serde(default = "default_rollover")]
+    pub rollover: bool,
+}
+
+fn default_rollover() -> bool {
+    true
+}
+
+impl PeriodConfig {
+    fn new(folder: &str, name_format: &str) -> Self {
+        Self { folder: folder.to_string(), name_format: name_format.to_string(), template: None, rollover: true }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PeriodicNotesConfig {
+    #[serde(default = "default_daily")]
+    pub daily: PeriodConfig,
+    #[serde(default = "default_weekly")]
+    pub weekly: PeriodConfig,
+    #[serde(default = "default_monthly")]
+    pub monthly: PeriodConfig,
+}
+
+fn default_daily() -> PeriodConfig {
+    PeriodConfig::new("Daily", "YYYY-MM-DD")
+}
+
+fn default_weekly() -> PeriodConfig {
+    PeriodConfig::new("Weekly", "GGGG-[W]WW")
+}
+
+fn default_monthly() -> PeriodConfig {
+    PeriodConfig::new("Monthly", "YYYY-MM")
+}
+
+impl Default for PeriodicNotesConfig {
+    fn default() -> Self {
+        Self { daily: default_daily(), weekly: default_weekly(), monthly: default_monthly() }
+    }
+}
+
+impl VaultJsonConfig for PeriodicNotesConfig {
+    const FILE_NAME: &'static str = "periodic-notes.json";
+
+    fn validate(&self) -> Result<(), String> {
+        for (period, config) in [(Period::Daily, &self.daily), (Period::Weekly, &self.weekly), (Period::Monthly, &self.monthly)] {
+            let folder = Path::new(&config.folder);
+            if folder.is_absolute() || config.folder.split(['/', '\\']).any(|part| part == "..") {
+                return Err(format!("{:?} folder must be inside the vault: {}", period, config.folder));
+            }
+            let sample = NaiveDate::from_ymd_opt(2026, 1, 31).unwrap_or_default();
+            if file_name(config, sample).is_empty() {
+                return Err(format!("{:?} name format gives an empty file name: {}", period, config.name_format));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PeriodicNotesConfig {
+    pub fn get(&self, period: Period) -> &PeriodConfig {
+        match period {
+            Period::Daily => &self.daily,
+            Period::Weekly => &self.weekly,
+            Period::Monthly => &self.monthly,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PeriodicNote {
+    pub path: String,
+    pub period: Period,
+    /// First day of the period (YYYY-MM-DD)
+    pub start: String,
+    pub exists: bool,
+    /// Created by this call
+    pub created: bool,
+    /// Task lines carried over into the new note
+    pub rolled_over: Vec<String>,
+}
+
+/// File name (without `.md`) of the period starting at `start`
+fn file_name(config: &PeriodConfig, start: NaiveDate) -> String {
+    let name = start.format(&date_format(&config.name_format)).to_string();
+    name.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "-").trim().to_string()
+}
+
+/// Path of the note for the period containing `date`
+pub fn note_path(vault_path: &Path, config: &PeriodConfig, period: Period, date: NaiveDate) -> PathBuf {
+    let name = file_name(config, period.start(date));
+    vault_path.join(&config.folder).join(format!("{}.md", name))
+}
+
+/// Open checkbox tasks of a note body (markdown or editor HTML), without earlier origin links
+fn open_tasks(body: &str) -> Vec<String> {
+    let mut tasks = Vec::new();
+    let mut in_code = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        } else if !in_code {
+            if let Some(cap) = OPEN_TASK.captures(line) {
+                tasks.push(cap[1].to_string());
+            }
+        }
+    }
+    for cap in OPEN_TASK_HTML.captures_iter(body) {
+        let text = HTML_TAG.replace_all(&cap[1], " ");
+        tasks.push(text.split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+    tasks
+        .into_iter()
+        .map(|task| ORIGIN.replace(&task, "").to_string())
+        .filter(|task| !task.is_empty())
+        .collect()
+}
+
+fn stem(path: &Path) -> String {
+    path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+/// Task lines for a new period note starting at `start`
+fn rollover_tasks(vault_path: &Path, config: &PeriodConfig, period: Period, start: NaiveDate, memos: &[IndexedMemo]) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    let mut earlier = start;
+    for _ in 0..LOOKBACK_PERIODS {
+        earlier = period.previous(earlier);
+        let path = vault_path.join(&config.folder).join(format!("{}.md", file_name(config, earlier)));
+        let Ok(content) = std::fs::read_to_string(&path) else { continue };
+        let (_, body) = split_frontmatter_body(&content);
+        let origin = stem(&path);
+        lines.extend(open_tasks(&body).into_iter().map(|task| format!("- [ ] {} ← [[{}]]", task, origin)));
+        break;
+    }
+
+    let (from, to) = (period.previous(start).format("%Y-%m-%d").to_string(), start.format("%Y-%m-%d").to_string());
+    for memo in memos {
+        let Some(task) = memo.task.as_ref().filter(|_| !memo.resolved) else { continue };
+        let date = task.due_date.as_deref().unwrap_or(&memo.created);
+        if date >= from.as_str() && date < to.as_str() {
+            let summary = if task.summary.trim().is_empty() { &memo.content } else { &task.summary };
+            lines.push(format!("- [ ] {} ← [[{}]]", summary.trim(), stem(Path::new(&memo.note_path))));
+        }
+    }
+    lines
+}
+
+/// The note for the period containing `date`, created (with task rollover) when `create` is
+/// set and it does not exist yet. `memos` are the vault's memos; unresolved tasks among them
+/// due in the previous period are rolled over.
+pub fn open_periodic_note(
+    vault_path: &Path,
+    config: &PeriodicNotesConfig,
+    period: Period,
+    date: NaiveDate,
+    create: bool,
+    memos: &[IndexedMemo],
+    taken: impl Fn(&str) -> bool,
+) -> Result<PeriodicNote, String> {
+    let period_config = config.get(period);
+    let start = period.start(date);
+    let path = note_path(vault_path, period_config, period, date);
+    let mut note = PeriodicNote {
+        path: path.to_string_lossy().to_string(),
+        period,
+        start: start.format("%Y-%m-%d").to_string(),
+        exists: path.exists(),
+        created: false,
+        rolled_over: Vec::new(),
+    };
+    if note.exists || !create {
+        return Ok(note);
+    }
+
+    let rolled_over = if period_config.rollover {
+        rollover_tasks(vault_path, period_config, period, start, memos)
+    } else {
+        Vec::new()
+    };
+    let rollover = rolled_over.join("\n");
+    let now = Local::now();
+    let context = TemplateContext {
+        title: stem(&path),
+        container: Path::new(&period_config.folder).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        answers: HashMap::from([("rollover".to_string(), rollover.clone())]),
+        now: Local.from_local_datetime(&start.and_time(now.time())).earliest().unwrap_or(now),
+    };
+    let rendered = match &period_config.template {
+        Some(template) => templates::render_template(vault_path, template, &context, taken)?,
+        None => templates::render_template_source(vault_path, "periodic", DEFAULT_TEMPLATE, &context, taken)?,
+    };
+    let mut content = rendered.content;
+    if !rollover.is_empty() && !content.contains(&rollover) {
+        content = format!("{}\n\n{}\n{}\n", content.trim_end(), ROLLOVER_HEADING, rollover);
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    crate::atomic_write_file(&path, content.as_bytes())?;
+    note.exists = true;
+    note.created = true;
+    note.rolled_over = rolled_over;
+    Ok(note)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_period_starts_and_names() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 11).unwrap(); // Wednesday
+        assert_eq!(Period::Weekly.start(date).to_string(), "2026-03-09");
+        assert_eq!(Period::Monthly.start(date).to_string(), "2026-03-01");
+        assert_eq!(Period::Monthly.previous(Period::Monthly.start(date)).to_string(), "2026-02-01");
+
+        let config = PeriodicNotesConfig::default();
+        let jan1 = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap();
+        assert_eq!(file_name(&config.weekly, Period::Weekly.start(jan1)), "2026-W53", "ISO 연도/주차");
+        assert_eq!(file_name(&config.daily, date), "2026-03-11");
+    }
+
+    #[test]
+    fn test_open_tasks() {
+        let body = "- [ ] 보고서 ← [[2026-03-10]]\n- [x] 끝\n  * [ ] 하위 작업\n```\n- [ ] 코드\n```\n<ul data-type=\"taskList\"><li data-type=\"taskItem\" data-checked=\"false\"><p>HTML <b>작업</b></p></li></ul>";
+        assert_eq!(open_tasks(body), vec!["보고서", "하위 작업", "HTML 작업"]);
+    }
+}
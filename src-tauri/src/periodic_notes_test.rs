@@ -0,0 +1,96 @@
+// Periodic notes - 일간/주간 노트 생성, 재실행 시 변경 없음, 이전 기간 할 일/메모 할 일 이월 검증
+
+#[cfg(test)]
+mod periodic_notes_tests {
+    use crate::frontmatter::FrontmatterParser;
+    use crate::memo::{IndexedMemo, MemoTask};
+    use crate::periodic_notes::{open_periodic_note, Period, PeriodicNotesConfig};
+    use crate::templates::templates_dir;
+    use crate::VaultJsonConfig;
+    use chrono::NaiveDate;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn memo(note_path: &str, summary: &str, due: &str, resolved: bool) -> IndexedMemo {
+        IndexedMemo {
+            id: summary.to_string(),
+            note_path: note_path.to_string(),
+            note_title: "프로젝트".to_string(),
+            content: String::new(),
+            anchor_text: String::new(),
+            created: "2026-03-01".to_string(),
+            created_time: String::new(),
+            resolved,
+            task: Some(MemoTask { summary: summary.to_string(), due_date: Some(due.to_string()), due_time: None }),
+        }
+    }
+
+    #[test]
+    fn test_daily_note_rolls_over_tasks_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let config = PeriodicNotesConfig::default();
+        fs::create_dir_all(vault.join("Daily")).unwrap();
+        // 하루 건너뛴 이전 노트에서도 이월
+        fs::write(
+            vault.join("Daily").join("2026-03-08.md"),
+            "---\ntitle: 2026-03-08\n---\n\n- [x] 끝난 일\n- [ ] 보고서 작성\n- [ ] 회의 준비 ← [[2026-03-07]]\n",
+        )
+        .unwrap();
+        let project = vault.join("프로젝트.md").to_string_lossy().to_string();
+        let memos = vec![
+            memo(&project, "예산 검토", "2026-03-09", false),
+            memo(&project, "해결된 일", "2026-03-09", true),
+            memo(&project, "다음 주 일", "2026-03-12", false),
+        ];
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+
+        let peek = open_periodic_note(vault, &config, Period::Daily, today, false, &memos, |_| false).unwrap();
+        assert!(!peek.exists && !peek.created);
+        assert!(!vault.join("Daily").join("2026-03-10.md").exists(), "create=false는 파일을 만들지 않음");
+
+        let note = open_periodic_note(vault, &config, Period::Daily, today, true, &memos, |_| false).unwrap();
+        assert!(note.created);
+        assert_eq!(
+            note.rolled_over,
+            vec!["- [ ] 보고서 작성 ← [[2026-03-08]]", "- [ ] 회의 준비 ← [[2026-03-08]]", "- [ ] 예산 검토 ← [[프로젝트]]"]
+        );
+        let content = fs::read_to_string(&note.path).unwrap();
+        assert!(content.ends_with("---\n\n# 2026-03-10\n\n## Rolled over\n- [ ] 보고서 작성 ← [[2026-03-08]]\n- [ ] 회의 준비 ← [[2026-03-08]]\n- [ ] 예산 검토 ← [[프로젝트]]\n"), "{}", content);
+        let (fm, _) = FrontmatterParser::parse(&content).unwrap();
+        assert!(FrontmatterParser::validate(&fm.unwrap()).unwrap().is_empty());
+
+        let again = open_periodic_note(vault, &config, Period::Daily, today, true, &memos, |_| false).unwrap();
+        assert!(again.exists && !again.created);
+        assert_eq!(fs::read_to_string(&note.path).unwrap(), content, "두 번째 열기는 변경 없음");
+    }
+
+    #[test]
+    fn test_weekly_note_with_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(templates_dir(vault)).unwrap();
+        fs::write(templates_dir(vault).join("주간.md"), "---\ntype: NOTE\n---\n# {{date:YYYY년 M월 D일}} 주간\n\n## 지난 주\n{{rollover}}\n").unwrap();
+        fs::write(templates_dir(vault).join("월간.md"), "---\ntype: NOTE\n---\n# {{title}}\n").unwrap();
+        let mut config = PeriodicNotesConfig::default();
+        config.weekly.folder = "기록/주간".to_string();
+        config.weekly.template = Some("주간".to_string());
+        config.monthly.template = Some("월간".to_string());
+        fs::create_dir_all(vault.join("기록").join("주간")).unwrap();
+        fs::write(vault.join("기록").join("주간").join("2026-W10.md"), "- [ ] 배포\n").unwrap();
+
+        let wednesday = NaiveDate::from_ymd_opt(2026, 3, 11).unwrap();
+        let note = open_periodic_note(vault, &config, Period::Weekly, wednesday, true, &[], |_| false).unwrap();
+        assert!(note.path.ends_with("2026-W11.md"));
+        assert_eq!(note.start, "2026-03-09");
+        assert!(fs::read_to_string(&note.path).unwrap().ends_with("# 2026년 3월 9일 주간\n\n## 지난 주\n- [ ] 배포 ← [[2026-W10]]\n"));
+
+        fs::create_dir_all(vault.join("Monthly")).unwrap();
+        fs::write(vault.join("Monthly").join("2026-02.md"), "- [ ] 정산\n").unwrap();
+        let monthly = open_periodic_note(vault, &config, Period::Monthly, wednesday, true, &[], |_| false).unwrap();
+        assert!(fs::read_to_string(&monthly.path).unwrap().ends_with("# 2026-03\n\n## Rolled over\n- [ ] 정산 ← [[2026-02]]\n"), "템플릿에 {{rollover}}가 없으면 끝에 추가");
+
+        config.daily.folder = "../밖".to_string();
+        assert!(config.validate().is_err());
+    }
+}
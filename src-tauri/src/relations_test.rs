@@ -0,0 +1,200 @@
+// Typed semantic relations - 인덱싱, 그래프 엣지, 관계 질의 검증
+
+#[cfg(test)]
+mod relations_tests {
+    use crate::search::parser::{extract_relations, normalize_relation_type, parse_frontmatter};
+    use crate::search::{RelationDirection, SearchIndex};
+    use crate::test_common::{open_index, IndexDirGuard};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn write_note(dir: &Path, name: &str, relations: &str) {
+        let content = format!(
+            "---\ntitle: {}\ntype: NOTE\nrelations:\n{}---\n\n본문 {}\n",
+            name, relations, name
+        );
+        fs::write(dir.join(format!("{}.md", name)), content).unwrap();
+    }
+
+    fn setup_vault() -> (TempDir, SearchIndex, IndexDirGuard) {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+
+        write_note(vault, "가설", "  []\n");
+        write_note(
+            vault,
+            "반박노트",
+            "  - relation_type: refutes\n    target: \"[[가설]]\"\n    strength: 0.9\n",
+        );
+        write_note(
+            vault,
+            "파생1",
+            "  - relation_type: derives-from\n    target: 가설\n",
+        );
+        write_note(
+            vault,
+            "파생2",
+            "  - relation_type: derives-from\n    target: \"[[파생1|첫 파생]]\"\n    strength: 0.5\n",
+        );
+        write_note(
+            vault,
+            "지지노트",
+            "  - relation_type: supports\n    target: 가설\n  - relation_type: derives-from\n    target: 파생2\n",
+        );
+
+        let (index, guard) = open_index(vault);
+        (temp_dir, index, guard)
+    }
+
+    fn note_path(temp_dir: &TempDir, name: &str) -> String {
+        temp_dir.path().join(format!("{}.md", name)).to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_extract_relations_normalizes_type_and_target() {
+        let fm = parse_frontmatter(
+            "relations:\n  - relation_type: DerivesFrom\n    target: \"[[Note A#Heading|alias]]\"\n    strength: 1.7\n  - relation_type: supports\n  - target: missing-type\n",
+        );
+        let relations = extract_relations(&fm);
+
+        assert_eq!(relations.len(), 1, "relation_type 또는 target이 없으면 건너뛰어야 함");
+        assert_eq!(relations[0].relation_type, "derives-from");
+        assert_eq!(relations[0].target, "Note A");
+        assert_eq!(relations[0].strength, Some(1.0), "strength는 0.0-1.0으로 제한");
+        assert_eq!(relations[0].index_key(), "derives-from:note a");
+    }
+
+    #[test]
+    fn test_normalize_relation_type_variants() {
+        assert_eq!(normalize_relation_type("derives_from"), "derives-from");
+        assert_eq!(normalize_relation_type("IsExampleOf"), "is-example-of");
+        assert_eq!(normalize_relation_type("part-of"), "part-of");
+    }
+
+    #[test]
+    fn test_notes_that_refute() {
+        let (temp_dir, index, _guard) = setup_vault();
+        let target = note_path(&temp_dir, "가설");
+
+        let results = index
+            .query_relations(&target, Some("refutes"), RelationDirection::Incoming, false)
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].path, note_path(&temp_dir, "반박노트"));
+        assert_eq!(results[0].relation_type, "refutes");
+        assert_eq!(results[0].strength, Some(0.9));
+        assert_eq!(results[0].depth, 1);
+    }
+
+    #[test]
+    fn test_derived_from_transitively() {
+        let (temp_dir, index, _guard) = setup_vault();
+        let root = note_path(&temp_dir, "가설");
+
+        let direct = index
+            .query_relations(&root, Some("derives-from"), RelationDirection::Incoming, false)
+            .unwrap();
+        assert_eq!(direct.len(), 1, "직접 파생은 파생1 하나");
+
+        let mut all = index
+            .query_relations(&root, Some("derives-from"), RelationDirection::Incoming, true)
+            .unwrap();
+        all.sort_by_key(|r| r.depth);
+
+        let paths: Vec<&str> = all.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                note_path(&temp_dir, "파생1").as_str(),
+                note_path(&temp_dir, "파생2").as_str(),
+                note_path(&temp_dir, "지지노트").as_str(),
+            ]
+        );
+        assert_eq!(all[2].depth, 3);
+        assert_eq!(all[2].via, note_path(&temp_dir, "파생2"));
+    }
+
+    #[test]
+    fn test_outgoing_relations_any_type() {
+        let (temp_dir, index, _guard) = setup_vault();
+        let source = note_path(&temp_dir, "지지노트");
+
+        let mut results = index
+            .query_relations(&source, None, RelationDirection::Outgoing, false)
+            .unwrap();
+        results.sort_by(|a, b| a.relation_type.cmp(&b.relation_type));
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].relation_type, "derives-from");
+        assert_eq!(results[0].path, note_path(&temp_dir, "파생2"));
+        assert_eq!(results[1].relation_type, "supports");
+        assert_eq!(results[1].path, note_path(&temp_dir, "가설"));
+    }
+
+    #[test]
+    fn test_relation_edges_in_graph() {
+        let (temp_dir, index, _guard) = setup_vault();
+        let graph = index.get_graph_data(None, false).unwrap();
+
+        let refute_edge = graph
+            .edges
+            .iter()
+            .find(|e| e.edge_type == "relation" && e.relation_type.as_deref() == Some("refutes"))
+            .expect("refutes 관계 엣지가 있어야 함");
+        assert_eq!(refute_edge.source, note_path(&temp_dir, "반박노트"));
+        assert_eq!(refute_edge.target, note_path(&temp_dir, "가설"));
+        assert_eq!(refute_edge.strength, Some(0.9));
+
+        let relation_count = graph.edges.iter().filter(|e| e.edge_type == "relation").count();
+        assert_eq!(relation_count, 5);
+    }
+
+    #[test]
+    fn test_relations_updated_on_reindex_of_single_file() {
+        let (temp_dir, index, _guard) = setup_vault();
+        let target = note_path(&temp_dir, "가설");
+
+        // 반박노트의 관계를 supports로 변경
+        write_note(
+            temp_dir.path(),
+            "반박노트",
+            "  - relation_type: supports\n    target: 가설\n",
+        );
+        index.index_file(Path::new(&note_path(&temp_dir, "반박노트"))).unwrap();
+
+        let refutes = index
+            .query_relations(&target, Some("refutes"), RelationDirection::Incoming, false)
+            .unwrap();
+        assert!(refutes.is_empty(), "변경 후 refutes 관계가 남아있으면 안 됨");
+
+        let supports = index
+            .query_relations(&target, Some("supports"), RelationDirection::Incoming, false)
+            .unwrap();
+        assert_eq!(supports.len(), 2);
+    }
+    #[test]
+    fn test_path_style_relation_target_has_graph_edge() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join("연구")).unwrap();
+        fs::create_dir_all(vault.join("보관")).unwrap();
+        write_note(vault, "연구/가설", "  []\n");
+        write_note(vault, "보관/가설", "  []\n");
+        write_note(vault, "반론", "  - relation_type: refutes\n    target: \"[[보관/가설]]\"\n");
+        let (index, _guard) = open_index(vault);
+
+        let target = note_path(&temp_dir, "보관/가설");
+        let refuted_by = index
+            .query_relations(&target, Some("refutes"), RelationDirection::Incoming, false)
+            .unwrap();
+        assert_eq!(refuted_by.len(), 1);
+
+        let graph = index.get_graph_data(None, false).unwrap();
+        let edges: Vec<_> = graph.edges.iter().filter(|e| e.edge_type == "relation").collect();
+        assert_eq!(edges.len(), 1, "경로 형식 대상도 그래프 엣지로 해석");
+        assert_eq!(edges[0].source, note_path(&temp_dir, "반론"));
+        assert_eq!(edges[0].target, target);
+    }
+}
@@ -0,0 +1,310 @@
+//! Transactional rename journal
+//!
+//! A rename touches many files: the note, its `_att` folder, possibly the parent folder,
+//! and every note linking to it. Before anything is mutated, the full plan is written to
+//! `.notology/journal/<id>/` (step list + original and rewritten contents of every note).
+//!
+//! Steps are idempotent and checked against the disk, so no per-step progress is recorded:
+//! - Link rewrites run first (at pre-rename paths), file/folder renames last
+//! - A note edited between planning and applying is left untouched and reported as skipped
+//! - Case-only renames (`a.md` -> `A.md`) hop through a temporary name in both directions
+//! - A failure while applying rolls back immediately (renames reversed, originals restored)
+//! - A journal left behind by a crash is resumed at the next vault open, or rolled back
+//!   if it was already rolling back or can no longer be completed
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const JOURNAL_FILE: &str = "journal.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalStatus {
+    Applying,
+    RollingBack,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum JournalStep {
+    /// Replace a note's content; `backup` names the `<n>.orig` / `<n>.new` files in the journal dir
+    RewriteFile { path: String, backup: usize },
+    /// Rename a file or folder
+    Rename { from: String, to: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameJournal {
+    pub id: String,
+    pub description: String,
+    pub created_at: String,
+    pub status: JournalStatus,
+    pub steps: Vec<JournalStep>,
+}
+
+/// Result of replaying a leftover journal at startup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryOutcome {
+    Resumed(String),
+    RolledBack(String),
+}
+
+/// A planned rename whose journal is on disk but whose steps have not been applied yet
+pub struct RenameTransaction {
+    dir: PathBuf,
+    journal: RenameJournal,
+}
+
+fn journal_root(vault_path: &Path) -> PathBuf {
+    vault_path.join(".notology").join("journal")
+}
+
+impl RenameTransaction {
+    /// Persist the plan. `rewrites` are (note path, original content, updated content);
+    /// `renames` are applied in order after all rewrites.
+    pub fn begin(
+        vault_path: &Path,
+        description: &str,
+        rewrites: Vec<(PathBuf, String, String)>,
+        renames: Vec<(PathBuf, PathBuf)>,
+    ) -> Result<Self, String> {
+        let id = format!("{}-{}", Utc::now().format("%Y%m%d%H%M%S%3f"), std::process::id());
+        let dir = journal_root(vault_path).join(&id);
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create rename journal: {}", e))?;
+
+        let mut steps = Vec::with_capacity(rewrites.len() + renames.len());
+        for (n, (path, original, updated)) in rewrites.into_iter().enumerate() {
+            fs::write(dir.join(format!("{}.orig", n)), original)
+                .map_err(|e| format!("Failed to write journal backup: {}", e))?;
+            fs::write(dir.join(format!("{}.new", n)), updated)
+                .map_err(|e| format!("Failed to write journal backup: {}", e))?;
+            steps.push(JournalStep::RewriteFile {
+                path: path.to_string_lossy().to_string(),
+                backup: n,
+            });
+        }
+        for (from, to) in renames {
+            steps.push(JournalStep::Rename {
+                from: from.to_string_lossy().to_string(),
+                to: to.to_string_lossy().to_string(),
+            });
+        }
+
+        let journal = RenameJournal {
+            id,
+            description: description.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            status: JournalStatus::Applying,
+            steps,
+        };
+        // Written last: a journal dir without journal.json is an incomplete plan (nothing mutated)
+        write_journal(&dir, &journal)?;
+
+        log::info!(
+            "[rename_journal] {} planned: {} steps ({})",
+            journal.id,
+            journal.steps.len(),
+            journal.description
+        );
+        Ok(Self { dir, journal })
+    }
+
+    /// Apply every step. Returns the notes whose rewrite was skipped because they changed
+    /// since planning. On failure the transaction is rolled back and the error returned.
+    pub fn apply(mut self) -> Result<Vec<PathBuf>, String> {
+        match apply_forward(&self.dir, &self.journal) {
+            Ok(skipped) => {
+                self.finish();
+                Ok(skipped)
+            }
+            Err(e) => {
+                log::warn!("[rename_journal] {} failed, rolling back: {}", self.journal.id, e);
+                self.journal.status = JournalStatus::RollingBack;
+                write_journal(&self.dir, &self.journal)?;
+                match roll_back(&self.dir, &self.journal) {
+                    Ok(()) => {
+                        self.finish();
+                        Err(e)
+                    }
+                    Err(rollback_err) => Err(format!(
+                        "{} (rollback incomplete, will retry at next startup: {})",
+                        e, rollback_err
+                    )),
+                }
+            }
+        }
+    }
+
+    fn finish(&self) {
+        if let Err(e) = fs::remove_dir_all(&self.dir) {
+            log::warn!("[rename_journal] Failed to remove journal {:?}: {}", self.dir, e);
+        }
+    }
+}
+
+fn write_journal(dir: &Path, journal: &RenameJournal) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(journal).map_err(|e| e.to_string())?;
+    crate::atomic_write_file(&dir.join(JOURNAL_FILE), json.as_bytes())
+}
+
+fn read_backup(dir: &Path, backup: usize, ext: &str) -> Result<String, String> {
+    fs::read_to_string(dir.join(format!("{}.{}", backup, ext)))
+        .map_err(|e| format!("Journal backup {}.{} unreadable: {}", backup, ext, e))
+}
+
+/// Differs from `to` only in case (the target "exists" on case-insensitive filesystems)
+fn is_case_only(from: &Path, to: &Path) -> bool {
+    from != to && from.to_string_lossy().to_lowercase() == to.to_string_lossy().to_lowercase()
+}
+
+/// `path` exists with exactly this file name, not just a case variant of it
+fn exists_exactly(path: &Path) -> bool {
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+        return path.exists();
+    };
+    fs::read_dir(parent).is_ok_and(|entries| entries.flatten().any(|e| e.file_name() == name))
+}
+
+/// Rename, hopping through a temporary name when only the case changes
+/// (a direct rename is a no-op or an error on some case-insensitive filesystems)
+fn rename_path(from: &Path, to: &Path) -> Result<(), String> {
+    let failed = |e: std::io::Error| format!("Failed to rename {} -> {}: {}", from.display(), to.display(), e);
+    if !is_case_only(from, to) {
+        return fs::rename(from, to).map_err(failed);
+    }
+    let name = from.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let temp = from.with_file_name(format!("{}.notology-tmp", name));
+    fs::rename(from, &temp).map_err(failed)?;
+    fs::rename(&temp, to).map_err(|e| {
+        let _ = fs::rename(&temp, from);
+        failed(e)
+    })
+}
+
+fn apply_forward(dir: &Path, journal: &RenameJournal) -> Result<Vec<PathBuf>, String> {
+    let mut skipped = Vec::new();
+    for step in &journal.steps {
+        match step {
+            JournalStep::RewriteFile { path, backup } => {
+                let path = Path::new(path);
+                // Already moved by a later rename step (resume after crash)
+                let Ok(current) = fs::read_to_string(path) else { continue };
+                let updated = read_backup(dir, *backup, "new")?;
+                if current == updated {
+                    continue;
+                }
+                if current != read_backup(dir, *backup, "orig")? {
+                    log::warn!("[rename_journal] {:?} changed since planning, leaving it untouched", path);
+                    skipped.push(path.to_path_buf());
+                    continue;
+                }
+                crate::atomic_write_file(path, updated.as_bytes())?;
+            }
+            JournalStep::Rename { from, to } => {
+                let (from, to) = (Path::new(from), Path::new(to));
+                let case_only = is_case_only(from, to);
+                // Done before the crash
+                if !from.exists() || (case_only && exists_exactly(to)) {
+                    continue;
+                }
+                if to.exists() && !case_only {
+                    return Err(format!("Rename target already exists: {}", to.display()));
+                }
+                rename_path(from, to)?;
+            }
+        }
+    }
+    Ok(skipped)
+}
+
+fn roll_back(dir: &Path, journal: &RenameJournal) -> Result<(), String> {
+    let mut errors = Vec::new();
+    for step in journal.steps.iter().rev() {
+        match step {
+            JournalStep::Rename { from, to } => {
+                let (from, to) = (Path::new(from), Path::new(to));
+                let applied = if is_case_only(from, to) { exists_exactly(to) } else { to.exists() && !from.exists() };
+                if applied {
+                    if let Err(e) = rename_path(to, from) {
+                        errors.push(e);
+                    }
+                }
+            }
+            JournalStep::RewriteFile { path, backup } => {
+                let path = Path::new(path);
+                let Ok(current) = fs::read_to_string(path) else { continue };
+                if current != read_backup(dir, *backup, "new")? {
+                    continue;
+                }
+                let original = read_backup(dir, *backup, "orig")?;
+                if let Err(e) = crate::atomic_write_file(path, original.as_bytes()) {
+                    errors.push(e);
+                }
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Replay journals left by an interrupted rename. Called when a vault is opened,
+/// before indexing, so the index sees the recovered state.
+pub fn recover_pending(vault_path: &Path) -> Vec<RecoveryOutcome> {
+    let Ok(entries) = fs::read_dir(journal_root(vault_path)) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect();
+    dirs.sort();
+
+    let mut outcomes = Vec::new();
+    for dir in dirs {
+        let journal: RenameJournal = match fs::read_to_string(dir.join(JOURNAL_FILE))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+        {
+            Some(j) => j,
+            None => {
+                // Incomplete plan: nothing was mutated yet
+                let _ = fs::remove_dir_all(&dir);
+                continue;
+            }
+        };
+
+        let mut journal = journal;
+        if journal.status == JournalStatus::Applying {
+            match apply_forward(&dir, &journal) {
+                Ok(skipped) => {
+                    log::info!("[rename_journal] Resumed {} ({})", journal.id, journal.description);
+                    if !skipped.is_empty() {
+                        log::warn!("[rename_journal] {} notes changed since planning kept their links: {:?}", skipped.len(), skipped);
+                    }
+                    let _ = fs::remove_dir_all(&dir);
+                    outcomes.push(RecoveryOutcome::Resumed(journal.description.clone()));
+                    continue;
+                }
+                Err(e) => {
+                    log::warn!("[rename_journal] Cannot resume {}: {}, rolling back", journal.id, e);
+                    journal.status = JournalStatus::RollingBack;
+                    let _ = write_journal(&dir, &journal);
+                }
+            }
+        }
+
+        match roll_back(&dir, &journal) {
+            Ok(()) => {
+                log::info!("[rename_journal] Rolled back {} ({})", journal.id, journal.description);
+                let _ = fs::remove_dir_all(&dir);
+                outcomes.push(RecoveryOutcome::RolledBack(journal.description.clone()));
+            }
+            Err(e) => {
+                log::error!("[rename_journal] Rollback of {} incomplete, keeping journal: {}", journal.id, e);
+            }
+        }
+    }
+    outcomes
+}
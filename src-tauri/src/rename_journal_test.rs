@@ -0,0 +1,198 @@
+// Rename journal - 트랜잭션 이름 변경, 실패 시 롤백, 크래시 후 재개/롤백 검증
+
+#[cfg(test)]
+mod rename_journal_tests {
+    use crate::rename_journal::{recover_pending, RecoveryOutcome, RenameTransaction};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn journal_dir_is_empty(vault: &Path) -> bool {
+        let dir = vault.join(".notology").join("journal");
+        !dir.exists() || fs::read_dir(&dir).unwrap().next().is_none()
+    }
+
+    #[test]
+    fn test_folder_note_rename_commits_and_clears_journal() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let folder = vault.join("프로젝트");
+        fs::create_dir_all(folder.join("프로젝트_att")).unwrap();
+        fs::write(folder.join("프로젝트.md"), "---\ntype: CONTAINER\n---\n").unwrap();
+        fs::write(folder.join("프로젝트_att").join("자료.pdf"), b"%PDF").unwrap();
+        fs::write(vault.join("참조.md"), "[[프로젝트]] 참고").unwrap();
+
+        let new_path = crate::rename_file_with_links(
+            folder.join("프로젝트.md").to_string_lossy().to_string(),
+            "연구.md".to_string(),
+            vault.to_string_lossy().to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(Path::new(&new_path.final_path), vault.join("연구").join("연구.md"));
+        assert!(vault.join("연구").join("연구_att").join("자료.pdf").exists());
+        assert!(!folder.exists());
+        assert_eq!(fs::read_to_string(vault.join("참조.md")).unwrap(), "[[연구]] 참고");
+        assert!(journal_dir_is_empty(vault), "성공 후 저널이 남으면 안 됨");
+    }
+
+    #[test]
+    fn test_failed_step_rolls_back_rewrites_and_renames() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let note = vault.join("링크.md");
+        fs::write(&note, "[[A]]").unwrap();
+        fs::write(vault.join("A.md"), "A").unwrap();
+        fs::write(vault.join("B.md"), "이미 존재").unwrap();
+        fs::write(vault.join("C.md"), "C").unwrap();
+
+        let tx = RenameTransaction::begin(
+            vault,
+            "test",
+            vec![(note.clone(), "[[A]]".into(), "[[B]]".into())],
+            vec![
+                (vault.join("C.md"), vault.join("D.md")),
+                // 대상 충돌로 실패
+                (vault.join("A.md"), vault.join("B.md")),
+            ],
+        )
+        .unwrap();
+        assert!(tx.apply().is_err());
+
+        assert_eq!(fs::read_to_string(&note).unwrap(), "[[A]]", "링크 수정이 복원되어야 함");
+        assert!(vault.join("C.md").exists() && !vault.join("D.md").exists(), "앞선 이름 변경도 되돌려야 함");
+        assert_eq!(fs::read_to_string(vault.join("B.md")).unwrap(), "이미 존재");
+        assert!(journal_dir_is_empty(vault));
+    }
+
+    #[test]
+    fn test_crash_mid_rename_is_resumed_at_startup() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let n1 = vault.join("n1.md");
+        let n2 = vault.join("n2.md");
+        fs::write(&n1, "[[옛이름]]").unwrap();
+        fs::write(&n2, "[[옛이름]] 두번째").unwrap();
+        fs::write(vault.join("옛이름.md"), "본문").unwrap();
+
+        // 계획 기록 후 첫 번째 수정만 적용된 상태에서 "크래시"
+        let _tx = RenameTransaction::begin(
+            vault,
+            "rename 옛이름 -> 새이름",
+            vec![
+                (n1.clone(), "[[옛이름]]".into(), "[[새이름]]".into()),
+                (n2.clone(), "[[옛이름]] 두번째".into(), "[[새이름]] 두번째".into()),
+            ],
+            vec![(vault.join("옛이름.md"), vault.join("새이름.md"))],
+        )
+        .unwrap();
+        fs::write(&n1, "[[새이름]]").unwrap();
+
+        let outcomes = recover_pending(vault);
+        assert_eq!(outcomes, vec![RecoveryOutcome::Resumed("rename 옛이름 -> 새이름".into())]);
+        assert_eq!(fs::read_to_string(&n2).unwrap(), "[[새이름]] 두번째");
+        assert!(vault.join("새이름.md").exists() && !vault.join("옛이름.md").exists());
+        assert!(journal_dir_is_empty(vault));
+    }
+
+    #[test]
+    fn test_interrupted_rollback_continues_at_startup() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let note = vault.join("링크.md");
+        fs::write(&note, "[[X]]").unwrap();
+        fs::write(vault.join("X.md"), "X").unwrap();
+
+        let _tx = RenameTransaction::begin(
+            vault,
+            "rename X -> Y",
+            vec![(note.clone(), "[[X]]".into(), "[[Y]]".into())],
+            vec![(vault.join("X.md"), vault.join("Y.md"))],
+        )
+        .unwrap();
+        // 모든 단계 적용 후 롤백 도중 크래시한 상황
+        fs::write(&note, "[[Y]]").unwrap();
+        fs::rename(vault.join("X.md"), vault.join("Y.md")).unwrap();
+        let journal_dir = fs::read_dir(vault.join(".notology").join("journal")).unwrap().next().unwrap().unwrap().path();
+        let journal_path = journal_dir.join("journal.json");
+        let json = fs::read_to_string(&journal_path).unwrap().replace("\"applying\"", "\"rolling_back\"");
+        fs::write(&journal_path, json).unwrap();
+
+        let outcomes = recover_pending(vault);
+        assert_eq!(outcomes, vec![RecoveryOutcome::RolledBack("rename X -> Y".into())]);
+        assert_eq!(fs::read_to_string(&note).unwrap(), "[[X]]");
+        assert!(vault.join("X.md").exists() && !vault.join("Y.md").exists());
+    }
+
+    #[test]
+    fn test_incomplete_plan_is_discarded() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let partial = vault.join(".notology").join("journal").join("20260101000000000-1");
+        fs::create_dir_all(&partial).unwrap();
+        fs::write(partial.join("0.orig"), "x").unwrap();
+
+        assert!(recover_pending(vault).is_empty());
+        assert!(!partial.exists());
+    }
+
+    #[test]
+    fn test_notes_changed_since_planning_are_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let n1 = vault.join("n1.md");
+        let n2 = vault.join("n2.md");
+        fs::write(&n1, "[[A]]").unwrap();
+        fs::write(&n2, "[[A]] 둘").unwrap();
+        fs::write(vault.join("A.md"), "A").unwrap();
+
+        let tx = RenameTransaction::begin(
+            vault,
+            "rename A -> B",
+            vec![(n1.clone(), "[[A]]".into(), "[[B]]".into()), (n2.clone(), "[[A]] 둘".into(), "[[B]] 둘".into())],
+            vec![(vault.join("A.md"), vault.join("B.md"))],
+        )
+        .unwrap();
+        fs::write(&n2, "[[A]] 둘 (수정됨)").unwrap();
+
+        let skipped = tx.apply().unwrap();
+        assert_eq!(skipped, vec![n2.clone()], "계획 후 바뀐 노트는 건너뛴 것으로 보고");
+        assert_eq!(fs::read_to_string(&n1).unwrap(), "[[B]]");
+        assert_eq!(fs::read_to_string(&n2).unwrap(), "[[A]] 둘 (수정됨)");
+        assert!(vault.join("B.md").exists());
+        assert!(journal_dir_is_empty(vault));
+    }
+
+    #[test]
+    fn test_case_only_rename_is_rolled_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::write(vault.join("note.md"), "본문").unwrap();
+        fs::write(vault.join("C.md"), "이미 존재").unwrap();
+        fs::write(vault.join("D.md"), "D").unwrap();
+
+        let tx = RenameTransaction::begin(
+            vault,
+            "test",
+            Vec::new(),
+            vec![
+                (vault.join("note.md"), vault.join("Note.md")),
+                // 대상 충돌로 실패
+                (vault.join("D.md"), vault.join("C.md")),
+            ],
+        )
+        .unwrap();
+        assert!(tx.apply().is_err());
+
+        let names: Vec<String> = fs::read_dir(vault)
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|n| n.ends_with(".md") || n.contains("notology-tmp"))
+            .collect();
+        assert!(names.contains(&"note.md".to_string()), "대소문자만 바뀐 이름도 원래대로: {:?}", names);
+        assert!(!names.contains(&"Note.md".to_string()));
+        assert!(!names.iter().any(|n| n.contains("notology-tmp")), "임시 이름이 남으면 안 됨");
+        assert_eq!(fs::read_to_string(vault.join("note.md")).unwrap(), "본문");
+    }
+}
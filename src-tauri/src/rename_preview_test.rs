@@ -0,0 +1,80 @@
+// Rename/move dry-run - diff·충돌 보고, 파일 시스템 무변경 검증
+
+#[cfg(test)]
+mod rename_preview_tests {
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn s(p: &std::path::Path) -> String {
+        p.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_rename_preview_lists_diffs_without_writing() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::create_dir_all(vault.join("회의_att")).unwrap();
+        fs::write(vault.join("회의.md"), "---\ntype: MTG\n---\n\n회의 내용\n").unwrap();
+        let linking = "첫 줄\n[[회의]] 참고\n중간\n<span data-wiki-link=\"회의\">회의</span>\n";
+        fs::write(vault.join("일지.md"), linking).unwrap();
+
+        let preview = crate::preview_rename_file_with_links(
+            s(&vault.join("회의.md")),
+            "주간회의.md".to_string(),
+            s(vault),
+        )
+        .unwrap();
+
+        assert_eq!(preview.final_path, s(&vault.join("주간회의.md")));
+        assert_eq!(preview.moves.len(), 2, "노트와 _att 폴더");
+        assert!(preview.moves[1].is_dir);
+        assert!(preview.collisions.is_empty());
+
+        assert_eq!(preview.diffs.len(), 1);
+        let diff = &preview.diffs[0].diff;
+        assert!(diff.starts_with("--- a/일지.md\n+++ b/일지.md\n"));
+        assert!(diff.contains("-[[회의]] 참고\n+[[주간회의]] 참고\n"));
+        assert!(diff.contains("+<span data-wiki-link=\"주간회의\">주간회의</span>\n"));
+
+        // 아무것도 쓰지 않음
+        assert_eq!(fs::read_to_string(vault.join("일지.md")).unwrap(), linking);
+        assert!(vault.join("회의.md").exists() && !vault.join("주간회의.md").exists());
+        assert!(!vault.join(".notology").exists(), "미리보기는 저널도 만들지 않아야 함");
+    }
+
+    #[test]
+    fn test_rename_preview_reports_collision_with_resolved_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        fs::write(vault.join("A.md"), "A").unwrap();
+        fs::write(vault.join("B.md"), "B").unwrap();
+
+        let preview = crate::preview_rename_file_with_links(s(&vault.join("A.md")), "B.md".to_string(), s(vault)).unwrap();
+        assert_eq!(preview.collisions.len(), 1);
+        assert_eq!(preview.collisions[0].resolved_path, s(&vault.join("B_1.md")));
+
+        // 실제 실행은 같은 충돌로 거부
+        let err = crate::rename_file_with_links(s(&vault.join("A.md")), "B.md".to_string(), s(vault)).unwrap_err();
+        assert_eq!(err, preview.collisions[0].reason);
+    }
+
+    #[test]
+    fn test_move_note_preview_includes_attachments_and_collisions() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let dest = vault.join("보관");
+        fs::create_dir_all(dest.join("보고서_att")).unwrap();
+        fs::create_dir_all(vault.join("보고서_att")).unwrap();
+        fs::write(vault.join("보고서.md"), "본문").unwrap();
+
+        let preview = crate::preview_move_note(s(&vault.join("보고서.md")), s(&dest), s(vault)).unwrap();
+        assert_eq!(preview.moves.len(), 2);
+        assert_eq!(preview.collisions.len(), 1);
+        assert_eq!(preview.collisions[0].path, s(&dest.join("보고서_att")));
+        assert!(vault.join("보고서.md").exists());
+
+        // 충돌이 있으면 실제 이동도 시작하지 않음
+        assert!(crate::move_note(s(&vault.join("보고서.md")), s(&dest)).is_err());
+        assert!(vault.join("보고서.md").exists() && !dest.join("보고서.md").exists());
+    }
+}
@@ -0,0 +1,337 @@
+use serde::{Deserialize, Serialize};
+
+use super::{GraphData, GraphEdge, GraphNode};
+
+/// Supported file formats for exporting the vault graph to external tools (Gephi, Graphviz, ...)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphExportFormat {
+    GraphMl,
+    Gexf,
+    Dot,
+}
+
+impl GraphExportFormat {
+    /// Default file extension for the format (without leading dot)
+    pub fn extension(&self) -> &'static str {
+        match self {
+            GraphExportFormat::GraphMl => "graphml",
+            GraphExportFormat::Gexf => "gexf",
+            GraphExportFormat::Dot => "dot",
+        }
+    }
+}
+
+/// Serialize graph data into the requested format
+pub fn export_graph(data: &GraphData, format: GraphExportFormat) -> String {
+    match format {
+        GraphExportFormat::GraphMl => to_graphml(data),
+        GraphExportFormat::Gexf => to_gexf(data),
+        GraphExportFormat::Dot => to_dot(data),
+    }
+}
+
+/// Node attributes shared by all formats: (key, value, is_boolean)
+fn node_attributes(node: &GraphNode) -> [(&'static str, String, bool); 6] {
+    [
+        ("label", node.label.clone(), false),
+        ("node_type", node.node_type.clone(), false),
+        ("note_type", node.note_type.clone(), false),
+        ("path", node.path.clone(), false),
+        ("is_folder_note", node.is_folder_note.to_string(), true),
+        ("tag_namespace", node.tag_namespace.clone(), false),
+    ]
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters are not allowed in XML 1.0 (except tab/newline/CR)
+            c if (c as u32) < 0x20 && c != '\t' && c != '\n' && c != '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+/// GraphML (yEd, Gephi, NetworkX)
+pub fn to_graphml(data: &GraphData) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" ");
+    out.push_str("xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" ");
+    out.push_str("xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">\n");
+
+    out.push_str("  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"node_type\" for=\"node\" attr.name=\"node_type\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"note_type\" for=\"node\" attr.name=\"note_type\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"path\" for=\"node\" attr.name=\"path\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"is_folder_note\" for=\"node\" attr.name=\"is_folder_note\" attr.type=\"boolean\"/>\n");
+    out.push_str("  <key id=\"tag_namespace\" for=\"node\" attr.name=\"tag_namespace\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"edge_type\" for=\"edge\" attr.name=\"edge_type\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"relation_type\" for=\"edge\" attr.name=\"relation_type\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"strength\" for=\"edge\" attr.name=\"strength\" attr.type=\"double\"/>\n");
+    out.push_str("  <graph id=\"notology\" edgedefault=\"directed\">\n");
+
+    for node in &data.nodes {
+        out.push_str(&format!("    <node id=\"{}\">\n", xml_escape(&node.id)));
+        for (key, value, _) in node_attributes(node) {
+            out.push_str(&format!(
+                "      <data key=\"{}\">{}</data>\n",
+                key,
+                xml_escape(&value)
+            ));
+        }
+        out.push_str("    </node>\n");
+    }
+
+    for (i, edge) in data.edges.iter().enumerate() {
+        out.push_str(&format!(
+            "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
+            i,
+            xml_escape(&edge.source),
+            xml_escape(&edge.target)
+        ));
+        push_graphml_edge_data(&mut out, edge);
+        out.push_str("    </edge>\n");
+    }
+
+    out.push_str("  </graph>\n");
+    out.push_str("</graphml>\n");
+    out
+}
+
+fn push_graphml_edge_data(out: &mut String, edge: &GraphEdge) {
+    out.push_str(&format!(
+        "      <data key=\"edge_type\">{}</data>\n",
+        xml_escape(&edge.edge_type)
+    ));
+    if let Some(ref relation_type) = edge.relation_type {
+        out.push_str(&format!(
+            "      <data key=\"relation_type\">{}</data>\n",
+            xml_escape(relation_type)
+        ));
+    }
+    if let Some(strength) = edge.strength {
+        out.push_str(&format!("      <data key=\"strength\">{}</data>\n", strength));
+    }
+}
+
+/// GEXF 1.3 (Gephi native format)
+pub fn to_gexf(data: &GraphData) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n");
+    out.push_str(&format!(
+        "  <meta lastmodifieddate=\"{}\">\n    <creator>Notology {}</creator>\n  </meta>\n",
+        chrono::Local::now().format("%Y-%m-%d"),
+        env!("CARGO_PKG_VERSION")
+    ));
+    out.push_str("  <graph mode=\"static\" defaultedgetype=\"directed\">\n");
+
+    out.push_str("    <attributes class=\"node\">\n");
+    out.push_str("      <attribute id=\"0\" title=\"node_type\" type=\"string\"/>\n");
+    out.push_str("      <attribute id=\"1\" title=\"note_type\" type=\"string\"/>\n");
+    out.push_str("      <attribute id=\"2\" title=\"path\" type=\"string\"/>\n");
+    out.push_str("      <attribute id=\"3\" title=\"is_folder_note\" type=\"boolean\"/>\n");
+    out.push_str("      <attribute id=\"4\" title=\"tag_namespace\" type=\"string\"/>\n");
+    out.push_str("    </attributes>\n");
+    out.push_str("    <attributes class=\"edge\">\n");
+    out.push_str("      <attribute id=\"0\" title=\"edge_type\" type=\"string\"/>\n");
+    out.push_str("      <attribute id=\"1\" title=\"relation_type\" type=\"string\"/>\n");
+    out.push_str("      <attribute id=\"2\" title=\"strength\" type=\"double\"/>\n");
+    out.push_str("    </attributes>\n");
+
+    out.push_str("    <nodes>\n");
+    for node in &data.nodes {
+        out.push_str(&format!(
+            "      <node id=\"{}\" label=\"{}\">\n        <attvalues>\n",
+            xml_escape(&node.id),
+            xml_escape(&node.label)
+        ));
+        // Skip "label" (index 0) - it is the node's own label attribute in GEXF
+        for (i, (_, value, _)) in node_attributes(node).iter().skip(1).enumerate() {
+            out.push_str(&format!(
+                "          <attvalue for=\"{}\" value=\"{}\"/>\n",
+                i,
+                xml_escape(value)
+            ));
+        }
+        out.push_str("        </attvalues>\n      </node>\n");
+    }
+    out.push_str("    </nodes>\n");
+
+    out.push_str("    <edges>\n");
+    for (i, edge) in data.edges.iter().enumerate() {
+        // Relation edges are labelled with their relation type and weighted by strength
+        let label = edge.relation_type.as_deref().unwrap_or(&edge.edge_type);
+        let weight = edge
+            .strength
+            .map(|s| format!(" weight=\"{}\"", s))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "      <edge id=\"{}\" source=\"{}\" target=\"{}\" label=\"{}\"{}>\n",
+            i,
+            xml_escape(&edge.source),
+            xml_escape(&edge.target),
+            xml_escape(label),
+            weight
+        ));
+        out.push_str("        <attvalues>\n");
+        out.push_str(&format!(
+            "          <attvalue for=\"0\" value=\"{}\"/>\n",
+            xml_escape(&edge.edge_type)
+        ));
+        if let Some(ref relation_type) = edge.relation_type {
+            out.push_str(&format!(
+                "          <attvalue for=\"1\" value=\"{}\"/>\n",
+                xml_escape(relation_type)
+            ));
+        }
+        if let Some(strength) = edge.strength {
+            out.push_str(&format!("          <attvalue for=\"2\" value=\"{}\"/>\n", strength));
+        }
+        out.push_str("        </attvalues>\n");
+        out.push_str("      </edge>\n");
+    }
+    out.push_str("    </edges>\n");
+
+    out.push_str("  </graph>\n");
+    out.push_str("</gexf>\n");
+    out
+}
+
+/// Graphviz DOT
+pub fn to_dot(data: &GraphData) -> String {
+    let mut out = String::new();
+    out.push_str("digraph notology {\n");
+
+    for node in &data.nodes {
+        let attrs: Vec<String> = node_attributes(node)
+            .iter()
+            .map(|(key, value, is_bool)| {
+                if *is_bool {
+                    format!("{}={}", key, value)
+                } else {
+                    format!("{}=\"{}\"", key, dot_escape(value))
+                }
+            })
+            .collect();
+        out.push_str(&format!("  \"{}\" [{}];\n", dot_escape(&node.id), attrs.join(", ")));
+    }
+
+    for edge in &data.edges {
+        let mut attrs = vec![format!("edge_type=\"{}\"", dot_escape(&edge.edge_type))];
+        if let Some(ref relation_type) = edge.relation_type {
+            attrs.push(format!("relation_type=\"{}\"", dot_escape(relation_type)));
+            attrs.push(format!("label=\"{}\"", dot_escape(relation_type)));
+        }
+        if let Some(strength) = edge.strength {
+            attrs.push(format!("strength={}", strength));
+            attrs.push(format!("weight={}", strength));
+        }
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\" [{}];\n",
+            dot_escape(&edge.source),
+            dot_escape(&edge.target),
+            attrs.join(", ")
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_graph() -> GraphData {
+        GraphData {
+            nodes: vec![
+                GraphNode {
+                    id: "/vault/A & B.md".to_string(),
+                    label: "A & B \"quoted\"".to_string(),
+                    node_type: "note".to_string(),
+                    note_type: "MTG".to_string(),
+                    path: "/vault/A & B.md".to_string(),
+                    is_folder_note: false,
+                    tag_namespace: String::new(),
+                },
+                GraphNode {
+                    id: "tag:domain/특허".to_string(),
+                    label: "특허".to_string(),
+                    node_type: "tag".to_string(),
+                    note_type: String::new(),
+                    path: String::new(),
+                    is_folder_note: false,
+                    tag_namespace: "domain".to_string(),
+                },
+            ],
+            edges: vec![
+                GraphEdge {
+                    source: "/vault/A & B.md".to_string(),
+                    target: "tag:domain/특허".to_string(),
+                    edge_type: "tag".to_string(),
+                    relation_type: None,
+                    strength: None,
+                },
+                GraphEdge {
+                    source: "/vault/A & B.md".to_string(),
+                    target: "/vault/C.md".to_string(),
+                    edge_type: "relation".to_string(),
+                    relation_type: Some("refutes".to_string()),
+                    strength: Some(0.8),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_graphml_escapes_and_includes_attributes() {
+        let xml = to_graphml(&sample_graph());
+        assert!(xml.contains("<node id=\"/vault/A &amp; B.md\">"));
+        assert!(xml.contains("<data key=\"label\">A &amp; B &quot;quoted&quot;</data>"));
+        assert!(xml.contains("<data key=\"note_type\">MTG</data>"));
+        assert!(xml.contains("<data key=\"is_folder_note\">false</data>"));
+        assert!(xml.contains("<data key=\"tag_namespace\">domain</data>"));
+        assert!(xml.contains("<data key=\"edge_type\">tag</data>"));
+        assert!(xml.contains("<data key=\"relation_type\">refutes</data>"));
+        assert!(xml.contains("<data key=\"strength\">0.8</data>"));
+    }
+
+    #[test]
+    fn test_gexf_attvalues_match_declared_attributes() {
+        let xml = to_gexf(&sample_graph());
+        assert!(xml.contains("<node id=\"tag:domain/특허\" label=\"특허\">"));
+        // tag_namespace is attribute #4
+        assert!(xml.contains("<attvalue for=\"4\" value=\"domain\"/>"));
+        assert!(xml.contains("label=\"tag\""));
+        assert!(xml.contains("label=\"refutes\" weight=\"0.8\""));
+        assert_eq!(xml.matches("<edge id=").count(), 2);
+    }
+
+    #[test]
+    fn test_dot_quotes_ids_and_labels() {
+        let dot = to_dot(&sample_graph());
+        assert!(dot.starts_with("digraph notology {"));
+        assert!(dot.contains("label=\"A & B \\\"quoted\\\"\""));
+        assert!(dot.contains("is_folder_note=false"));
+        assert!(dot.contains("\"/vault/A & B.md\" -> \"tag:domain/특허\" [edge_type=\"tag\"];"));
+        assert!(dot.contains("relation_type=\"refutes\""));
+        assert!(dot.contains("strength=0.8"));
+    }
+}
@@ -0,0 +1,445 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// A wiki-link whose target could not be resolved to a note or attachment
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BrokenLink {
+    /// Path of the note containing the link
+    pub source: String,
+    /// Link text as written (alias stripped)
+    pub link: String,
+}
+
+/// In-memory bidirectional wiki-link graph maintained alongside the tantivy index.
+///
+/// Outgoing links are stored per note as written; incoming links are keyed by the
+/// normalized link name so backlinks, outgoing links and broken-link checks are
+/// O(degree) instead of a term query (capped) or a full disk walk.
+#[derive(Default)]
+pub struct LinkGraph {
+    /// note path -> link names as written in the note
+    outgoing: HashMap<String, Vec<String>>,
+    /// normalized link name -> notes containing a link with that name
+    incoming: HashMap<String, BTreeSet<String>>,
+    /// lowercase file stem -> note paths
+    by_stem: HashMap<String, BTreeSet<String>>,
+    /// lowercase title -> note paths
+    by_title: HashMap<String, BTreeSet<String>>,
+    /// note path -> title
+    titles: HashMap<String, String>,
+    /// frontmatter id -> note paths (more than one only for duplicate ids)
+    by_id: HashMap<String, BTreeSet<String>>,
+    /// note path -> frontmatter id
+    ids: HashMap<String, String>,
+}
+
+/// Prefix of id links: `[[id:20250101120000]]` resolves by frontmatter `id`, not by name
+pub const ID_LINK_PREFIX: &str = "id:";
+
+/// The id of an id link ("id:20250101120000#Heading" -> "20250101120000")
+pub fn id_link_target(link: &str) -> Option<&str> {
+    let name = link.split('#').next().unwrap_or(link).trim();
+    name.strip_prefix(ID_LINK_PREFIX).map(str::trim).filter(|id| !id.is_empty())
+}
+
+/// Normalize a link name for lookup: strip "#heading", ".md", path prefix and case.
+/// "[[folder/Note#Heading]]" and "[[note]]" both map to "note".
+pub fn normalize_link_name(link: &str) -> String {
+    let name = link.split('#').next().unwrap_or(link).trim();
+    let name = name.trim_end_matches(".md");
+    let name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    name.to_lowercase()
+}
+
+/// Attachment links carry a non-markdown extension (e.g., "[[report.pdf]]")
+pub fn is_attachment_link(link: &str) -> bool {
+    let name = link.split('#').next().unwrap_or(link).trim();
+    match Path::new(name).extension().and_then(|e| e.to_str()) {
+        Some(ext) => {
+            !ext.eq_ignore_ascii_case("md")
+                && !ext.is_empty()
+                && ext.len() <= 5
+                && ext.chars().all(|c| c.is_ascii_alphanumeric())
+        }
+        None => false,
+    }
+}
+
+/// Locate an attachment linked from `source`: the note's `<stem>_att` folder first, then its directory
+pub fn attachment_path(source: &str, link: &str) -> Option<PathBuf> {
+    let name = link.split('#').next().unwrap_or(link).trim();
+    let source_path = Path::new(source);
+    let dir = source_path.parent()?;
+    let stem = source_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    [dir.join(format!("{}_att", stem)).join(name), dir.join(name)]
+        .into_iter()
+        .find(|p| p.exists())
+}
+
+fn file_stem_lower(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Path with separators unified, for matching path-style links ("folder/Note")
+fn unified(path: &str) -> String {
+    path.replace('\\', "/").to_lowercase()
+}
+
+impl LinkGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of notes in the graph
+    pub fn len(&self) -> usize {
+        self.outgoing.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outgoing.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.outgoing.contains_key(path)
+    }
+
+    /// All note paths currently in the graph
+    pub fn paths(&self) -> impl Iterator<Item = &String> {
+        self.outgoing.keys()
+    }
+
+    /// Insert or replace a note (with its frontmatter id, "" if none) and its outgoing links
+    pub fn upsert(&mut self, path: &str, title: &str, id: &str, links: &[String]) {
+        self.remove(path);
+
+        if !id.is_empty() {
+            self.by_id.entry(id.to_string()).or_default().insert(path.to_string());
+            self.ids.insert(path.to_string(), id.to_string());
+        }
+
+        let stem = file_stem_lower(path);
+        if !stem.is_empty() {
+            self.by_stem.entry(stem).or_default().insert(path.to_string());
+        }
+        if !title.is_empty() {
+            self.by_title
+                .entry(title.to_lowercase())
+                .or_default()
+                .insert(path.to_string());
+        }
+        self.titles.insert(path.to_string(), title.to_string());
+
+        for link in links {
+            let key = normalize_link_name(link);
+            if !key.is_empty() {
+                self.incoming.entry(key).or_default().insert(path.to_string());
+            }
+        }
+        self.outgoing.insert(path.to_string(), links.to_vec());
+    }
+
+    /// Remove a note (its outgoing links and name registrations)
+    pub fn remove(&mut self, path: &str) {
+        let Some(links) = self.outgoing.remove(path) else {
+            return;
+        };
+
+        for link in &links {
+            let key = normalize_link_name(link);
+            if let Some(sources) = self.incoming.get_mut(&key) {
+                sources.remove(path);
+                if sources.is_empty() {
+                    self.incoming.remove(&key);
+                }
+            }
+        }
+
+        let stem = file_stem_lower(path);
+        if let Some(paths) = self.by_stem.get_mut(&stem) {
+            paths.remove(path);
+            if paths.is_empty() {
+                self.by_stem.remove(&stem);
+            }
+        }
+        if let Some(id) = self.ids.remove(path) {
+            if let Some(paths) = self.by_id.get_mut(&id) {
+                paths.remove(path);
+                if paths.is_empty() {
+                    self.by_id.remove(&id);
+                }
+            }
+        }
+        if let Some(title) = self.titles.remove(path) {
+            let title_key = title.to_lowercase();
+            if let Some(paths) = self.by_title.get_mut(&title_key) {
+                paths.remove(path);
+                if paths.is_empty() {
+                    self.by_title.remove(&title_key);
+                }
+            }
+        }
+    }
+
+    /// Remove every note under a directory (folder deletion), returning removed paths
+    pub fn remove_under(&mut self, dir: &str) -> Vec<String> {
+        let prefix = unified(dir).trim_end_matches('/').to_string() + "/";
+        let removed: Vec<String> = self
+            .outgoing
+            .keys()
+            .filter(|p| unified(p).starts_with(&prefix))
+            .cloned()
+            .collect();
+        for path in &removed {
+            self.remove(path);
+        }
+        removed
+    }
+
+    pub fn title(&self, path: &str) -> Option<&str> {
+        self.titles.get(path).map(|s| s.as_str())
+    }
+
+    /// Frontmatter id -> note path (the first path when an id is duplicated)
+    pub fn note_ids(&self) -> HashMap<String, String> {
+        self.by_id
+            .iter()
+            .filter_map(|(id, paths)| paths.iter().next().map(|p| (id.clone(), p.clone())))
+            .collect()
+    }
+
+    /// Resolve a link name to a note path.
+    /// File stem wins over title; path-style links ("folder/Note") prefer notes under that folder.
+    /// Id links ("id:20250101120000") resolve by frontmatter id only.
+    pub fn resolve(&self, link: &str) -> Option<String> {
+        if let Some(id) = id_link_target(link) {
+            return self.by_id.get(id).and_then(|paths| paths.iter().next().cloned());
+        }
+        let key = normalize_link_name(link);
+        if key.is_empty() {
+            return None;
+        }
+
+        let name = link.split('#').next().unwrap_or(link).trim().trim_end_matches(".md");
+        let path_hint = if name.contains('/') || name.contains('\\') {
+            Some(format!("/{}.md", unified(name)))
+        } else {
+            None
+        };
+
+        let pick = |candidates: &BTreeSet<String>| -> Option<String> {
+            if let Some(ref hint) = path_hint {
+                if let Some(p) = candidates.iter().find(|p| unified(p).ends_with(hint.as_str())) {
+                    return Some(p.clone());
+                }
+            }
+            candidates.iter().next().cloned()
+        };
+
+        self.by_stem
+            .get(&key)
+            .and_then(pick)
+            .or_else(|| self.by_title.get(&key).and_then(pick))
+    }
+
+    /// Outgoing links of a note as (link as written, resolved target path)
+    pub fn outgoing(&self, path: &str) -> Vec<(String, Option<String>)> {
+        self.outgoing
+            .get(path)
+            .map(|links| {
+                links
+                    .iter()
+                    .map(|link| (link.clone(), self.resolve(link)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Notes linking to `path`, each with the link text used. Exact and uncapped.
+    pub fn backlinks(&self, path: &str) -> Vec<(String, String)> {
+        let mut keys: Vec<String> = vec![file_stem_lower(path)];
+        if let Some(title) = self.titles.get(path) {
+            let title_key = title.to_lowercase();
+            if !keys.contains(&title_key) {
+                keys.push(title_key);
+            }
+        }
+        if let Some(id) = self.ids.get(path) {
+            keys.push(format!("{}{}", ID_LINK_PREFIX, id));
+        }
+
+        let mut results: Vec<(String, String)> = Vec::new();
+        let mut seen: BTreeSet<String> = BTreeSet::new();
+        for key in &keys {
+            let Some(sources) = self.incoming.get(key) else { continue };
+            for source in sources {
+                if source == path || seen.contains(source) {
+                    continue;
+                }
+                // Confirm the link actually resolves here (same-named notes elsewhere)
+                let matching = self.outgoing.get(source).and_then(|links| {
+                    links
+                        .iter()
+                        .find(|l| self.resolve(l).as_deref() == Some(path))
+                        .cloned()
+                });
+                if let Some(link) = matching {
+                    seen.insert(source.clone());
+                    results.push((source.clone(), link));
+                }
+            }
+        }
+
+        results.sort();
+        results
+    }
+
+    /// Notes with a link locating the attachment at `path` (see `attachment_path`), with the link as written
+    pub fn attachment_backlinks(&self, path: &str) -> Vec<(String, String)> {
+        let target = Path::new(path);
+        let key = target
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let Some(sources) = self.incoming.get(&key) else {
+            return Vec::new();
+        };
+        sources
+            .iter()
+            .filter_map(|source| {
+                self.outgoing
+                    .get(source)?
+                    .iter()
+                    .find(|l| is_attachment_link(l) && attachment_path(source, l).as_deref() == Some(target))
+                    .map(|l| (source.clone(), l.clone()))
+            })
+            .collect()
+    }
+
+    /// Broken links for one note, or for the whole vault when `path` is None.
+    /// Attachment links are checked against the note's `_att` folder and its directory on disk.
+    pub fn broken_links(&self, path: Option<&str>) -> Vec<BrokenLink> {
+        let sources: Vec<&String> = match path {
+            Some(p) => self.outgoing.get_key_value(p).map(|(k, _)| vec![k]).unwrap_or_default(),
+            None => self.outgoing.keys().collect(),
+        };
+
+        let mut broken = Vec::new();
+        for source in sources {
+            let Some(links) = self.outgoing.get(source) else { continue };
+            for link in links {
+                let resolved = if is_attachment_link(link) {
+                    attachment_path(source, link).is_some()
+                } else {
+                    self.resolve(link).is_some()
+                };
+                if !resolved {
+                    broken.push(BrokenLink {
+                        source: source.clone(),
+                        link: link.clone(),
+                    });
+                }
+            }
+        }
+
+        broken.sort_by(|a, b| a.source.cmp(&b.source).then_with(|| a.link.cmp(&b.link)));
+        broken.dedup();
+        broken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn links(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_normalize_link_name() {
+        assert_eq!(normalize_link_name("Folder/Note#Heading"), "note");
+        assert_eq!(normalize_link_name("노트.md"), "노트");
+    }
+
+    #[test]
+    fn test_backlinks_follow_upsert_and_remove() {
+        let mut graph = LinkGraph::new();
+        graph.upsert("/v/A.md", "A", "", &links(&["B"]));
+        graph.upsert("/v/B.md", "Bee", "", &[]);
+        graph.upsert("/v/C.md", "C", "", &links(&["bee#섹션"]));
+
+        let backlinks: Vec<String> = graph.backlinks("/v/B.md").into_iter().map(|(s, _)| s).collect();
+        assert_eq!(backlinks, vec!["/v/A.md".to_string(), "/v/C.md".to_string()]);
+
+        // A의 링크 제거 후 역링크 갱신
+        graph.upsert("/v/A.md", "A", "", &[]);
+        assert_eq!(graph.backlinks("/v/B.md").len(), 1);
+
+        graph.remove("/v/C.md");
+        assert!(graph.backlinks("/v/B.md").is_empty());
+    }
+
+    #[test]
+    fn test_path_style_link_prefers_matching_folder() {
+        let mut graph = LinkGraph::new();
+        graph.upsert("/v/x/Note.md", "Note", "", &[]);
+        graph.upsert("/v/y/Note.md", "Note", "", &[]);
+        graph.upsert("/v/A.md", "A", "", &links(&["y/Note"]));
+
+        assert_eq!(graph.resolve("y/Note").as_deref(), Some("/v/y/Note.md"));
+        assert_eq!(graph.backlinks("/v/y/Note.md").len(), 1);
+        assert!(graph.backlinks("/v/x/Note.md").is_empty(), "다른 폴더의 동명 노트는 역링크 아님");
+    }
+
+    #[test]
+    fn test_broken_links() {
+        let mut graph = LinkGraph::new();
+        graph.upsert("/v/A.md", "A", "", &links(&["B", "없음"]));
+        graph.upsert("/v/B.md", "B", "", &[]);
+
+        let broken = graph.broken_links(None);
+        assert_eq!(broken, vec![BrokenLink { source: "/v/A.md".into(), link: "없음".into() }]);
+
+        graph.upsert("/v/없음.md", "없음", "", &[]);
+        assert!(graph.broken_links(Some("/v/A.md")).is_empty());
+    }
+
+    #[test]
+    fn test_many_backlinks_are_not_capped() {
+        let mut graph = LinkGraph::new();
+        graph.upsert("/v/Index.md", "Index", "", &[]);
+        for i in 0..250 {
+            graph.upsert(&format!("/v/n{}.md", i), &format!("n{}", i), "", &links(&["Index"]));
+        }
+        assert_eq!(graph.backlinks("/v/Index.md").len(), 250);
+    }
+
+    #[test]
+    fn test_id_links_follow_the_note_id() {
+        let mut graph = LinkGraph::new();
+        graph.upsert("/v/a/회의.md", "회의", "20250101120000", &[]);
+        graph.upsert("/v/B.md", "B", "", &links(&["id:20250101120000#결정", "id:20000101000000"]));
+        assert_eq!(graph.resolve("id:20250101120000").as_deref(), Some("/v/a/회의.md"));
+        assert_eq!(graph.backlinks("/v/a/회의.md"), vec![("/v/B.md".to_string(), "id:20250101120000#결정".to_string())]);
+
+        // 이름이 바뀌어도 id 링크는 유지
+        graph.remove("/v/a/회의.md");
+        graph.upsert("/v/b/정기 회의.md", "정기 회의", "20250101120000", &[]);
+        assert_eq!(graph.resolve("id:20250101120000").as_deref(), Some("/v/b/정기 회의.md"));
+        let broken: Vec<String> = graph.broken_links(None).into_iter().map(|b| b.link).collect();
+        assert_eq!(broken, vec!["id:20000101000000"]);
+        assert_eq!(graph.note_ids().len(), 1);
+    }
+}
@@ -0,0 +1,302 @@
+use std::ops::Range;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::is_cjk_char;
+use super::parser::escape_html;
+
+/// A plain-text occurrence of a note's title/alias in another note that is not a link
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UnlinkedMention {
+    pub source_path: String,
+    pub source_title: String,
+    pub target_path: String,
+    /// Text as it appears in the source (original casing)
+    pub matched_text: String,
+    /// Byte range in the source file content
+    pub start: usize,
+    pub end: usize,
+    /// 1-based line number
+    pub line: usize,
+    /// The line containing the mention (trimmed)
+    pub context: String,
+}
+
+/// A mention selected by the user for conversion into a wiki-link
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MentionSelection {
+    pub source_path: String,
+    pub start: usize,
+    pub end: usize,
+    pub matched_text: String,
+}
+
+lazy_static::lazy_static! {
+    /// Inline code regions (never rewritten or expanded)
+    static ref CODE_PATTERNS: Vec<Regex> = vec![
+        Regex::new(r"(?is)<pre[^>]*>.*?</pre>").unwrap(),
+        Regex::new(r"(?is)<code[^>]*>.*?</code>").unwrap(),
+        Regex::new(r"`[^`\n]+`").unwrap(),
+    ];
+
+    /// Inline regions that must never be turned into links
+    static ref PROTECTED_PATTERNS: Vec<Regex> = vec![
+        // Existing wiki-links and embeds
+        Regex::new(r"!?\[\[.+?\]\]").unwrap(),
+        // TipTap wiki-link spans (whole element, including display text)
+        Regex::new(r"(?s)<span[^>]*data-wiki-link[^>]*>.*?</span>").unwrap(),
+        // Any other HTML tag (attribute values)
+        Regex::new(r"<[^>\n]+>").unwrap(),
+        // Markdown link/image targets and bare URLs
+        Regex::new(r"\]\([^)\n]*\)").unwrap(),
+        Regex::new(r"https?://[^\s<>)\]]+").unwrap(),
+    ];
+}
+
+/// Byte ranges of frontmatter, fenced code blocks and inline code (sorted, merged)
+pub fn code_ranges(content: &str) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+
+    // Frontmatter
+    let mut body_start = 0;
+    if let Some(rest) = content.strip_prefix("---") {
+        if let Some(end_idx) = rest.find("\n---") {
+            let close = end_idx + 3 + 4;
+            body_start = content[close..].find('\n').map(|i| close + i + 1).unwrap_or(content.len());
+            ranges.push(0..body_start);
+        }
+    }
+
+    // Fenced code blocks (``` or ~~~, closed by the same fence of at least the same length)
+    let mut offset = body_start;
+    let mut open_fence: Option<(char, usize, usize)> = None; // (char, len, start)
+    for line in content[body_start..].split_inclusive('\n') {
+        let trimmed = line.trim_start_matches(' ');
+        let indent = line.len() - trimmed.len();
+        let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~');
+        let fence_len = fence_char.map(|c| trimmed.chars().take_while(|x| *x == c).count()).unwrap_or(0);
+
+        match open_fence {
+            None => {
+                if indent <= 3 && fence_len >= 3 {
+                    open_fence = Some((fence_char.unwrap(), fence_len, offset));
+                }
+            }
+            Some((c, len, start)) => {
+                let rest = trimmed[fence_len.min(trimmed.len())..].trim();
+                if fence_char == Some(c) && fence_len >= len && rest.is_empty() {
+                    ranges.push(start..offset + line.len());
+                    open_fence = None;
+                }
+            }
+        }
+        offset += line.len();
+    }
+    if let Some((_, _, start)) = open_fence {
+        // Unclosed fence runs to the end of the document
+        ranges.push(start..content.len());
+    }
+
+    for re in CODE_PATTERNS.iter() {
+        ranges.extend(re.find_iter(content).map(|m| m.range()));
+    }
+    merge_ranges(ranges)
+}
+
+/// Byte ranges of frontmatter, fenced code blocks, inline code, existing links and HTML markup
+pub fn protected_ranges(content: &str) -> Vec<Range<usize>> {
+    let mut ranges = code_ranges(content);
+    for re in PROTECTED_PATTERNS.iter() {
+        ranges.extend(re.find_iter(content).map(|m| m.range()));
+    }
+    merge_ranges(ranges)
+}
+
+fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|r| r.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for r in ranges {
+        if let Some(last) = merged.last_mut() {
+            if r.start <= last.end {
+                last.end = last.end.max(r.end);
+                continue;
+            }
+        }
+        merged.push(r);
+    }
+    merged
+}
+
+/// Whether `r` intersects any of the sorted, merged `ranges`
+pub fn overlaps(ranges: &[Range<usize>], r: &Range<usize>) -> bool {
+    let idx = ranges.partition_point(|p| p.end <= r.start);
+    ranges.get(idx).is_some_and(|p| p.start < r.end)
+}
+
+/// Word character for Latin-script boundary checks (CJK has no word boundaries)
+fn is_word_char(c: char) -> bool {
+    (c.is_alphanumeric() || c == '_') && !is_cjk_char(c)
+}
+
+/// Find plain-text mentions of any of `names` (case-insensitive) outside protected ranges.
+/// Latin edges of a name must sit on a word boundary ("art" does not match "party");
+/// CJK edges match anywhere, so "가설" matches "가설은" and "새가설".
+/// Longer names win when matches overlap (alias inside title).
+pub fn find_mentions(content: &str, names: &[String]) -> Vec<Range<usize>> {
+    let protected = protected_ranges(content);
+
+    let mut sorted: Vec<&String> = names.iter().filter(|n| !n.trim().is_empty()).collect();
+    sorted.sort_by_key(|n| std::cmp::Reverse(n.chars().count()));
+
+    let mut found: Vec<Range<usize>> = Vec::new();
+    for name in sorted {
+        let Ok(re) = Regex::new(&format!("(?i){}", regex::escape(name))) else { continue };
+        let first = name.chars().next().unwrap_or(' ');
+        let last = name.chars().next_back().unwrap_or(' ');
+
+        for m in re.find_iter(content) {
+            let r = m.range();
+            if is_word_char(first) && content[..r.start].chars().next_back().is_some_and(is_word_char) {
+                continue;
+            }
+            if is_word_char(last) && content[r.end..].chars().next().is_some_and(is_word_char) {
+                continue;
+            }
+            if overlaps(&protected, &r) || found.iter().any(|f| f.start < r.end && r.start < f.end) {
+                continue;
+            }
+            found.push(r);
+        }
+    }
+
+    found.sort_by_key(|r| r.start);
+    found
+}
+
+/// 1-based line number and trimmed line text for a byte offset
+pub fn line_context(content: &str, offset: usize) -> (usize, String) {
+    let line_start = content[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line_end = content[offset..].find('\n').map(|i| offset + i).unwrap_or(content.len());
+    let line_no = content[..offset].matches('\n').count() + 1;
+    let context: String = content[line_start..line_end].trim().chars().take(200).collect();
+    (line_no, context)
+}
+
+/// TipTap wiki-link span (the format WikiLinkPatterns rewrites on rename), attributes and text escaped
+pub fn wiki_link_span(file_name: &str, display_text: &str) -> String {
+    let name = escape_html(file_name);
+    let text = escape_html(display_text);
+    if display_text == file_name {
+        format!(
+            "<span data-wiki-link=\"{}\" class=\"wiki-link-inline wiki-link-node\">{}</span>",
+            name, text
+        )
+    } else {
+        format!(
+            "<span data-wiki-link=\"{}\" data-display-text=\"{}\" class=\"wiki-link-inline wiki-link-node\">{}</span>",
+            name, text, text
+        )
+    }
+}
+
+/// Replace the selected mention ranges with wiki-link spans pointing at `file_name`.
+/// Selections whose text no longer matches (file edited since the scan) or that now fall
+/// inside a link/code region are skipped. Returns the new content and the number applied.
+pub fn apply_mention_links(content: &str, selections: &[MentionSelection], file_name: &str) -> (String, usize) {
+    let protected = protected_ranges(content);
+
+    let mut sorted: Vec<&MentionSelection> = selections.iter().collect();
+    sorted.sort_by_key(|s| std::cmp::Reverse(s.start));
+
+    let mut result = content.to_string();
+    let mut applied = 0;
+    let mut last_start = usize::MAX;
+    for sel in sorted {
+        let r = sel.start..sel.end;
+        let valid = sel.start < sel.end
+            && sel.end <= last_start
+            && content.get(r.clone()) == Some(sel.matched_text.as_str())
+            && !overlaps(&protected, &r);
+        if !valid {
+            log::warn!(
+                "[mentions] Skipping stale mention {:?} at {}..{} in {}",
+                sel.matched_text, sel.start, sel.end, sel.source_path
+            );
+            continue;
+        }
+        result.replace_range(r, &wiki_link_span(file_name, &sel.matched_text));
+        last_start = sel.start;
+        applied += 1;
+    }
+
+    (result, applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn matched<'a>(content: &'a str, ranges: &[Range<usize>]) -> Vec<&'a str> {
+        ranges.iter().map(|r| &content[r.clone()]).collect()
+    }
+
+    #[test]
+    fn test_cjk_mentions_without_word_boundaries() {
+        let content = "새가설은 검증이 필요하다. 가설을 다시 본다.";
+        let found = find_mentions(content, &names(&["가설"]));
+        assert_eq!(matched(content, &found), vec!["가설", "가설"]);
+    }
+
+    #[test]
+    fn test_latin_mentions_respect_word_boundaries() {
+        let content = "Rust is great; rusty tools and trust issues. rust!";
+        let found = find_mentions(content, &names(&["Rust"]));
+        assert_eq!(matched(content, &found), vec!["Rust", "rust"]);
+    }
+
+    #[test]
+    fn test_skips_code_links_and_frontmatter() {
+        let content = "---\ntitle: 가설\n---\n\n```\n가설 in code\n```\n`가설` [[가설]] <span data-wiki-link=\"가설\">가설</span>\n본문의 가설\n";
+        let found = find_mentions(content, &names(&["가설"]));
+        assert_eq!(found.len(), 1, "본문의 일반 텍스트 하나만 매칭되어야 함");
+        assert_eq!(line_context(content, found[0].start).1, "본문의 가설");
+    }
+
+    #[test]
+    fn test_longer_alias_wins_overlap() {
+        let content = "머신러닝 가설과 가설";
+        let found = find_mentions(content, &names(&["가설", "머신러닝 가설"]));
+        assert_eq!(matched(content, &found), vec!["머신러닝 가설", "가설"]);
+    }
+
+    #[test]
+    fn test_apply_mention_links_writes_spans_and_skips_stale() {
+        let content = "가설 그리고 Hypothesis";
+        let selections = vec![
+            MentionSelection { source_path: "a.md".into(), start: 0, end: 6, matched_text: "가설".into() },
+            MentionSelection { source_path: "a.md".into(), start: 17, end: 27, matched_text: "Hypothesis".into() },
+            MentionSelection { source_path: "a.md".into(), start: 7, end: 13, matched_text: "바뀜".into() },
+        ];
+        let (result, applied) = apply_mention_links(content, &selections, "가설");
+
+        assert_eq!(applied, 2);
+        assert!(result.starts_with("<span data-wiki-link=\"가설\" class=\"wiki-link-inline wiki-link-node\">가설</span>"));
+        assert!(result.contains("data-display-text=\"Hypothesis\""));
+        assert_eq!(super::super::parser::extract_wiki_links(&result), vec!["가설", "가설"]);
+    }
+
+    #[test]
+    fn test_wiki_link_span_escapes_title() {
+        let span = wiki_link_span("A \"quoted\" <b> & co", "<b> & co");
+        assert_eq!(
+            span,
+            "<span data-wiki-link=\"A &quot;quoted&quot; &lt;b&gt; &amp; co\" data-display-text=\"&lt;b&gt; &amp; co\" class=\"wiki-link-inline wiki-link-node\">&lt;b&gt; &amp; co</span>"
+        );
+        assert_eq!(super::super::parser::extract_wiki_links(&span), vec!["A \"quoted\" <b> & co"], "링크 이름은 원래 제목으로 인식");
+    }
+}
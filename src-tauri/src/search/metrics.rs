@@ -0,0 +1,145 @@
+//! Per-note metrics computed at index time
+//!
+//! Words are counted CJK-aware: Han and Kana characters count as one word each (those scripts
+//! do not separate words with spaces), while Latin, Hangul and other scripts count
+//! space-separated runs. Incoming links are not stored here; they change with other notes and
+//! come from the link graph at query time.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::link_graph::{is_attachment_link, normalize_link_name};
+
+/// Reading speed for space-separated words
+const WORDS_PER_MINUTE: f64 = 200.0;
+/// Reading speed for Han/Kana characters
+const CJK_CHARS_PER_MINUTE: f64 = 500.0;
+
+lazy_static! {
+    static ref HTML_TAG: Regex = Regex::new(r"<[^>]+>").unwrap();
+    static ref HEADING: Regex = Regex::new(r"^\s{0,3}#{1,6}\s+\S").unwrap();
+    static ref OPEN_TASK: Regex = Regex::new(r"^\s*(?:[-*+]|\d+[.)])\s+\[ \]").unwrap();
+    static ref OPEN_TASK_HTML: Regex = Regex::new(r#"data-type="taskItem"[^>]*data-checked="false""#).unwrap();
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct NoteMetrics {
+    pub word_count: u64,
+    /// Characters excluding whitespace and markup tags
+    pub char_count: u64,
+    /// Whole minutes, at least 1 for a note with any words
+    pub reading_time: u64,
+    pub heading_count: u64,
+    /// Distinct notes linked from this note
+    pub outgoing_link_count: u64,
+    /// Distinct attachments linked or embedded
+    pub attachment_count: u64,
+    /// Unchecked task list items
+    pub open_task_count: u64,
+}
+
+fn is_cjk_ideograph(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF     // Hiragana, Katakana
+        | 0x3400..=0x4DBF   // CJK Extension A
+        | 0x4E00..=0x9FFF   // CJK Unified Ideographs
+        | 0xF900..=0xFAFF   // CJK Compatibility Ideographs
+        | 0x20000..=0x2FA1F // CJK Extensions B-F, Compatibility Supplement
+    )
+}
+
+/// (word count, Han/Kana characters among them)
+fn count_words(text: &str) -> (u64, u64) {
+    let (mut words, mut cjk, mut in_word) = (0u64, 0u64, false);
+    for c in text.chars() {
+        if is_cjk_ideograph(c) {
+            words += 1;
+            cjk += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                words += 1;
+                in_word = true;
+            }
+        } else if c.is_whitespace() || !matches!(c, '\'' | '’' | '-' | '_') {
+            in_word = false;
+        }
+    }
+    (words, cjk)
+}
+
+/// Metrics of a note body (frontmatter removed) and the wiki links it contains
+pub fn compute_metrics(body: &str, wiki_links: &[String]) -> NoteMetrics {
+    let mut heading_count = 0;
+    let mut open_task_count = 0;
+    let mut in_code = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        if HEADING.is_match(line) {
+            heading_count += 1;
+        }
+        if OPEN_TASK.is_match(line) {
+            open_task_count += 1;
+        }
+    }
+    open_task_count += OPEN_TASK_HTML.find_iter(body).count() as u64;
+
+    let text = HTML_TAG.replace_all(body, " ");
+    let (word_count, cjk) = count_words(&text);
+    let char_count = text.chars().filter(|c| !c.is_whitespace()).count() as u64;
+    let minutes = (word_count - cjk) as f64 / WORDS_PER_MINUTE + cjk as f64 / CJK_CHARS_PER_MINUTE;
+    let reading_time = if word_count == 0 { 0 } else { (minutes.ceil() as u64).max(1) };
+
+    let (mut notes, mut attachments) = (Vec::new(), Vec::new());
+    for link in wiki_links {
+        let key = normalize_link_name(link);
+        let list = if is_attachment_link(link) { &mut attachments } else { &mut notes };
+        if !key.is_empty() && !list.contains(&key) {
+            list.push(key);
+        }
+    }
+
+    NoteMetrics {
+        word_count,
+        char_count,
+        reading_time,
+        heading_count,
+        outgoing_link_count: notes.len() as u64,
+        attachment_count: attachments.len() as u64,
+        open_task_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cjk_aware_word_count() {
+        assert_eq!(count_words("hello world"), (2, 0));
+        assert_eq!(count_words("회의 결과 정리"), (3, 0), "한글은 띄어쓰기 단위");
+        assert_eq!(count_words("日本語の文章"), (6, 6), "한자/가나는 글자 단위");
+        assert_eq!(count_words("Rust와 東京 don't"), (4, 2));
+    }
+
+    #[test]
+    fn test_compute_metrics() {
+        let body = "# 제목\n\n본문 한 줄\n\n## 할 일\n- [ ] 열린 일\n- [x] 끝난 일\n```\n# 코드 주석\n- [ ] 코드\n```\n<li data-type=\"taskItem\" data-checked=\"false\">html</li>\n";
+        let links: Vec<String> = ["A", "a#섹션", "B", "보고서.pdf", "보고서.pdf"].iter().map(|s| s.to_string()).collect();
+        let metrics = compute_metrics(body, &links);
+        assert_eq!(metrics.heading_count, 2);
+        assert_eq!(metrics.open_task_count, 2);
+        assert_eq!(metrics.outgoing_link_count, 2);
+        assert_eq!(metrics.attachment_count, 1);
+        assert_eq!(metrics.reading_time, 1);
+        assert_eq!(metrics.word_count, 15, "태그 속성은 세지 않음");
+        assert_eq!(compute_metrics("", &[]), NoteMetrics::default());
+    }
+}
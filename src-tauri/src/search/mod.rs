@@ -1,4 +1,10 @@
+pub mod export;
+pub mod link_graph;
+pub mod mentions;
+pub mod metrics;
 pub mod parser;
+pub mod suggest;
+pub mod transclusion;
 pub mod watcher;
 
 use std::fs;
@@ -16,11 +22,19 @@ use tantivy::tokenizer::*;
 use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument};
 use walkdir::WalkDir;
 
+use link_graph::{BrokenLink, LinkGraph};
+use mentions::UnlinkedMention;
+use metrics::{compute_metrics, NoteMetrics};
+use suggest::{RelatedCandidate, SuggestionEvidence, TagStats};
+use transclusion::{TransclusionResult, Transcluder};
 use parser::*;
 
 /// Current schema version - increment this when index structure changes
 /// v3: Tags now include namespace prefix (e.g., "domain/특허출원")
-const SCHEMA_VERSION: u32 = 3;
+/// v4: Typed relations indexed as "relation_type:target" keys
+/// v5: Frontmatter id stored for `[[id:...]]` links
+/// v6: Note metrics (word count, headings, links, tasks) stored per document
+const SCHEMA_VERSION: u32 = 6;
 
 /// Metadata for version tracking and auto-regeneration
 #[derive(Serialize, Deserialize, Clone)]
@@ -67,8 +81,20 @@ pub struct NoteFilter {
     pub created_before: Option<String>,
     pub modified_after: Option<String>,
     pub modified_before: Option<String>,
+    /// Also accepts any of [`NoteMetadata::METRIC_NAMES`]
     pub sort_by: Option<String>,
     pub sort_order: Option<String>,
+    /// Inclusive ranges on note metrics, e.g. stubs under 50 words or notes with open tasks
+    #[serde(default)]
+    pub metrics: Option<Vec<MetricRange>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MetricRange {
+    /// One of [`NoteMetadata::METRIC_NAMES`]
+    pub metric: String,
+    pub min: Option<u64>,
+    pub max: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -81,6 +107,41 @@ pub struct NoteMetadata {
     pub modified: String,
     pub has_body: bool,
     pub comment_count: usize,
+    #[serde(flatten)]
+    pub metrics: NoteMetrics,
+    /// Distinct notes linking here (from the link graph, not stored in the index).
+    /// Only computed when a metric range or the sort uses it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incoming_link_count: Option<u64>,
+}
+
+impl NoteMetadata {
+    pub const METRIC_NAMES: [&'static str; 8] = [
+        "word_count",
+        "char_count",
+        "reading_time",
+        "heading_count",
+        "outgoing_link_count",
+        "incoming_link_count",
+        "attachment_count",
+        "open_task_count",
+    ];
+
+    /// Metric value by name, as used for `NoteFilter` sorting and ranges
+    pub fn metric(&self, name: &str) -> Option<u64> {
+        let m = &self.metrics;
+        match name {
+            "word_count" => Some(m.word_count),
+            "char_count" => Some(m.char_count),
+            "reading_time" => Some(m.reading_time),
+            "heading_count" => Some(m.heading_count),
+            "outgoing_link_count" => Some(m.outgoing_link_count),
+            "incoming_link_count" => self.incoming_link_count,
+            "attachment_count" => Some(m.attachment_count),
+            "open_task_count" => Some(m.open_task_count),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -114,6 +175,34 @@ pub struct GraphEdge {
     pub source: String,
     pub target: String,
     pub edge_type: String,
+    /// Only set for edge_type "relation" (e.g., "refutes", "derives-from")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relation_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strength: Option<f32>,
+}
+
+/// Direction for typed relation queries
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelationDirection {
+    /// Notes that declare a relation pointing at the given note ("notes that refute X")
+    Incoming,
+    /// Notes the given note declares a relation to ("what X derives from")
+    Outgoing,
+}
+
+/// A note reached through a typed relation query
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RelatedNote {
+    pub path: String,
+    pub title: String,
+    pub relation_type: String,
+    pub strength: Option<f32>,
+    /// 1 = direct relation, 2+ = reached transitively
+    pub depth: usize,
+    /// Path of the note this one was reached from
+    pub via: String,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -122,6 +211,41 @@ pub struct GraphData {
     pub edges: Vec<GraphEdge>,
 }
 
+/// Which neighbor kinds to follow when building a local (per-note) graph
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct LocalGraphFilter {
+    /// Outgoing + incoming wiki-links (default: true)
+    pub include_links: Option<bool>,
+    /// Tag co-membership via tag nodes (default: true)
+    pub include_tags: Option<bool>,
+    /// Folder note parent/children (default: true)
+    pub include_folders: Option<bool>,
+    /// Typed frontmatter relations, both directions (default: true)
+    pub include_relations: Option<bool>,
+    /// Only include notes of these types (the center note is always included)
+    pub note_types: Option<Vec<String>>,
+    /// Node budget (default: 200)
+    pub max_nodes: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalGraphNode {
+    #[serde(flatten)]
+    pub node: GraphNode,
+    /// Hop distance from the center note (0 = center)
+    pub hop: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalGraphData {
+    pub nodes: Vec<LocalGraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// True when the node budget stopped the expansion early
+    pub truncated: bool,
+}
+
 /// CJK-aware tokenizer that handles Korean, Japanese, Chinese characters
 /// by treating each CJK character as an individual token while using
 /// standard word-based tokenization for Latin scripts.
@@ -338,7 +462,10 @@ struct ParsedDocument {
     note_type: String,
     created: String,
     modified: String,
+    note_id: String,
+    metrics: NoteMetrics,
     wiki_links: Vec<String>,
+    relations: Vec<String>,
     frontmatter_raw: String,
 }
 
@@ -357,7 +484,10 @@ pub struct SearchIndex {
     f_created: Field,
     f_modified: Field,
     f_wiki_links: Field,
+    f_relations: Field,
     f_frontmatter_raw: Field,
+    f_note_id: Field,
+    f_metrics: Field,
     // File modification cache for incremental indexing
     file_cache: Arc<RwLock<std::collections::HashMap<String, std::time::SystemTime>>>,
     // Progress tracking
@@ -365,6 +495,10 @@ pub struct SearchIndex {
     // Track if reader needs reload (set after writes, cleared after reload)
     // This avoids unnecessary reader.reload() calls on every query
     needs_reload: AtomicBool,
+    // In-memory bidirectional wiki-link graph (exact, uncapped backlinks)
+    link_graph: RwLock<LinkGraph>,
+    // Vault-wide tag co-occurrence for tag suggestions, updated with the link graph
+    tag_stats: RwLock<TagStats>,
 }
 
 impl SearchIndex {
@@ -611,8 +745,11 @@ impl SearchIndex {
         let f_note_type = schema_builder.add_text_field("note_type", string_options.clone());
         let f_created = schema_builder.add_text_field("created", string_options.clone());
         let f_modified = schema_builder.add_text_field("modified", string_options.clone());
-        let f_wiki_links = schema_builder.add_text_field("wiki_links", string_options);
+        let f_wiki_links = schema_builder.add_text_field("wiki_links", string_options.clone());
+        let f_relations = schema_builder.add_text_field("relations", string_options);
         let f_frontmatter_raw = schema_builder.add_text_field("frontmatter_raw", STORED);
+        let f_note_id = schema_builder.add_text_field("note_id", STRING | STORED);
+        let f_metrics = schema_builder.add_text_field("metrics", STORED);
 
         let schema = schema_builder.build();
 
@@ -790,7 +927,7 @@ impl SearchIndex {
             );
         }
 
-        Ok(SearchIndex {
+        let search_index = SearchIndex {
             index,
             reader,
             writer: Arc::new(Mutex::new(writer)),
@@ -804,11 +941,57 @@ impl SearchIndex {
             f_created,
             f_modified,
             f_wiki_links,
+            f_relations,
             f_frontmatter_raw,
+            f_note_id,
+            f_metrics,
             file_cache: Arc::new(RwLock::new(std::collections::HashMap::new())),
             progress: Arc::new(IndexProgress::default()),
             needs_reload: AtomicBool::new(false),
-        })
+            link_graph: RwLock::new(LinkGraph::new()),
+            tag_stats: RwLock::new(TagStats::default()),
+        };
+
+        // Populate link graph from the existing index (no file reads)
+        if let Err(e) = search_index.rebuild_link_graph() {
+            log::warn!("[SearchIndex] Failed to build link graph (non-fatal): {}", e);
+        }
+
+        Ok(search_index)
+    }
+
+    /// Rebuild the in-memory link graph from stored documents (single index scan)
+    pub fn rebuild_link_graph(&self) -> Result<usize, String> {
+        self.force_reload()?;
+        let searcher = self.reader.searcher();
+        let top_docs = searcher
+            .search(&tantivy::query::AllQuery, &TopDocs::with_limit(1_000_000))
+            .map_err(|e| e.to_string())?;
+
+        let mut graph = LinkGraph::new();
+        let mut stats = TagStats::default();
+        for (_score, doc_address) in top_docs {
+            let doc: TantivyDocument = searcher.doc(doc_address).map_err(|e| e.to_string())?;
+            let path = doc.get_first(self.f_path).and_then(|v| v.as_str()).unwrap_or("");
+            if path.is_empty() {
+                continue;
+            }
+            let title = doc.get_first(self.f_title).and_then(|v| v.as_str()).unwrap_or("");
+            let note_id = doc.get_first(self.f_note_id).and_then(|v| v.as_str()).unwrap_or("");
+            let links: Vec<String> = doc
+                .get_all(self.f_wiki_links)
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect();
+            graph.upsert(path, title, note_id, &links);
+            let tags: Vec<String> = doc.get_all(self.f_tags).filter_map(|v| v.as_str().map(|s| s.to_string())).collect();
+            stats.upsert(path, &tags);
+        }
+
+        let count = graph.len();
+        *self.link_graph.write().map_err(|e| e.to_string())? = graph;
+        *self.tag_stats.write().map_err(|e| e.to_string())? = stats;
+        log::info!("[SearchIndex] Link graph built: {} notes", count);
+        Ok(count)
     }
 
     /// Index a single markdown file with retry logic for NAS/cloud sync environments
@@ -861,7 +1044,9 @@ impl SearchIndex {
         let note_type = extract_note_type(&frontmatter);
         let created = extract_date_field(&frontmatter, "created");
         let modified = extract_date_field(&frontmatter, "modified");
+        let note_id = extract_note_id(&frontmatter);
         let wiki_links = extract_wiki_links(&content);
+        let relations: Vec<String> = extract_relations(&frontmatter).iter().map(|r| r.index_key()).collect();
 
         let path_str = path.to_string_lossy().to_string();
 
@@ -871,6 +1056,7 @@ impl SearchIndex {
         } else {
             body.clone()
         };
+        let metrics = compute_metrics(&searchable_body, &wiki_links);
 
         // Remove existing document for this path (try both original and lowercase variants on Windows)
         let mut writer = self.writer.lock().map_err(|e| e.to_string())?;
@@ -895,6 +1081,8 @@ impl SearchIndex {
         doc.add_text(self.f_created, &created);
         doc.add_text(self.f_modified, &modified);
         doc.add_text(self.f_frontmatter_raw, fm_raw.as_deref().unwrap_or(""));
+        doc.add_text(self.f_note_id, &note_id);
+        doc.add_text(self.f_metrics, serde_json::to_string(&metrics).unwrap_or_default());
 
         for tag in &tags {
             doc.add_text(self.f_tags, tag);
@@ -902,11 +1090,21 @@ impl SearchIndex {
         for link in &wiki_links {
             doc.add_text(self.f_wiki_links, link);
         }
+        for relation in &relations {
+            doc.add_text(self.f_relations, relation);
+        }
 
         writer.add_document(doc).map_err(|e| e.to_string())?;
         writer.commit().map_err(|e| e.to_string())?;
         log::info!("[index_file] Committed to index: {:?}", path);
 
+        if let Ok(mut graph) = self.link_graph.write() {
+            graph.upsert(&path_str, &title, &note_id, &wiki_links);
+        }
+        if let Ok(mut stats) = self.tag_stats.write() {
+            stats.upsert(&path_str, &tags);
+        }
+
         // Drop the writer lock before reloading to prevent blocking
         drop(writer);
 
@@ -953,6 +1151,17 @@ impl SearchIndex {
 
         writer.commit().map_err(|e| e.to_string())?;
 
+        if let Ok(mut graph) = self.link_graph.write() {
+            graph.remove(&path_str);
+            graph.remove(&path_backslash);
+            graph.remove(&path_forward);
+        }
+        if let Ok(mut stats) = self.tag_stats.write() {
+            stats.remove(&path_str);
+            stats.remove(&path_backslash);
+            stats.remove(&path_forward);
+        }
+
         // Force reload to ensure removal is immediately visible
         self.force_reload()?;
         log::debug!("[remove_file] Removed and reader reloaded: {:?}", path);
@@ -993,23 +1202,7 @@ impl SearchIndex {
 
         // Build and add documents (this is fast since parsing is already done)
         for doc_data in &parsed_docs {
-            let mut doc = TantivyDocument::new();
-            doc.add_text(self.f_path, &doc_data.path);
-            doc.add_text(self.f_title, &doc_data.title);
-            doc.add_text(self.f_body, &doc_data.body);
-            doc.add_text(self.f_note_type, &doc_data.note_type);
-            doc.add_text(self.f_created, &doc_data.created);
-            doc.add_text(self.f_modified, &doc_data.modified);
-            doc.add_text(self.f_frontmatter_raw, &doc_data.frontmatter_raw);
-
-            for tag in &doc_data.tags {
-                doc.add_text(self.f_tags, tag);
-            }
-            for link in &doc_data.wiki_links {
-                doc.add_text(self.f_wiki_links, link);
-            }
-
-            if let Err(e) = writer.add_document(doc) {
+            if let Err(e) = writer.add_document(self.build_document(doc_data)) {
                 log::warn!("Failed to add document {}: {}", doc_data.path, e);
             }
         }
@@ -1017,6 +1210,19 @@ impl SearchIndex {
         // Single commit for all changes
         writer.commit().map_err(|e| e.to_string())?;
 
+        if let Ok(mut graph) = self.link_graph.write() {
+            graph.clear();
+            for doc_data in &parsed_docs {
+                graph.upsert(&doc_data.path, &doc_data.title, &doc_data.note_id, &doc_data.wiki_links);
+            }
+        }
+        if let Ok(mut stats) = self.tag_stats.write() {
+            stats.clear();
+            for doc_data in &parsed_docs {
+                stats.upsert(&doc_data.path, &doc_data.tags);
+            }
+        }
+
         // Update file cache for incremental indexing
         let mut cache = self.file_cache.write().map_err(|e| e.to_string())?;
         cache.clear();
@@ -1097,27 +1303,22 @@ impl SearchIndex {
             writer.delete_term(path_term);
 
             // Add new version
-            let mut doc = TantivyDocument::new();
-            doc.add_text(self.f_path, &doc_data.path);
-            doc.add_text(self.f_title, &doc_data.title);
-            doc.add_text(self.f_body, &doc_data.body);
-            doc.add_text(self.f_note_type, &doc_data.note_type);
-            doc.add_text(self.f_created, &doc_data.created);
-            doc.add_text(self.f_modified, &doc_data.modified);
-            doc.add_text(self.f_frontmatter_raw, &doc_data.frontmatter_raw);
+            let _ = writer.add_document(self.build_document(doc_data));
+        }
+
+        writer.commit().map_err(|e| e.to_string())?;
 
-            for tag in &doc_data.tags {
-                doc.add_text(self.f_tags, tag);
+        if let Ok(mut graph) = self.link_graph.write() {
+            for doc_data in &parsed_docs {
+                graph.upsert(&doc_data.path, &doc_data.title, &doc_data.note_id, &doc_data.wiki_links);
             }
-            for link in &doc_data.wiki_links {
-                doc.add_text(self.f_wiki_links, link);
+        }
+        if let Ok(mut stats) = self.tag_stats.write() {
+            for doc_data in &parsed_docs {
+                stats.upsert(&doc_data.path, &doc_data.tags);
             }
-
-            let _ = writer.add_document(doc);
         }
 
-        writer.commit().map_err(|e| e.to_string())?;
-
         // Update cache
         let mut cache = self.file_cache.write().map_err(|e| e.to_string())?;
         for path in &files_to_update {
@@ -1135,6 +1336,79 @@ impl SearchIndex {
         Ok(update_count)
     }
 
+    /// Re-index a set of files with a single commit (bulk edits touching many notes).
+    /// Returns the number of documents written.
+    pub fn index_files(&self, paths: &[PathBuf]) -> Result<usize, String> {
+        if paths.is_empty() {
+            return Ok(0);
+        }
+        let parsed_docs: Vec<ParsedDocument> = paths
+            .par_iter()
+            .filter_map(|path| match Self::parse_file_for_index(path) {
+                Ok(doc) => Some(doc),
+                Err(e) => {
+                    log::warn!("[index_files] Failed to parse {:?}: {}", path, e);
+                    None
+                }
+            })
+            .collect();
+
+        let mut writer = self.writer.lock().map_err(|e| e.to_string())?;
+        for doc_data in &parsed_docs {
+            writer.delete_term(tantivy::Term::from_field_text(self.f_path, &doc_data.path));
+            writer.add_document(self.build_document(doc_data)).map_err(|e| e.to_string())?;
+        }
+        writer.commit().map_err(|e| e.to_string())?;
+        drop(writer);
+
+        if let Ok(mut graph) = self.link_graph.write() {
+            for doc_data in &parsed_docs {
+                graph.upsert(&doc_data.path, &doc_data.title, &doc_data.note_id, &doc_data.wiki_links);
+            }
+        }
+        if let Ok(mut stats) = self.tag_stats.write() {
+            for doc_data in &parsed_docs {
+                stats.upsert(&doc_data.path, &doc_data.tags);
+            }
+        }
+        if let Ok(mut cache) = self.file_cache.write() {
+            for path in paths {
+                if let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) {
+                    cache.insert(path.to_string_lossy().to_string(), modified);
+                }
+            }
+        }
+
+        self.force_reload()?;
+        log::info!("[index_files] Committed {} documents", parsed_docs.len());
+        Ok(parsed_docs.len())
+    }
+
+    /// Tantivy document for a parsed note
+    fn build_document(&self, doc_data: &ParsedDocument) -> TantivyDocument {
+        let mut doc = TantivyDocument::new();
+        doc.add_text(self.f_path, &doc_data.path);
+        doc.add_text(self.f_title, &doc_data.title);
+        doc.add_text(self.f_body, &doc_data.body);
+        doc.add_text(self.f_note_type, &doc_data.note_type);
+        doc.add_text(self.f_created, &doc_data.created);
+        doc.add_text(self.f_modified, &doc_data.modified);
+        doc.add_text(self.f_frontmatter_raw, &doc_data.frontmatter_raw);
+        doc.add_text(self.f_note_id, &doc_data.note_id);
+        doc.add_text(self.f_metrics, serde_json::to_string(&doc_data.metrics).unwrap_or_default());
+
+        for tag in &doc_data.tags {
+            doc.add_text(self.f_tags, tag);
+        }
+        for link in &doc_data.wiki_links {
+            doc.add_text(self.f_wiki_links, link);
+        }
+        for relation in &doc_data.relations {
+            doc.add_text(self.f_relations, relation);
+        }
+        doc
+    }
+
     /// Parallel file collection using walkdir (optimized for 100k+ files)
     fn collect_md_files_parallel(&self) -> Vec<PathBuf> {
         WalkDir::new(&self.vault_path)
@@ -1176,7 +1450,9 @@ impl SearchIndex {
         let note_type = extract_note_type(&frontmatter);
         let created = extract_date_field(&frontmatter, "created");
         let modified = extract_date_field(&frontmatter, "modified");
+        let note_id = extract_note_id(&frontmatter);
         let wiki_links = extract_wiki_links(&content);
+        let relations: Vec<String> = extract_relations(&frontmatter).iter().map(|r| r.index_key()).collect();
 
         let path_str = path.to_string_lossy().to_string();
 
@@ -1186,6 +1462,7 @@ impl SearchIndex {
         } else {
             body
         };
+        let metrics = compute_metrics(&searchable_body, &wiki_links);
 
         Ok(ParsedDocument {
             path: path_str,
@@ -1195,7 +1472,10 @@ impl SearchIndex {
             note_type,
             created,
             modified,
+            note_id,
+            metrics,
             wiki_links,
+            relations,
             frontmatter_raw: fm_raw.unwrap_or_default(),
         })
     }
@@ -1245,7 +1525,9 @@ impl SearchIndex {
         let note_type = extract_note_type(&frontmatter);
         let created = extract_date_field(&frontmatter, "created");
         let modified = extract_date_field(&frontmatter, "modified");
+        let note_id = extract_note_id(&frontmatter);
         let wiki_links = extract_wiki_links(&content);
+        let relations: Vec<String> = extract_relations(&frontmatter).iter().map(|r| r.index_key()).collect();
 
         let path_str = path.to_string_lossy().to_string();
 
@@ -1256,6 +1538,7 @@ impl SearchIndex {
         } else {
             body.clone()
         };
+        let metrics = compute_metrics(&searchable_body, &wiki_links);
 
         // Build document
         let mut doc = TantivyDocument::new();
@@ -1266,6 +1549,8 @@ impl SearchIndex {
         doc.add_text(self.f_created, &created);
         doc.add_text(self.f_modified, &modified);
         doc.add_text(self.f_frontmatter_raw, fm_raw.as_deref().unwrap_or(""));
+        doc.add_text(self.f_note_id, &note_id);
+        doc.add_text(self.f_metrics, serde_json::to_string(&metrics).unwrap_or_default());
 
         for tag in &tags {
             doc.add_text(self.f_tags, tag);
@@ -1273,6 +1558,9 @@ impl SearchIndex {
         for link in &wiki_links {
             doc.add_text(self.f_wiki_links, link);
         }
+        for relation in &relations {
+            doc.add_text(self.f_relations, relation);
+        }
 
         writer.add_document(doc).map_err(|e| e.to_string())?;
 
@@ -1354,6 +1642,10 @@ impl SearchIndex {
 
     /// Query notes by filter criteria (optimized for large vaults with 100k+ notes)
     pub fn query_notes(&self, filter: &NoteFilter) -> Result<Vec<NoteMetadata>, String> {
+        if let Some(range) = filter.metrics.iter().flatten().find(|r| !NoteMetadata::METRIC_NAMES.contains(&r.metric.as_str())) {
+            return Err(format!("Unknown note metric: {}", range.metric));
+        }
+
         // Conditionally reload reader only if index was modified since last reload
         let reloaded = self.reload_if_needed()?;
         let searcher = self.reader.searcher();
@@ -1432,6 +1724,7 @@ impl SearchIndex {
         // Parallel sort for large result sets
         let sort_order = filter.sort_order.as_deref().unwrap_or("desc");
         let ascending = sort_order == "asc";
+        let metric_sort = filter.sort_by.as_deref().is_some_and(|key| NoteMetadata::METRIC_NAMES.contains(&key));
 
         // Helper: secondary sort by modified when primary values are equal
         // This ensures meaningful ordering even when all primary values are identical
@@ -1472,6 +1765,15 @@ impl SearchIndex {
                         } else { cmp }
                     });
                 }
+                Some(metric) if metric_sort => {
+                    results.par_sort_by(|a, b| {
+                        let cmp = a.metric(metric).cmp(&b.metric(metric));
+                        let cmp = if ascending { cmp } else { cmp.reverse() };
+                        if cmp == std::cmp::Ordering::Equal {
+                            b.modified.cmp(&a.modified)
+                        } else { cmp }
+                    });
+                }
                 _ => {
                     results.par_sort_by(|a, b| {
                         let cmp = a.modified.cmp(&b.modified);
@@ -1515,6 +1817,15 @@ impl SearchIndex {
                         } else { cmp }
                     });
                 }
+                Some(metric) if metric_sort => {
+                    results.sort_by(|a, b| {
+                        let cmp = a.metric(metric).cmp(&b.metric(metric));
+                        let cmp = if ascending { cmp } else { cmp.reverse() };
+                        if cmp == std::cmp::Ordering::Equal {
+                            b.modified.cmp(&a.modified)
+                        } else { cmp }
+                    });
+                }
                 _ => {
                     results.sort_by(|a, b| {
                         let cmp = a.modified.cmp(&b.modified);
@@ -1568,6 +1879,20 @@ impl SearchIndex {
         let body = doc.get_first(self.f_body).and_then(|v| v.as_str()).unwrap_or("");
         let has_body = !body.trim().is_empty();
 
+        let metrics: NoteMetrics = doc
+            .get_first(self.f_metrics)
+            .and_then(|v| v.as_str())
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+        let needs_incoming = filter.sort_by.as_deref() == Some("incoming_link_count")
+            || filter.metrics.iter().flatten().any(|r| r.metric == "incoming_link_count");
+        let incoming_link_count = needs_incoming.then(|| {
+            self.link_graph
+                .read()
+                .map(|graph| graph.backlinks(&path).len() as u64)
+                .unwrap_or(0)
+        });
+
         // Count comments from note_att/comments.json
         let comment_count = Self::count_comments(&path);
 
@@ -1580,8 +1905,18 @@ impl SearchIndex {
             modified,
             has_body,
             comment_count,
+            metrics,
+            incoming_link_count,
         };
 
+        // Apply metric ranges
+        for range in filter.metrics.iter().flatten() {
+            let value = metadata.metric(&range.metric)?;
+            if range.min.is_some_and(|min| value < min) || range.max.is_some_and(|max| value > max) {
+                return None;
+            }
+        }
+
         // Use lowercase path as key on Windows for case-insensitive deduplication
         let dedup_key = if cfg!(windows) {
             path.to_lowercase()
@@ -1599,129 +1934,381 @@ impl SearchIndex {
     }
 
     /// Get relationships (incoming/outgoing links) for a file
+    /// Served from the in-memory link graph: exact and uncapped.
     pub fn get_relationships(&self, file_path: &str) -> Result<RelationshipData, String> {
-        let searcher = self.reader.searcher();
+        let graph = self.link_graph.read().map_err(|e| e.to_string())?;
+        let key = Self::graph_key(&graph, file_path);
 
-        // Get outgoing links for this file
-        let path_term = tantivy::Term::from_field_text(self.f_path, file_path);
-        let path_query = TermQuery::new(path_term, IndexRecordOption::Basic);
+        let outgoing_links = graph
+            .outgoing(&key)
+            .into_iter()
+            .filter_map(|(_link, target)| target)
+            .filter(|target| *target != key)
+            .map(|target| LinkInfo {
+                title: graph.title(&target).unwrap_or("").to_string(),
+                path: target,
+                context: String::new(),
+            })
+            .collect();
 
-        let top_docs = searcher
-            .search(&path_query, &TopDocs::with_limit(1))
-            .map_err(|e| e.to_string())?;
+        let incoming_links = graph
+            .backlinks(&key)
+            .into_iter()
+            .map(|(source, link)| LinkInfo {
+                title: graph.title(&source).unwrap_or("").to_string(),
+                path: source,
+                context: format!("[[{}]]", link),
+            })
+            .collect();
 
-        let mut outgoing_links = Vec::new();
-        let mut file_name = String::new();
+        Ok(RelationshipData {
+            outgoing_links,
+            incoming_links,
+        })
+    }
 
-        if let Some((_score, doc_address)) = top_docs.first() {
-            let doc: TantivyDocument = searcher.doc(*doc_address).map_err(|e| e.to_string())?;
+    /// Broken wiki-links for one note, or vault-wide when `file_path` is None
+    pub fn get_broken_links(&self, file_path: Option<&str>) -> Result<Vec<BrokenLink>, String> {
+        let graph = self.link_graph.read().map_err(|e| e.to_string())?;
+        let key = file_path.map(|p| Self::graph_key(&graph, p));
+        Ok(graph.broken_links(key.as_deref()))
+    }
 
-            // Get the file name for incoming link search
-            let path = doc.get_first(self.f_path).and_then(|v| v.as_str()).unwrap_or("");
-            file_name = Path::new(path)
-                .file_stem()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_default();
+    /// Resolve a note name (stem, title or "folder/Note") to an indexed note path
+    pub fn resolve_note(&self, name: &str) -> Option<String> {
+        self.link_graph.read().ok()?.resolve(name)
+    }
 
-            // Collect outgoing wiki links
-            let links: Vec<String> = doc
-                .get_all(self.f_wiki_links)
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                .collect();
+    /// Frontmatter id -> note path for every indexed note with an id
+    pub fn note_ids(&self) -> Result<std::collections::HashMap<String, String>, String> {
+        Ok(self.link_graph.read().map_err(|e| e.to_string())?.note_ids())
+    }
 
-            // Resolve each outgoing link
-            for link_name in &links {
-                if let Some(link_info) = self.resolve_link(&searcher, link_name)? {
-                    outgoing_links.push(link_info);
-                }
+    /// Find notes that mention the target's title, file name or aliases in plain text without linking to it.
+    /// Candidates come from a phrase query on the body field (CJK-aware tokenizer), then each candidate
+    /// file is scanned so offsets refer to the file on disk.
+    pub fn find_unlinked_mentions(&self, target_path: &str) -> Result<Vec<UnlinkedMention>, String> {
+        self.reload_if_needed()?;
+        let searcher = self.reader.searcher();
+
+        let target_doc = self.find_doc_by_path(&searcher, target_path)?
+            .ok_or_else(|| format!("Note not found in index: {}", target_path))?;
+        let title = target_doc.get_first(self.f_title).and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let stem = Path::new(target_path).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let fm_raw = target_doc.get_first(self.f_frontmatter_raw).and_then(|v| v.as_str()).unwrap_or("");
+
+        let mut names: Vec<String> = Vec::new();
+        for name in [title, stem].into_iter().chain(extract_aliases(&parse_frontmatter(fm_raw))) {
+            // Single characters are too noisy (especially CJK)
+            if name.chars().count() >= 2 && !names.iter().any(|n| n.to_lowercase() == name.to_lowercase()) {
+                names.push(name);
             }
         }
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        // Get incoming links (files that link to this file)
-        let incoming_links = if !file_name.is_empty() {
-            self.find_incoming_links(&searcher, &file_name, file_path)?
-        } else {
-            Vec::new()
+        let linked: std::collections::HashSet<String> = {
+            let graph = self.link_graph.read().map_err(|e| e.to_string())?;
+            graph.backlinks(target_path).into_iter().map(|(source, _)| source).collect()
         };
 
-        Ok(RelationshipData {
-            outgoing_links,
-            incoming_links,
-        })
+        let query_parser = QueryParser::for_index(&self.index, vec![self.f_body]);
+        let mut candidates: Vec<(String, String)> = Vec::new();
+        let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+        for name in &names {
+            let Ok(query) = query_parser.parse_query(&format!("\"{}\"", name.replace('"', " "))) else {
+                continue;
+            };
+            let top_docs = searcher
+                .search(&query, &TopDocs::with_limit(10_000))
+                .map_err(|e| e.to_string())?;
+            for (_score, doc_address) in top_docs {
+                let doc: TantivyDocument = searcher.doc(doc_address).map_err(|e| e.to_string())?;
+                let path = doc.get_first(self.f_path).and_then(|v| v.as_str()).unwrap_or("").to_string();
+                let note_type = doc.get_first(self.f_note_type).and_then(|v| v.as_str()).unwrap_or("");
+                // Canvas (SKETCH) bodies are JSON; mentions there cannot be linked inline
+                if path == target_path || linked.contains(&path) || note_type.eq_ignore_ascii_case("SKETCH") {
+                    continue;
+                }
+                if seen.insert(path.clone()) {
+                    let source_title = doc.get_first(self.f_title).and_then(|v| v.as_str()).unwrap_or("").to_string();
+                    candidates.push((path, source_title));
+                }
+            }
+        }
+
+        let mut results: Vec<UnlinkedMention> = candidates
+            .par_iter()
+            .flat_map(|(path, source_title)| {
+                let Ok(content) = fs::read_to_string(path) else { return Vec::new() };
+                mentions::find_mentions(&content, &names)
+                    .into_iter()
+                    .map(|r| {
+                        let (line, context) = mentions::line_context(&content, r.start);
+                        UnlinkedMention {
+                            source_path: path.clone(),
+                            source_title: source_title.clone(),
+                            target_path: target_path.to_string(),
+                            matched_text: content[r.clone()].to_string(),
+                            start: r.start,
+                            end: r.end,
+                            line,
+                            context,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        results.sort_by(|a, b| a.source_path.cmp(&b.source_path).then(a.start.cmp(&b.start)));
+        Ok(results)
     }
 
-    fn resolve_link(
-        &self,
-        searcher: &tantivy::Searcher,
-        link_name: &str,
-    ) -> Result<Option<LinkInfo>, String> {
-        // Search for a file whose title matches the link name
-        let query_parser = QueryParser::for_index(&self.index, vec![self.f_title]);
-        let query = query_parser
-            .parse_query(&format!("\"{}\"", link_name))
-            .map_err(|e| e.to_string())?;
+    /// Evidence for a note's tag and relation suggestions. Related-note candidates come from a
+    /// query on the note's most distinctive body terms, from notes linking to the same targets or
+    /// directly to/from it, and from notes sharing two or more tags; notes it already declares a
+    /// relation to are left out. Tag co-occurrence comes from the vault-wide `TagStats` kept with the
+    /// link graph, so no full index scan is needed.
+    pub fn suggestion_evidence(&self, path: &str, limit: usize) -> Result<SuggestionEvidence, String> {
+        const TERM_CANDIDATES: usize = 50;
+        const TAG_CANDIDATES: usize = 3;
 
-        let top_docs = searcher
-            .search(&query, &TopDocs::with_limit(5))
-            .map_err(|e| e.to_string())?;
+        self.reload_if_needed()?;
+        let searcher = self.reader.searcher();
+        let doc = self.find_doc_by_path(&searcher, path)?
+            .ok_or_else(|| format!("Note not found in index: {}", path))?;
+        let note_type = doc.get_first(self.f_note_type).and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let tags: Vec<String> = doc.get_all(self.f_tags).filter_map(|v| v.as_str()).map(|t| t.to_string()).collect();
+        let tag_set: std::collections::HashSet<String> = tags.iter().cloned().collect();
 
-        for (_score, doc_address) in top_docs {
-            let doc: TantivyDocument = searcher.doc(doc_address).map_err(|e| e.to_string())?;
-            let path = doc.get_first(self.f_path).and_then(|v| v.as_str()).unwrap_or("").to_string();
-            let title = doc.get_first(self.f_title).and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let body = doc.get_first(self.f_body).and_then(|v| v.as_str()).unwrap_or("");
+        let terms = suggest::distinctive_terms(
+            &suggest::term_counts(body),
+            |term| searcher.doc_freq(&tantivy::Term::from_field_text(self.f_body, term)).unwrap_or(0),
+            searcher.num_docs(),
+        );
+
+        // Candidate documents by path; term matches arrive loaded, the rest are fetched in one query
+        let mut candidates: std::collections::BTreeMap<String, Option<TantivyDocument>> = std::collections::BTreeMap::new();
+        if !terms.is_empty() {
+            let subqueries: Vec<(Occur, Box<dyn tantivy::query::Query>)> = terms
+                .iter()
+                .map(|(term, _)| {
+                    let term = tantivy::Term::from_field_text(self.f_body, term);
+                    (Occur::Should, Box::new(TermQuery::new(term, IndexRecordOption::Basic)) as Box<dyn tantivy::query::Query>)
+                })
+                .collect();
+            let top_docs = searcher
+                .search(&BooleanQuery::new(subqueries), &TopDocs::with_limit(TERM_CANDIDATES))
+                .map_err(|e| e.to_string())?;
+            for (_score, doc_address) in top_docs {
+                let other: TantivyDocument = searcher.doc(doc_address).map_err(|e| e.to_string())?;
+                if let Some(other_path) = other.get_first(self.f_path).and_then(|v| v.as_str()) {
+                    candidates.insert(other_path.to_string(), Some(other));
+                }
+            }
+        }
+
+        let (tag_candidates, sharing_tags) = {
+            let stats = self.tag_stats.read().map_err(|e| e.to_string())?;
+            (stats.candidates(&tags, TAG_CANDIDATES), stats.notes_sharing(&tags, 2))
+        };
+        for other_path in sharing_tags {
+            candidates.entry(other_path).or_insert(None);
+        }
 
-            // Check if the title or file stem matches the link name
-            let stem = Path::new(&path)
-                .file_stem()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_default();
+        let graph = self.link_graph.read().map_err(|e| e.to_string())?;
+        let key = Self::graph_key(&graph, path);
+        let link_targets = |p: &str| -> std::collections::HashSet<String> {
+            graph.outgoing(p).into_iter().filter_map(|(_, target)| target).filter(|t| t != p).collect()
+        };
+        let outgoing = link_targets(&key);
+        let incoming: std::collections::HashSet<String> = graph.backlinks(&key).into_iter().map(|(source, _)| source).collect();
+        let linked = outgoing
+            .iter()
+            .chain(&incoming)
+            .cloned()
+            .chain(outgoing.iter().flat_map(|target| graph.backlinks(target).into_iter().map(|(source, _)| source)));
+        for other_path in linked {
+            candidates.entry(other_path).or_insert(None);
+        }
+        let declared: std::collections::HashSet<String> = self.stored_relations(&doc)
+            .iter()
+            .filter_map(|r| graph.resolve(&r.target))
+            .collect();
+        candidates.retain(|candidate, _| *candidate != key && !declared.contains(candidate));
+
+        let missing: Vec<(Occur, Box<dyn tantivy::query::Query>)> = candidates
+            .iter()
+            .filter(|(_, loaded)| loaded.is_none())
+            .map(|(other_path, _)| {
+                let term = tantivy::Term::from_field_text(self.f_path, other_path);
+                (Occur::Should, Box::new(TermQuery::new(term, IndexRecordOption::Basic)) as Box<dyn tantivy::query::Query>)
+            })
+            .collect();
+        if !missing.is_empty() {
+            let limit = missing.len();
+            let top_docs = searcher
+                .search(&BooleanQuery::new(missing), &TopDocs::with_limit(limit))
+                .map_err(|e| e.to_string())?;
+            for (_score, doc_address) in top_docs {
+                let other: TantivyDocument = searcher.doc(doc_address).map_err(|e| e.to_string())?;
+                if let Some(other_path) = other.get_first(self.f_path).and_then(|v| v.as_str()).map(|p| p.to_string()) {
+                    candidates.insert(other_path, Some(other));
+                }
+            }
+        }
 
-            if stem == link_name || title == link_name {
-                return Ok(Some(LinkInfo {
-                    path,
-                    title,
-                    context: String::new(),
-                }));
+        let mut related: Vec<RelatedCandidate> = Vec::new();
+        for (candidate, other) in candidates {
+            let Some(other) = other else { continue };
+            let other_tags: std::collections::HashSet<String> =
+                other.get_all(self.f_tags).filter_map(|v| v.as_str()).map(|t| t.to_string()).collect();
+
+            let other_body = other.get_first(self.f_body).and_then(|v| v.as_str()).unwrap_or("");
+            let (term_similarity, mut shared_terms) = suggest::term_overlap(&terms, &suggest::term_counts(other_body));
+            shared_terms.truncate(suggest::MAX_SHOWN);
+
+            let other_links = link_targets(&candidate);
+            let mut shared_links: Vec<String> = outgoing
+                .intersection(&other_links)
+                .map(|p| graph.title(p).map(|t| t.to_string()).unwrap_or_else(|| {
+                    Path::new(p).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default()
+                }))
+                .collect();
+            shared_links.sort();
+            shared_links.truncate(suggest::MAX_SHOWN);
+
+            let mut shared_tags: Vec<String> = tag_set.intersection(&other_tags).cloned().collect();
+            shared_tags.sort();
+            shared_tags.truncate(suggest::MAX_SHOWN);
+
+            let links_to = outgoing.contains(&candidate);
+            let linked_from = incoming.contains(&candidate);
+            let score = suggest::related_score(
+                term_similarity,
+                suggest::jaccard(&outgoing, &other_links),
+                suggest::jaccard(&tag_set, &other_tags),
+                links_to || linked_from,
+            );
+            if score < suggest::MIN_RELATED_SCORE {
+                continue;
             }
+            related.push(RelatedCandidate {
+                path: candidate.clone(),
+                title: other.get_first(self.f_title).and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                note_type: other.get_first(self.f_note_type).and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                score,
+                term_similarity,
+                shared_terms,
+                shared_links,
+                links_to,
+                linked_from,
+                shared_tags,
+            });
         }
+        related.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.path.cmp(&b.path)));
+        related.truncate(limit);
 
-        Ok(None)
+        Ok(SuggestionEvidence {
+            path: path.to_string(),
+            note_type,
+            tag_candidates,
+            tags,
+            related,
+        })
     }
 
-    fn find_incoming_links(
-        &self,
-        searcher: &tantivy::Searcher,
-        file_name: &str,
-        exclude_path: &str,
-    ) -> Result<Vec<LinkInfo>, String> {
-        // Search for documents that have this file name in their wiki_links field
-        let term = tantivy::Term::from_field_text(self.f_wiki_links, file_name);
-        let query = TermQuery::new(term, IndexRecordOption::Basic);
+    /// Expand `![[...]]` embeds of a note (nested up to `max_depth`, cycles become placeholders)
+    pub fn resolve_transclusions(&self, path: &str, max_depth: Option<usize>) -> Result<TransclusionResult, String> {
+        let graph = self.link_graph.read().map_err(|e| e.to_string())?;
+        let resolve = |name: &str| graph.resolve(name);
+        Transcluder::new(&resolve, max_depth.unwrap_or(transclusion::DEFAULT_EMBED_DEPTH))
+            .resolve_file(&Self::graph_key(&graph, path))
+    }
 
-        let top_docs = searcher
-            .search(&query, &TopDocs::with_limit(100))
-            .map_err(|e| e.to_string())?;
+    /// Notes whose expanded content depends on `path`, directly or through nested embeds.
+    /// `path` may be a note or an attachment. Candidates come from the link graph's backlinks;
+    /// each is confirmed by reading its embeds.
+    pub fn embed_dependents(&self, path: &str) -> Result<Vec<String>, String> {
+        let graph = self.link_graph.read().map_err(|e| e.to_string())?;
+        let start = Self::graph_key(&graph, path);
+
+        let mut dependents: std::collections::BTreeSet<String> = std::collections::BTreeSet::new();
+        let mut frontier = vec![start.clone()];
+        for _ in 0..transclusion::MAX_EMBED_DEPTH {
+            let mut next = Vec::new();
+            for target in &frontier {
+                // Attachments are not graph nodes: match links against their location on disk
+                let is_note = graph.contains(target) || target.ends_with(".md");
+                let sources = if is_note { graph.backlinks(target) } else { graph.attachment_backlinks(target) };
+                for (source, _) in sources {
+                    if source == start || dependents.contains(&source) {
+                        continue;
+                    }
+                    let Ok(content) = fs::read_to_string(&source) else { continue };
+                    let embeds_target = transclusion::extract_embeds(&content).iter().any(|link| {
+                        let name = link.split('#').next().unwrap_or(link).trim();
+                        if is_note {
+                            graph.resolve(name).as_deref() == Some(target.as_str())
+                        } else {
+                            link_graph::attachment_path(&source, name).as_deref() == Some(Path::new(target))
+                        }
+                    });
+                    if embeds_target {
+                        dependents.insert(source.clone());
+                        next.push(source);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
 
-        let mut results = Vec::new();
-        for (_score, doc_address) in top_docs {
-            let doc: TantivyDocument = searcher.doc(doc_address).map_err(|e| e.to_string())?;
-            let path = doc.get_first(self.f_path).and_then(|v| v.as_str()).unwrap_or("").to_string();
+        Ok(dependents.into_iter().collect())
+    }
 
-            // Skip self-reference
-            if path == exclude_path {
-                continue;
+    /// Remove every indexed note under a deleted directory (single commit)
+    pub fn remove_dir(&self, dir: &Path) -> Result<usize, String> {
+        let removed = {
+            let mut graph = self.link_graph.write().map_err(|e| e.to_string())?;
+            graph.remove_under(&dir.to_string_lossy())
+        };
+        if removed.is_empty() {
+            return Ok(0);
+        }
+        if let Ok(mut stats) = self.tag_stats.write() {
+            for path in &removed {
+                stats.remove(path);
             }
+        }
 
-            let title = doc.get_first(self.f_title).and_then(|v| v.as_str()).unwrap_or("").to_string();
-
-            results.push(LinkInfo {
-                path,
-                title,
-                context: format!("[[{}]]", file_name),
-            });
+        let mut writer = self.writer.lock().map_err(|e| e.to_string())?;
+        for path in &removed {
+            writer.delete_term(tantivy::Term::from_field_text(self.f_path, path));
         }
+        writer.commit().map_err(|e| e.to_string())?;
+        drop(writer);
+        self.force_reload()?;
 
-        Ok(results)
+        log::info!("[remove_dir] Removed {} notes under {:?}", removed.len(), dir);
+        Ok(removed.len())
+    }
+
+    /// Path as stored in the link graph (JS may send "/" while the index uses "\")
+    fn graph_key(graph: &LinkGraph, path: &str) -> String {
+        if graph.contains(path) {
+            return path.to_string();
+        }
+        for variant in [path.replace('/', "\\"), path.replace('\\', "/")] {
+            if graph.contains(&variant) {
+                return variant;
+            }
+        }
+        path.to_string()
     }
 
     /// Get graph data for visualization (all nodes + edges in a single Tantivy scan)
@@ -1745,6 +2332,7 @@ impl SearchIndex {
         struct DocInfo {
             path: String,
             wiki_links: Vec<String>,
+            relations: Vec<RelationRef>,
         }
         let mut doc_infos: Vec<DocInfo> = Vec::new();
 
@@ -1807,30 +2395,19 @@ impl SearchIndex {
                     source: path.clone(),
                     target: format!("tag:{}", tag),
                     edge_type: "tag".to_string(),
+                    relation_type: None,
+                    strength: None,
                 });
             }
 
-            doc_infos.push(DocInfo { path, wiki_links });
+            let relations = self.stored_relations(&doc);
+
+            doc_infos.push(DocInfo { path, wiki_links, relations });
         }
 
         // Add tag nodes with namespace detection
         for tag in &tag_set {
-            let label = tag.split('/').last().unwrap_or(tag).to_string();
-            // Extract namespace: "domain/AI" -> "domain"
-            let namespace = if tag.contains('/') {
-                tag.split('/').next().unwrap_or("").to_string()
-            } else {
-                String::new()
-            };
-            nodes.push(GraphNode {
-                id: format!("tag:{}", tag),
-                label,
-                node_type: "tag".to_string(),
-                note_type: String::new(),
-                path: String::new(),
-                is_folder_note: false,
-                tag_namespace: namespace,
-            });
+            nodes.push(Self::tag_graph_node(tag));
         }
 
         // Resolve wiki_links into edges
@@ -1846,15 +2423,37 @@ impl SearchIndex {
                             source: info.path.clone(),
                             target,
                             edge_type: "wiki_link".to_string(),
+                            relation_type: None,
+                            strength: None,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Resolve typed relations into edges (carry relation type + strength), through the
+        // link graph like `query_relations` so path-style and `id:` targets resolve too
+        let node_id_set: std::collections::HashSet<String> = note_paths.iter().cloned().collect();
+        let graph = self.link_graph.read().map_err(|e| e.to_string())?;
+        for info in &doc_infos {
+            for relation in &info.relations {
+                if let Some(target) = graph.resolve(&relation.target) {
+                    if target != info.path && node_id_set.contains(&target) {
+                        edges.push(GraphEdge {
+                            source: info.path.clone(),
+                            target,
+                            edge_type: "relation".to_string(),
+                            relation_type: Some(relation.relation_type.clone()),
+                            strength: relation.strength,
                         });
                     }
                 }
             }
         }
+        drop(graph);
 
         // Build folder hierarchy edges: folder note -> child notes in same folder
         // Also link sub-folder notes to their parent folder note (grandparent)
-        let node_id_set: std::collections::HashSet<String> = note_paths.iter().cloned().collect();
         for note_path in &note_paths {
             let p = Path::new(note_path);
             let stem = p.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
@@ -1880,6 +2479,8 @@ impl SearchIndex {
                                     source: gp_note_str,
                                     target: note_path.clone(),
                                     edge_type: "contains".to_string(),
+                                    relation_type: None,
+                                    strength: None,
                                 });
                             }
                         }
@@ -1893,6 +2494,8 @@ impl SearchIndex {
                             source: folder_note_str,
                             target: note_path.clone(),
                             edge_type: "contains".to_string(),
+                            relation_type: None,
+                            strength: None,
                         });
                     }
                 }
@@ -1931,6 +2534,8 @@ impl SearchIndex {
                                     source: note_path.clone(),
                                     target: att_path_str,
                                     edge_type: "attachment".to_string(),
+                                    relation_type: None,
+                                    strength: None,
                                 });
                             }
                         }
@@ -1942,6 +2547,465 @@ impl SearchIndex {
         Ok(GraphData { nodes, edges })
     }
 
+    /// Build a graph node for an indexed note document
+    fn note_graph_node(&self, doc: &TantivyDocument) -> GraphNode {
+        let path = doc.get_first(self.f_path).and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let title = doc.get_first(self.f_title).and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let note_type = doc.get_first(self.f_note_type).and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+        let p = Path::new(&path);
+        let stem = p.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let is_folder_note = p.parent()
+            .and_then(|parent| parent.file_name())
+            .map(|folder_name| folder_name.to_string_lossy().to_lowercase() == stem.to_lowercase())
+            .unwrap_or(false);
+        let label = if title.is_empty() { stem } else { title };
+
+        GraphNode {
+            id: path.clone(),
+            label,
+            node_type: "note".to_string(),
+            note_type,
+            path,
+            is_folder_note,
+            tag_namespace: String::new(),
+        }
+    }
+
+    /// Build a graph node for a tag ("domain/AI" -> id "tag:domain/AI", namespace "domain")
+    fn tag_graph_node(tag: &str) -> GraphNode {
+        let label = tag.split('/').next_back().unwrap_or(tag).to_string();
+        // Extract namespace: "domain/AI" -> "domain"
+        let namespace = if tag.contains('/') {
+            tag.split('/').next().unwrap_or("").to_string()
+        } else {
+            String::new()
+        };
+        GraphNode {
+            id: format!("tag:{}", tag),
+            label,
+            node_type: "tag".to_string(),
+            note_type: String::new(),
+            path: String::new(),
+            is_folder_note: false,
+            tag_namespace: namespace,
+        }
+    }
+
+    /// Get the N-hop neighborhood of a note (links, relations, folder parent/children, tag co-membership).
+    /// Uses targeted term queries per expanded node instead of scanning the whole vault,
+    /// and stops adding nodes once the node budget is reached.
+    pub fn get_local_graph(&self, path: &str, depth: usize, filter: &LocalGraphFilter) -> Result<LocalGraphData, String> {
+        const DEFAULT_MAX_NODES: usize = 200;
+        const HARD_MAX_NODES: usize = 5_000;
+        const MAX_DEPTH: usize = 5;
+
+        self.reload_if_needed()?;
+        let searcher = self.reader.searcher();
+
+        let root_doc = self.find_doc_by_path(&searcher, path)?
+            .ok_or_else(|| format!("Note not found in index: {}", path))?;
+
+        let depth = depth.min(MAX_DEPTH);
+        let budget = filter.max_nodes.unwrap_or(DEFAULT_MAX_NODES).clamp(1, HARD_MAX_NODES);
+        let allowed_types: Option<Vec<String>> = filter.note_types.as_ref()
+            .map(|types| types.iter().map(|t| t.to_uppercase()).collect());
+
+        let mut nodes: Vec<LocalGraphNode> = vec![LocalGraphNode { node: self.note_graph_node(&root_doc), hop: 0 }];
+        let mut node_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
+        node_ids.insert(path.to_string());
+        let mut edges: Vec<GraphEdge> = Vec::new();
+        let mut edge_keys: std::collections::HashSet<(String, String, String)> = std::collections::HashSet::new();
+        let mut truncated = false;
+
+        let mut frontier: Vec<String> = vec![path.to_string()];
+        for hop in 1..=depth {
+            let mut next_frontier = Vec::new();
+
+            for current in &frontier {
+                for (neighbor, edge) in self.local_neighbors(&searcher, current, filter, budget)? {
+                    if !node_ids.contains(&neighbor.id) {
+                        if neighbor.node_type == "note" {
+                            if let Some(ref types) = allowed_types {
+                                if !types.contains(&neighbor.note_type.to_uppercase()) {
+                                    continue;
+                                }
+                            }
+                        }
+                        if nodes.len() >= budget {
+                            truncated = true;
+                            continue;
+                        }
+                        node_ids.insert(neighbor.id.clone());
+                        next_frontier.push(neighbor.id.clone());
+                        nodes.push(LocalGraphNode { node: neighbor, hop });
+                    }
+
+                    let key = (edge.source.clone(), edge.target.clone(), edge.edge_type.clone());
+                    if edge_keys.insert(key) {
+                        edges.push(edge);
+                    }
+                }
+            }
+
+            if next_frontier.is_empty() {
+                break;
+            }
+            frontier = next_frontier;
+        }
+
+        Ok(LocalGraphData { nodes, edges, truncated })
+    }
+
+    /// Direct neighbors of a local graph node (note path or "tag:..." id) with the connecting edge
+    fn local_neighbors(
+        &self,
+        searcher: &tantivy::Searcher,
+        id: &str,
+        filter: &LocalGraphFilter,
+        budget: usize,
+    ) -> Result<Vec<(GraphNode, GraphEdge)>, String> {
+        let edge = |source: &str, target: &str, edge_type: &str| GraphEdge {
+            source: source.to_string(),
+            target: target.to_string(),
+            edge_type: edge_type.to_string(),
+            relation_type: None,
+            strength: None,
+        };
+
+        let mut neighbors = Vec::new();
+
+        // Tag node: expand to member notes (tag co-membership)
+        if let Some(tag) = id.strip_prefix("tag:") {
+            let term = tantivy::Term::from_field_text(self.f_tags, tag);
+            let query = TermQuery::new(term, IndexRecordOption::Basic);
+            let top_docs = searcher
+                .search(&query, &TopDocs::with_limit(budget))
+                .map_err(|e| e.to_string())?;
+            for (_score, doc_address) in top_docs {
+                let doc: TantivyDocument = searcher.doc(doc_address).map_err(|e| e.to_string())?;
+                let node = self.note_graph_node(&doc);
+                let e = edge(&node.id, id, "tag");
+                neighbors.push((node, e));
+            }
+            return Ok(neighbors);
+        }
+
+        let Some(doc) = self.find_doc_by_path(searcher, id)? else {
+            return Ok(neighbors);
+        };
+
+        if filter.include_links.unwrap_or(true) {
+            let (outgoing, incoming) = {
+                let graph = self.link_graph.read().map_err(|e| e.to_string())?;
+                let outgoing: Vec<String> = graph.outgoing(id).into_iter().filter_map(|(_, target)| target).collect();
+                let incoming: Vec<String> = graph.backlinks(id).into_iter().map(|(source, _)| source).collect();
+                (outgoing, incoming)
+            };
+
+            for target in outgoing.iter().filter(|t| t.as_str() != id) {
+                if let Some(target_doc) = self.find_doc_by_path(searcher, target)? {
+                    neighbors.push((self.note_graph_node(&target_doc), edge(id, target, "wiki_link")));
+                }
+            }
+            for source in &incoming {
+                if let Some(source_doc) = self.find_doc_by_path(searcher, source)? {
+                    neighbors.push((self.note_graph_node(&source_doc), edge(source, id, "wiki_link")));
+                }
+            }
+        }
+
+        if filter.include_relations.unwrap_or(true) {
+            for (target, _title, relation) in self.outgoing_relations(searcher, id, None)? {
+                if let Some(target_doc) = self.find_doc_by_path(searcher, &target)? {
+                    let mut e = edge(id, &target, "relation");
+                    e.relation_type = Some(relation.relation_type);
+                    e.strength = relation.strength;
+                    neighbors.push((self.note_graph_node(&target_doc), e));
+                }
+            }
+            for (source, _title, relation) in self.incoming_relations(searcher, id, None)? {
+                if let Some(source_doc) = self.find_doc_by_path(searcher, &source)? {
+                    let mut e = edge(&source, id, "relation");
+                    e.relation_type = Some(relation.relation_type);
+                    e.strength = relation.strength;
+                    neighbors.push((self.note_graph_node(&source_doc), e));
+                }
+            }
+        }
+
+        if filter.include_folders.unwrap_or(true) {
+            for (parent, child) in self.folder_neighbors(id) {
+                let other = if parent == id { &child } else { &parent };
+                if let Some(other_doc) = self.find_doc_by_path(searcher, other)? {
+                    neighbors.push((self.note_graph_node(&other_doc), edge(&parent, &child, "contains")));
+                }
+            }
+        }
+
+        if filter.include_tags.unwrap_or(true) {
+            for tag_value in doc.get_all(self.f_tags) {
+                if let Some(tag) = tag_value.as_str() {
+                    let tag_node = Self::tag_graph_node(tag);
+                    let e = edge(id, &tag_node.id, "tag");
+                    neighbors.push((tag_node, e));
+                }
+            }
+        }
+
+        Ok(neighbors)
+    }
+
+    /// Folder hierarchy edges touching a note, as (folder note, child) path pairs.
+    /// Mirrors get_graph_data: folder note -> notes in its folder and sub-folder notes;
+    /// a regular note's parent is its folder's folder note, a folder note's parent is the grandparent folder note.
+    fn folder_neighbors(&self, path: &str) -> Vec<(String, String)> {
+        let folder_note_of = |dir: &Path| -> Option<PathBuf> {
+            let name = dir.file_name()?.to_string_lossy().to_string();
+            let candidate = dir.join(format!("{}.md", name));
+            candidate.is_file().then_some(candidate)
+        };
+
+        let p = Path::new(path);
+        let stem = p.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let Some(dir) = p.parent() else { return Vec::new() };
+        let folder_name = dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let is_folder_note = !folder_name.is_empty() && folder_name == stem;
+
+        let mut pairs = Vec::new();
+
+        // Parent
+        let parent_dir = if is_folder_note { dir.parent() } else { Some(dir) };
+        if let Some(parent_note) = parent_dir.filter(|d| d.starts_with(&self.vault_path)).and_then(folder_note_of) {
+            let parent_str = parent_note.to_string_lossy().to_string();
+            if parent_str != path {
+                pairs.push((parent_str, path.to_string()));
+            }
+        }
+
+        // Children (only folder notes have children)
+        if is_folder_note {
+            if let Ok(entries) = fs::read_dir(dir) {
+                for entry in entries.flatten() {
+                    let entry_path = entry.path();
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if name.starts_with('.') || name.ends_with("_att") {
+                        continue;
+                    }
+                    let child = if entry_path.is_dir() {
+                        folder_note_of(&entry_path)
+                    } else if name.ends_with(".md") && entry_path != p {
+                        Some(entry_path)
+                    } else {
+                        None
+                    };
+                    if let Some(child) = child {
+                        pairs.push((path.to_string(), child.to_string_lossy().to_string()));
+                    }
+                }
+            }
+        }
+
+        pairs
+    }
+
+    /// Parse typed relations of an indexed document from its stored frontmatter
+    /// (only parses YAML when the document actually has relation keys)
+    fn stored_relations(&self, doc: &TantivyDocument) -> Vec<RelationRef> {
+        if doc.get_first(self.f_relations).is_none() {
+            return Vec::new();
+        }
+        let fm_raw = doc.get_first(self.f_frontmatter_raw).and_then(|v| v.as_str()).unwrap_or("");
+        extract_relations(&parse_frontmatter(fm_raw))
+    }
+
+    /// Find the indexed document for an exact path
+    fn find_doc_by_path(&self, searcher: &tantivy::Searcher, path: &str) -> Result<Option<TantivyDocument>, String> {
+        let term = tantivy::Term::from_field_text(self.f_path, path);
+        let query = TermQuery::new(term, IndexRecordOption::Basic);
+        let top_docs = searcher
+            .search(&query, &TopDocs::with_limit(1))
+            .map_err(|e| e.to_string())?;
+        match top_docs.first() {
+            Some((_score, doc_address)) => {
+                let doc: TantivyDocument = searcher.doc(*doc_address).map_err(|e| e.to_string())?;
+                Ok(Some(doc))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Query notes connected by typed relations.
+    /// - Incoming + "refutes": notes that refute the given note
+    /// - Incoming + "derives-from" + transitive: everything derived from the note, at any depth
+    /// - Outgoing: relation targets declared by the note itself
+    ///
+    /// `relation_type` of None matches every relation type.
+    pub fn query_relations(
+        &self,
+        path: &str,
+        relation_type: Option<&str>,
+        direction: RelationDirection,
+        transitive: bool,
+    ) -> Result<Vec<RelatedNote>, String> {
+        const MAX_RELATION_DEPTH: usize = 32;
+
+        self.reload_if_needed()?;
+        let searcher = self.reader.searcher();
+        let relation_type = relation_type.map(normalize_relation_type);
+
+        let mut results: Vec<RelatedNote> = Vec::new();
+        let mut visited: std::collections::HashSet<String> = std::collections::HashSet::new();
+        visited.insert(path.to_string());
+
+        let mut frontier: Vec<String> = vec![path.to_string()];
+        let mut depth = 0;
+
+        while !frontier.is_empty() && depth < MAX_RELATION_DEPTH {
+            depth += 1;
+            let mut next_frontier = Vec::new();
+
+            for current in &frontier {
+                let found = match direction {
+                    RelationDirection::Incoming => {
+                        self.incoming_relations(&searcher, current, relation_type.as_deref())?
+                    }
+                    RelationDirection::Outgoing => {
+                        self.outgoing_relations(&searcher, current, relation_type.as_deref())?
+                    }
+                };
+
+                for (related_path, title, relation) in found {
+                    if !visited.insert(related_path.clone()) {
+                        continue;
+                    }
+                    next_frontier.push(related_path.clone());
+                    results.push(RelatedNote {
+                        path: related_path,
+                        title,
+                        relation_type: relation.relation_type,
+                        strength: relation.strength,
+                        depth,
+                        via: current.clone(),
+                    });
+                }
+            }
+
+            if !transitive {
+                break;
+            }
+            frontier = next_frontier;
+        }
+
+        Ok(results)
+    }
+
+    /// Notes declaring a relation whose target is the note at `path` (by file stem or title)
+    fn incoming_relations(
+        &self,
+        searcher: &tantivy::Searcher,
+        path: &str,
+        relation_type: Option<&str>,
+    ) -> Result<Vec<(String, String, RelationRef)>, String> {
+        let Some(target_doc) = self.find_doc_by_path(searcher, path)? else {
+            return Ok(Vec::new());
+        };
+
+        let stem = Path::new(path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let title = target_doc
+            .get_first(self.f_title)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_lowercase();
+
+        let mut names: Vec<String> = vec![stem];
+        if !title.is_empty() && !names.contains(&title) {
+            names.push(title);
+        }
+        names.retain(|n| !n.is_empty());
+
+        let mut subqueries: Vec<(Occur, Box<dyn tantivy::query::Query>)> = Vec::new();
+        for name in &names {
+            match relation_type {
+                Some(rt) => {
+                    let term = tantivy::Term::from_field_text(self.f_relations, &relation_index_key(rt, name));
+                    subqueries.push((Occur::Should, Box::new(TermQuery::new(term, IndexRecordOption::Basic))));
+                }
+                None => {
+                    let pattern = format!("[^:]+:{}", regex::escape(name));
+                    let query = tantivy::query::RegexQuery::from_pattern(&pattern, self.f_relations)
+                        .map_err(|e| e.to_string())?;
+                    subqueries.push((Occur::Should, Box::new(query)));
+                }
+            }
+        }
+        if subqueries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let top_docs = searcher
+            .search(&BooleanQuery::new(subqueries), &TopDocs::with_limit(100_000))
+            .map_err(|e| e.to_string())?;
+
+        let mut results = Vec::new();
+        for (_score, doc_address) in top_docs {
+            let doc: TantivyDocument = searcher.doc(doc_address).map_err(|e| e.to_string())?;
+            let source_path = doc.get_first(self.f_path).and_then(|v| v.as_str()).unwrap_or("").to_string();
+            if source_path.is_empty() || source_path == path {
+                continue;
+            }
+            let source_title = doc.get_first(self.f_title).and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+            // Pick the strongest matching relation declared by the source note
+            let best = self.stored_relations(&doc)
+                .into_iter()
+                .filter(|r| names.contains(&r.target.to_lowercase()))
+                .filter(|r| relation_type.map_or(true, |rt| r.relation_type == rt))
+                .max_by(|a, b| {
+                    a.strength.unwrap_or(0.0)
+                        .partial_cmp(&b.strength.unwrap_or(0.0))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+            if let Some(relation) = best {
+                results.push((source_path, source_title, relation));
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Relation targets declared by the note at `path`, resolved to indexed notes
+    fn outgoing_relations(
+        &self,
+        searcher: &tantivy::Searcher,
+        path: &str,
+        relation_type: Option<&str>,
+    ) -> Result<Vec<(String, String, RelationRef)>, String> {
+        let Some(doc) = self.find_doc_by_path(searcher, path)? else {
+            return Ok(Vec::new());
+        };
+
+        let mut results = Vec::new();
+        for relation in self.stored_relations(&doc) {
+            if relation_type.is_some_and(|rt| relation.relation_type != rt) {
+                continue;
+            }
+            if let Some(target) = self.resolve_note(&relation.target) {
+                if target != path {
+                    let title = self.link_graph.read().ok()
+                        .and_then(|g| g.title(&target).map(|t| t.to_string()))
+                        .unwrap_or_default();
+                    results.push((target, title, relation));
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
     /// Check if a file name exists in the index (for wiki-link resolution)
     #[allow(dead_code)]
     pub fn file_exists(&self, file_name: &str) -> bool {
@@ -2024,4 +3088,32 @@ impl SearchIndex {
         log::info!("[get_all_tags] Found {} unique tags in vault", tags.len());
         Ok(tags)
     }
+
+    /// Paths of notes carrying any of `tags` (indexed `facet/name` form)
+    pub fn notes_with_tags(&self, tags: &[String]) -> Result<Vec<String>, String> {
+        self.reload_if_needed()?;
+        let searcher = self.reader.searcher();
+
+        let subqueries: Vec<(Occur, Box<dyn tantivy::query::Query>)> = tags
+            .iter()
+            .map(|tag| {
+                let term = tantivy::Term::from_field_text(self.f_tags, tag);
+                (Occur::Should, Box::new(TermQuery::new(term, IndexRecordOption::Basic)) as Box<dyn tantivy::query::Query>)
+            })
+            .collect();
+        let top_docs = searcher
+            .search(&BooleanQuery::new(subqueries), &TopDocs::with_limit(100_000))
+            .map_err(|e| e.to_string())?;
+
+        let mut paths: Vec<String> = top_docs
+            .iter()
+            .filter_map(|(_score, doc_address)| {
+                let doc: TantivyDocument = searcher.doc(*doc_address).ok()?;
+                doc.get_first(self.f_path).and_then(|v| v.as_str()).map(|p| p.to_string())
+            })
+            .collect();
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
 }
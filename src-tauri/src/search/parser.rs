@@ -1,13 +1,13 @@
 use regex::Regex;
 use std::collections::HashMap;
 
-/// Extract wiki-links ([[...]]) from content
+/// Extract wiki-links ([[...]] and <span data-wiki-link="..."> spans) from content
 /// Handles filenames containing ] by using non-greedy matching until ]]
 pub fn extract_wiki_links(content: &str) -> Vec<String> {
     // .+? = non-greedy, ]] 가 먼저 나오면 중단
     // 예: [[[디자인여백플러스] 파일.pdf]] -> [디자인여백플러스] 파일.pdf
     let re = Regex::new(r"\[\[(.+?)\]\]").unwrap();
-    re.captures_iter(content)
+    let mut links: Vec<String> = re.captures_iter(content)
         .map(|cap| {
             let full = cap[1].to_string();
             // Strip alias part: "fileName|displayText" → "fileName"
@@ -18,29 +18,58 @@ pub fn extract_wiki_links(content: &str) -> Vec<String> {
                 full
             }
         })
-        .collect()
+        .collect();
+
+    // TipTap HTML spans: <span data-wiki-link="fileName" ...>displayText</span>
+    let span_re = Regex::new(r#"data-wiki-link="([^"]+)""#).unwrap();
+    links.extend(span_re.captures_iter(content).map(|cap| unescape_html(&cap[1])));
+    links
+}
+
+/// Escape text for an HTML attribute value or element content
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Reverse of `escape_html` (attribute values read back from TipTap spans)
+pub fn unescape_html(s: &str) -> String {
+    s.replace("&quot;", "\"").replace("&lt;", "<").replace("&gt;", ">").replace("&amp;", "&")
 }
 
 /// Split frontmatter and body from markdown content
 pub fn split_frontmatter_body(content: &str) -> (Option<String>, String) {
-    if content.starts_with("---") {
-        if let Some(end_idx) = content[3..].find("\n---") {
-            let frontmatter = content[3..end_idx + 3].trim().to_string();
-            let body_start = end_idx + 3 + 4;
-            let body = if body_start < content.len() {
-                content[body_start..].trim_start_matches('\n').to_string()
-            } else {
-                String::new()
-            };
-            return (Some(frontmatter), body);
+    match split_frontmatter_raw(content) {
+        Some((yaml, rest)) => {
+            let body = rest.get(4..).unwrap_or("").trim_start_matches('\n').to_string();
+            (Some(yaml.trim().to_string()), body)
         }
+        None => (None, content.to_string()),
     }
-    (None, content.to_string())
 }
 
-/// Parse YAML frontmatter into a HashMap
+/// Untrimmed frontmatter text and the rest of the note starting at the closing `\n---`,
+/// for edits that must preserve the file text around the YAML
+pub fn split_frontmatter_raw(content: &str) -> Option<(&str, &str)> {
+    let after_open = content.strip_prefix("---")?;
+    let end_idx = after_open.find("\n---")?;
+    Some(after_open.split_at(end_idx))
+}
+
+/// Parse YAML frontmatter into a HashMap. Invalid YAML yields an empty map (logged);
+/// `lint_vault` reports such notes with their position.
 pub fn parse_frontmatter(raw: &str) -> HashMap<String, serde_yaml::Value> {
-    serde_yaml::from_str(raw).unwrap_or_default()
+    try_parse_frontmatter(raw).unwrap_or_else(|e| {
+        log::warn!("[parser] Invalid frontmatter YAML: {}", e);
+        HashMap::new()
+    })
+}
+
+/// Parse YAML frontmatter into a HashMap, keeping the parse error
+pub fn try_parse_frontmatter(raw: &str) -> Result<HashMap<String, serde_yaml::Value>, serde_yaml::Error> {
+    if raw.trim().is_empty() {
+        return Ok(HashMap::new());
+    }
+    serde_yaml::from_str(raw)
 }
 
 /// Extract title from frontmatter or filename
@@ -116,6 +145,113 @@ pub fn extract_note_type(frontmatter: &HashMap<String, serde_yaml::Value>) -> St
     "NOTE".to_string()
 }
 
+/// Extract the note id (`id`, string or number) from frontmatter, "" if missing
+pub fn extract_note_id(frontmatter: &HashMap<String, serde_yaml::Value>) -> String {
+    match frontmatter.get("id") {
+        Some(serde_yaml::Value::String(s)) => s.trim().to_string(),
+        Some(serde_yaml::Value::Number(n)) => n.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// A typed semantic relation declared in the `relations` frontmatter array
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationRef {
+    /// Kebab-case relation type (e.g., "refutes", "derives-from")
+    pub relation_type: String,
+    /// Target note name (wiki-link brackets, alias and heading stripped)
+    pub target: String,
+    pub strength: Option<f32>,
+}
+
+impl RelationRef {
+    /// Key stored in the index `relations` field: "{relation_type}:{target}"
+    pub fn index_key(&self) -> String {
+        relation_index_key(&self.relation_type, &self.target)
+    }
+}
+
+/// Build the index key for a relation type + target name (target is case-insensitive)
+pub fn relation_index_key(relation_type: &str, target: &str) -> String {
+    format!("{}:{}", relation_type, target.to_lowercase())
+}
+
+/// Normalize a relation type to kebab-case ("DerivesFrom", "derives_from" -> "derives-from")
+pub fn normalize_relation_type(raw: &str) -> String {
+    let mut out = String::new();
+    for (i, ch) in raw.trim().chars().enumerate() {
+        if ch == '_' || ch == ' ' {
+            out.push('-');
+        } else if ch.is_uppercase() {
+            if i > 0 && !out.ends_with('-') {
+                out.push('-');
+            }
+            out.extend(ch.to_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+/// Normalize a relation target: "[[Note#Heading|alias]]" -> "Note"
+pub fn normalize_relation_target(raw: &str) -> String {
+    let mut target = raw.trim();
+    if let Some(inner) = target.strip_prefix("[[").and_then(|t| t.strip_suffix("]]")) {
+        target = inner;
+    }
+    if let Some(idx) = target.find('|') {
+        target = &target[..idx];
+    }
+    if let Some(idx) = target.find('#') {
+        target = &target[..idx];
+    }
+    target.trim().trim_end_matches(".md").to_string()
+}
+
+/// Extract typed relations from frontmatter
+/// Entries without a relation_type or target are skipped
+pub fn extract_relations(frontmatter: &HashMap<String, serde_yaml::Value>) -> Vec<RelationRef> {
+    let Some(seq) = frontmatter.get("relations").and_then(|v| v.as_sequence()) else {
+        return Vec::new();
+    };
+
+    seq.iter()
+        .filter_map(|item| {
+            let map = item.as_mapping()?;
+            let relation_type = map.get("relation_type").and_then(|v| v.as_str())?;
+            let target = map.get("target").and_then(|v| v.as_str())?;
+            let strength = map
+                .get("strength")
+                .and_then(|v| v.as_f64())
+                .map(|s| s.clamp(0.0, 1.0) as f32);
+
+            let relation_type = normalize_relation_type(relation_type);
+            let target = normalize_relation_target(target);
+            if relation_type.is_empty() || target.is_empty() {
+                return None;
+            }
+            Some(RelationRef { relation_type, target, strength })
+        })
+        .collect()
+}
+
+/// Extract note aliases from frontmatter (`aliases: [..]`, `aliases: x` or `alias: x`)
+pub fn extract_aliases(frontmatter: &HashMap<String, serde_yaml::Value>) -> Vec<String> {
+    let mut aliases = Vec::new();
+    for key in ["aliases", "alias"] {
+        match frontmatter.get(key) {
+            Some(serde_yaml::Value::Sequence(seq)) => {
+                aliases.extend(seq.iter().filter_map(|v| v.as_str().map(|s| s.trim().to_string())));
+            }
+            Some(serde_yaml::Value::String(s)) => aliases.push(s.trim().to_string()),
+            _ => {}
+        }
+    }
+    aliases.retain(|a| !a.is_empty());
+    aliases
+}
+
 /// Extract a date field from frontmatter
 pub fn extract_date_field(frontmatter: &HashMap<String, serde_yaml::Value>, field: &str) -> String {
     if let Some(v) = frontmatter.get(field) {
@@ -0,0 +1,316 @@
+//! Evidence for tag and relation suggestions, computed from the index
+//!
+//! Related notes are scored on three signals: body terms (the note's most distinctive terms,
+//! TF-IDF weighted against the index), shared link targets, and shared tags. Tag candidates come
+//! from co-occurrence across the whole vault: a tag is suggested when most notes carrying one of
+//! the note's tags also carry it. Turning evidence into user-facing suggestions is left to
+//! `frontmatter::suggestions`.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tantivy::tokenizer::{TokenStream, Tokenizer};
+
+use super::CjkTokenizer;
+
+/// Distinctive body terms compared between notes
+pub const MAX_TERMS: usize = 20;
+/// Shared terms/links/tags listed per related note
+pub const MAX_SHOWN: usize = 5;
+/// Related notes scoring below this are dropped
+pub const MIN_RELATED_SCORE: f32 = 0.15;
+/// Tag pairs seen together fewer times are noise
+const MIN_TOGETHER: usize = 2;
+/// Share of notes with `with_tag` that must also carry the candidate
+const MIN_COOCCURRENCE: f32 = 0.3;
+
+const TERM_WEIGHT: f32 = 0.5;
+const LINK_WEIGHT: f32 = 0.3;
+const TAG_WEIGHT: f32 = 0.2;
+/// Added when either note already links to the other
+const DIRECT_LINK_BONUS: f32 = 0.2;
+
+lazy_static! {
+    /// Link names are compared as links, not as body terms
+    static ref WIKI_LINK: Regex = Regex::new(r"!?\[\[[^\]]*\]\]").unwrap();
+}
+
+/// Index-side evidence for one note's suggestions
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct SuggestionEvidence {
+    pub path: String,
+    pub note_type: String,
+    /// Indexed `facet/name` tags of the note
+    pub tags: Vec<String>,
+    /// Best first
+    pub related: Vec<RelatedCandidate>,
+    /// Best first
+    pub tag_candidates: Vec<TagCandidate>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RelatedCandidate {
+    pub path: String,
+    pub title: String,
+    pub note_type: String,
+    /// Combined score, 0.0-1.0
+    pub score: f32,
+    /// Share of the note's distinctive term weight also found in this note, 0.0-1.0
+    pub term_similarity: f32,
+    /// Most distinctive shared terms first
+    pub shared_terms: Vec<String>,
+    /// Titles of notes both link to
+    pub shared_links: Vec<String>,
+    /// The note links to this one
+    pub links_to: bool,
+    /// This one links to the note
+    pub linked_from: bool,
+    pub shared_tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TagCandidate {
+    /// Indexed `facet/name` form
+    pub tag: String,
+    /// The note's tag it co-occurs with most strongly
+    pub with_tag: String,
+    /// Notes carrying both tags
+    pub together: usize,
+    /// Notes carrying `with_tag`
+    pub with_count: usize,
+}
+
+impl TagCandidate {
+    pub fn ratio(&self) -> f32 {
+        self.together as f32 / self.with_count.max(1) as f32
+    }
+}
+
+/// Lowercased terms of a body as the index tokenizes it, minus wiki-links and single
+/// characters (lone Han/Hangul syllables and stray letters match almost everything)
+pub fn term_counts(text: &str) -> HashMap<String, usize> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let text = WIKI_LINK.replace_all(text, " ");
+    let mut tokenizer = CjkTokenizer;
+    let mut stream = tokenizer.token_stream(&text);
+    while stream.advance() {
+        let term = &stream.token().text;
+        if term.chars().count() >= 2 && !term.chars().all(|c| c.is_ascii_digit()) {
+            *counts.entry(term.clone()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// The `MAX_TERMS` most distinctive terms, weighted by log term frequency times BM25 idf.
+/// Terms no other note contains (`doc_freq` <= 1) cannot match anything and are skipped.
+pub fn distinctive_terms(counts: &HashMap<String, usize>, doc_freq: impl Fn(&str) -> u64, num_docs: u64) -> Vec<(String, f32)> {
+    let n = num_docs as f32;
+    let mut weighted: Vec<(String, f32)> = counts
+        .iter()
+        .filter_map(|(term, count)| {
+            let df = doc_freq(term);
+            if df <= 1 {
+                return None;
+            }
+            let df = df as f32;
+            let idf = (1.0 + (n - df + 0.5) / (df + 0.5)).ln();
+            Some((term.clone(), (1.0 + (*count as f32).ln()) * idf))
+        })
+        .collect();
+    weighted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.0.cmp(&b.0)));
+    weighted.truncate(MAX_TERMS);
+    weighted
+}
+
+/// Share of `terms`' weight present in `other`, and the shared terms by weight
+pub fn term_overlap(terms: &[(String, f32)], other: &HashMap<String, usize>) -> (f32, Vec<String>) {
+    let total: f32 = terms.iter().map(|(_, w)| w).sum();
+    if total <= 0.0 {
+        return (0.0, Vec::new());
+    }
+    let shared: Vec<&(String, f32)> = terms.iter().filter(|(t, _)| other.contains_key(t)).collect();
+    let weight: f32 = shared.iter().map(|(_, w)| w).sum();
+    (weight / total, shared.into_iter().map(|(t, _)| t.clone()).collect())
+}
+
+pub fn jaccard<T: Eq + std::hash::Hash>(a: &HashSet<T>, b: &HashSet<T>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+/// Weighted combination of the three signals plus the direct-link bonus, capped at 1.0
+pub fn related_score(term_similarity: f32, link_similarity: f32, tag_similarity: f32, direct_link: bool) -> f32 {
+    let bonus = if direct_link { DIRECT_LINK_BONUS } else { 0.0 };
+    (TERM_WEIGHT * term_similarity + LINK_WEIGHT * link_similarity + TAG_WEIGHT * tag_similarity + bonus).min(1.0)
+}
+
+/// Vault-wide tag and tag-pair counts, kept up to date alongside the link graph
+#[derive(Default)]
+pub struct TagStats {
+    /// note path -> its tags
+    notes: HashMap<String, Vec<String>>,
+    /// tag -> notes carrying it
+    by_tag: HashMap<String, BTreeSet<String>>,
+    pairs: HashMap<String, HashMap<String, usize>>,
+}
+
+impl TagStats {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Insert or replace a note's tags
+    pub fn upsert(&mut self, path: &str, tags: &[String]) {
+        self.remove(path);
+        let tags: Vec<String> = tags.iter().collect::<BTreeSet<_>>().into_iter().cloned().collect();
+        for tag in &tags {
+            self.by_tag.entry(tag.clone()).or_default().insert(path.to_string());
+            for other in tags.iter().filter(|o| *o != tag) {
+                *self.pairs.entry(tag.clone()).or_default().entry(other.clone()).or_insert(0) += 1;
+            }
+        }
+        self.notes.insert(path.to_string(), tags);
+    }
+
+    pub fn remove(&mut self, path: &str) {
+        let Some(tags) = self.notes.remove(path) else { return };
+        for tag in &tags {
+            if let Some(paths) = self.by_tag.get_mut(tag) {
+                paths.remove(path);
+                if paths.is_empty() {
+                    self.by_tag.remove(tag);
+                }
+            }
+            let Some(pairs) = self.pairs.get_mut(tag) else { continue };
+            for other in tags.iter().filter(|o| *o != tag) {
+                if let Some(count) = pairs.get_mut(other) {
+                    *count -= 1;
+                    if *count == 0 {
+                        pairs.remove(other);
+                    }
+                }
+            }
+            if pairs.is_empty() {
+                self.pairs.remove(tag);
+            }
+        }
+    }
+
+    /// Notes carrying at least `min` of `tags`
+    pub fn notes_sharing(&self, tags: &[String], min: usize) -> Vec<String> {
+        let mut shared: HashMap<&String, usize> = HashMap::new();
+        for tag in tags {
+            for path in self.by_tag.get(tag).into_iter().flatten() {
+                *shared.entry(path).or_insert(0) += 1;
+            }
+        }
+        shared.into_iter().filter(|(_, n)| *n >= min).map(|(p, _)| p.clone()).collect()
+    }
+
+    /// Tags not in `current` that co-occur with one of them often enough, strongest first
+    pub fn candidates(&self, current: &[String], limit: usize) -> Vec<TagCandidate> {
+        let mut best: HashMap<&String, TagCandidate> = HashMap::new();
+        for with_tag in current {
+            let with_count = self.by_tag.get(with_tag).map_or(0, |paths| paths.len());
+            let Some(pairs) = self.pairs.get(with_tag) else { continue };
+            for (tag, together) in pairs {
+                if current.contains(tag) || *together < MIN_TOGETHER {
+                    continue;
+                }
+                let candidate = TagCandidate { tag: tag.clone(), with_tag: with_tag.clone(), together: *together, with_count };
+                if candidate.ratio() < MIN_COOCCURRENCE {
+                    continue;
+                }
+                let better = best.get(tag).map_or(true, |b| {
+                    candidate.ratio() > b.ratio() || (candidate.ratio() == b.ratio() && candidate.together > b.together)
+                });
+                if better {
+                    best.insert(tag, candidate);
+                }
+            }
+        }
+
+        let mut candidates: Vec<TagCandidate> = best.into_values().collect();
+        candidates.sort_by(|a, b| {
+            b.ratio()
+                .partial_cmp(&a.ratio())
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.together.cmp(&a.together))
+                .then_with(|| a.tag.cmp(&b.tag))
+        });
+        candidates.truncate(limit);
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_term_weights_prefer_rare_terms() {
+        let counts = term_counts("Rust 소유권 규칙과 rust borrow 검사기. a 1 2026 [[Cargo 문서]]");
+        assert_eq!(counts.get("rust"), Some(&2));
+        assert!(counts.contains_key("소유"), "한글은 바이그램");
+        assert!(!counts.contains_key("a") && !counts.contains_key("2026") && !counts.contains_key("소"));
+        assert!(!counts.contains_key("cargo"), "링크 이름 제외");
+
+        let df = |term: &str| match term {
+            "rust" => 9,
+            "borrow" => 2,
+            "소유" => 1,
+            _ => 5,
+        };
+        let terms = distinctive_terms(&counts, df, 10);
+        assert_eq!(terms[0].0, "borrow");
+        assert!(!terms.iter().any(|(t, _)| t == "소유"), "다른 노트에 없는 단어 제외");
+
+        let other = term_counts("borrow checker");
+        let (similarity, shared) = term_overlap(&terms, &other);
+        assert_eq!(shared, vec!["borrow".to_string()]);
+        assert!(similarity > 0.0 && similarity < 1.0);
+    }
+
+    #[test]
+    fn test_tag_cooccurrence() {
+        let mut stats = TagStats::default();
+        stats.upsert("a.md", &tags(&["domain/ml", "method/실험"]));
+        stats.upsert("b.md", &tags(&["domain/ml", "method/실험", "who/김"]));
+        stats.upsert("c.md", &tags(&["domain/ml", "method/survey"]));
+        stats.upsert("d.md", &tags(&["domain/ml", "who/김"]));
+
+        let candidates = stats.candidates(&tags(&["domain/ml"]), 3);
+        assert_eq!(candidates.len(), 2, "한 번만 함께 쓰인 태그 제외");
+        assert_eq!(candidates[0].tag, "method/실험");
+        assert_eq!((candidates[0].together, candidates[0].with_count), (2, 4));
+        assert!(stats.candidates(&tags(&["domain/ml", "method/실험", "who/김"]), 3).is_empty());
+
+        let mut sharing = stats.notes_sharing(&tags(&["domain/ml", "who/김"]), 2);
+        sharing.sort();
+        assert_eq!(sharing, vec!["b.md".to_string(), "d.md".to_string()]);
+    }
+
+    #[test]
+    fn test_tag_stats_follow_updates() {
+        let mut stats = TagStats::default();
+        stats.upsert("a.md", &tags(&["domain/ml", "method/실험"]));
+        stats.upsert("b.md", &tags(&["domain/ml", "method/실험"]));
+        assert_eq!(stats.candidates(&tags(&["domain/ml"]), 3).len(), 1);
+
+        stats.upsert("b.md", &tags(&["domain/ml"]));
+        assert!(stats.candidates(&tags(&["domain/ml"]), 3).is_empty(), "태그 변경 반영");
+        stats.remove("a.md");
+        stats.remove("b.md");
+        assert!(stats.notes_sharing(&tags(&["domain/ml"]), 1).is_empty(), "삭제 반영");
+    }
+}
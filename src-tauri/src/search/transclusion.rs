@@ -0,0 +1,316 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::link_graph::{attachment_path, is_attachment_link};
+use super::mentions::{code_ranges, overlaps};
+use super::parser::{escape_html, split_frontmatter_body};
+
+/// Default and hard maximum nesting depth for embeds
+pub const DEFAULT_EMBED_DEPTH: usize = 3;
+pub const MAX_EMBED_DEPTH: usize = 10;
+
+lazy_static::lazy_static! {
+    static ref EMBED_RE: Regex = Regex::new(r"!\[\[(.+?)\]\]").unwrap();
+}
+
+/// What an `![[...]]` embed resolved to
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbedKind {
+    /// Whole note body inlined
+    Note,
+    /// Single heading section inlined (`![[Note#Heading]]`)
+    Section,
+    Image,
+    Pdf,
+    Video,
+    Audio,
+    /// Any other attachment
+    File,
+    /// Target note or attachment does not exist
+    Missing,
+    /// Target note exists but the heading does not
+    MissingSection,
+    /// Embedding the target would loop back to a note already being expanded
+    Cycle,
+    /// Nesting deeper than the requested depth
+    DepthLimit,
+}
+
+impl EmbedKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            EmbedKind::Note => "note",
+            EmbedKind::Section => "section",
+            EmbedKind::Image => "image",
+            EmbedKind::Pdf => "pdf",
+            EmbedKind::Video => "video",
+            EmbedKind::Audio => "audio",
+            EmbedKind::File => "file",
+            EmbedKind::Missing => "missing",
+            EmbedKind::MissingSection => "missing_section",
+            EmbedKind::Cycle => "cycle",
+            EmbedKind::DepthLimit => "depth_limit",
+        }
+    }
+
+    fn for_attachment(name: &str) -> Self {
+        let ext = Path::new(name)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match ext.as_str() {
+            "png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" | "bmp" => EmbedKind::Image,
+            "pdf" => EmbedKind::Pdf,
+            "mp4" | "webm" | "mov" | "mkv" => EmbedKind::Video,
+            "mp3" | "wav" | "ogg" | "m4a" | "flac" => EmbedKind::Audio,
+            _ => EmbedKind::File,
+        }
+    }
+}
+
+/// One embed encountered while expanding a note (nested embeds included)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EmbedInfo {
+    /// Note containing the embed
+    pub source_path: String,
+    /// Embed text as written (without `![[ ]]`)
+    pub link: String,
+    pub kind: EmbedKind,
+    pub target_path: Option<String>,
+    pub heading: Option<String>,
+    /// 1 = embedded directly in the requested note
+    pub depth: usize,
+}
+
+/// A note body with its embeds expanded
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TransclusionResult {
+    pub path: String,
+    /// Body (frontmatter stripped) with note embeds inlined and other embeds replaced by placeholders
+    pub content: String,
+    pub embeds: Vec<EmbedInfo>,
+    /// Every note/attachment path the expanded content depends on (sorted, unique)
+    pub dependencies: Vec<String>,
+}
+
+/// Embed targets in a note body as written ("Note#Heading", alias stripped), skipping code
+pub fn extract_embeds(content: &str) -> Vec<String> {
+    let code = code_ranges(content);
+    EMBED_RE
+        .captures_iter(content)
+        .filter(|cap| !overlaps(&code, &cap.get(0).unwrap().range()))
+        .map(|cap| strip_alias(&cap[1]).to_string())
+        .collect()
+}
+
+fn strip_alias(link: &str) -> &str {
+    link.split('|').next().unwrap_or(link).trim()
+}
+
+/// Typed placeholder inserted for embeds that are not inlined
+pub fn embed_placeholder(kind: EmbedKind, link: &str, target: Option<&str>) -> String {
+    format!(
+        "<div class=\"embed-placeholder\" data-embed-kind=\"{}\" data-embed-link=\"{}\" data-embed-target=\"{}\"></div>",
+        kind.as_str(),
+        escape_html(link),
+        escape_html(target.unwrap_or("")),
+    )
+}
+
+/// Markdown section under `heading` (case-insensitive), up to the next heading of the same or higher level
+pub fn extract_section(body: &str, heading: &str) -> Option<String> {
+    let wanted = heading.trim().to_lowercase();
+    let mut level = 0;
+    let mut section: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+        let is_heading = !in_fence
+            && (1..=6).contains(&hashes)
+            && trimmed[hashes..].starts_with(' ');
+
+        if level == 0 {
+            if is_heading && trimmed[hashes..].trim().trim_end_matches('#').trim().to_lowercase() == wanted {
+                level = hashes;
+                section.push(line);
+            }
+        } else if is_heading && hashes <= level {
+            break;
+        } else {
+            section.push(line);
+        }
+    }
+
+    if level == 0 {
+        None
+    } else {
+        Some(section.join("\n").trim_end().to_string())
+    }
+}
+
+/// Expands embeds recursively. `resolve` maps a note name to its path (link graph resolution).
+pub struct Transcluder<'a> {
+    resolve: &'a dyn Fn(&str) -> Option<String>,
+    max_depth: usize,
+    embeds: Vec<EmbedInfo>,
+    dependencies: BTreeSet<String>,
+}
+
+impl<'a> Transcluder<'a> {
+    pub fn new(resolve: &'a dyn Fn(&str) -> Option<String>, max_depth: usize) -> Self {
+        Self {
+            resolve,
+            max_depth: max_depth.clamp(1, MAX_EMBED_DEPTH),
+            embeds: Vec::new(),
+            dependencies: BTreeSet::new(),
+        }
+    }
+
+    /// Read `path` and expand its body
+    pub fn resolve_file(mut self, path: &str) -> Result<TransclusionResult, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let (_, body) = split_frontmatter_body(&content);
+        let mut stack = vec![path.to_string()];
+        let content = self.expand(&body, path, 1, &mut stack);
+
+        Ok(TransclusionResult {
+            path: path.to_string(),
+            content,
+            embeds: self.embeds,
+            dependencies: self.dependencies.into_iter().collect(),
+        })
+    }
+
+    /// Expand embeds in `body` of note `source`; `stack` holds the notes currently being expanded
+    fn expand(&mut self, body: &str, source: &str, depth: usize, stack: &mut Vec<String>) -> String {
+        let code = code_ranges(body);
+        let mut out = String::with_capacity(body.len());
+        let mut last = 0;
+
+        for cap in EMBED_RE.captures_iter(body) {
+            let whole = cap.get(0).unwrap();
+            if overlaps(&code, &whole.range()) {
+                continue;
+            }
+            out.push_str(&body[last..whole.start()]);
+            last = whole.end();
+
+            let link = strip_alias(&cap[1]).to_string();
+            out.push_str(&self.expand_embed(&link, source, depth, stack));
+        }
+        out.push_str(&body[last..]);
+        out
+    }
+
+    fn expand_embed(&mut self, link: &str, source: &str, depth: usize, stack: &mut Vec<String>) -> String {
+        let (name, heading) = match link.split_once('#') {
+            Some((n, h)) => (n.trim(), Some(h.trim().to_string())),
+            None => (link, None),
+        };
+
+        if is_attachment_link(name) {
+            let target = attachment_path(source, name).map(|p| p.to_string_lossy().to_string());
+            let kind = if target.is_some() { EmbedKind::for_attachment(name) } else { EmbedKind::Missing };
+            if let Some(ref t) = target {
+                self.dependencies.insert(t.clone());
+            }
+            return self.placeholder(kind, link, source, target, heading, depth);
+        }
+
+        let Some(target) = (self.resolve)(name) else {
+            return self.placeholder(EmbedKind::Missing, link, source, None, heading, depth);
+        };
+        self.dependencies.insert(target.clone());
+
+        if stack.contains(&target) {
+            return self.placeholder(EmbedKind::Cycle, link, source, Some(target), heading, depth);
+        }
+        if depth > self.max_depth {
+            return self.placeholder(EmbedKind::DepthLimit, link, source, Some(target), heading, depth);
+        }
+
+        let Ok(content) = fs::read_to_string(&target) else {
+            return self.placeholder(EmbedKind::Missing, link, source, Some(target), heading, depth);
+        };
+        let (_, body) = split_frontmatter_body(&content);
+        let (kind, body) = match heading {
+            Some(ref h) => match extract_section(&body, h) {
+                Some(section) => (EmbedKind::Section, section),
+                None => {
+                    return self.placeholder(EmbedKind::MissingSection, link, source, Some(target), heading, depth);
+                }
+            },
+            None => (EmbedKind::Note, body.trim_end().to_string()),
+        };
+
+        self.embeds.push(EmbedInfo {
+            source_path: source.to_string(),
+            link: link.to_string(),
+            kind,
+            target_path: Some(target.clone()),
+            heading,
+            depth,
+        });
+
+        stack.push(target.clone());
+        let expanded = self.expand(&body, &target, depth + 1, stack);
+        stack.pop();
+        expanded
+    }
+
+    fn placeholder(
+        &mut self,
+        kind: EmbedKind,
+        link: &str,
+        source: &str,
+        target: Option<String>,
+        heading: Option<String>,
+        depth: usize,
+    ) -> String {
+        let html = embed_placeholder(kind, link, target.as_deref());
+        self.embeds.push(EmbedInfo {
+            source_path: source.to_string(),
+            link: link.to_string(),
+            kind,
+            target_path: target,
+            heading,
+            depth,
+        });
+        html
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_embeds_skips_code_and_strips_alias() {
+        let content = "![[A]] [[B]] `![[C]]`\n```\n![[D]]\n```\n![[E#섹션|별칭]]";
+        assert_eq!(extract_embeds(content), vec!["A", "E#섹션"]);
+    }
+
+    #[test]
+    fn test_extract_section_stops_at_same_level() {
+        let body = "# 제목\n서론\n## 방법\n내용1\n### 세부\n내용2\n## 결과\n끝";
+        assert_eq!(extract_section(body, "방법").unwrap(), "## 방법\n내용1\n### 세부\n내용2");
+        assert!(extract_section(body, "없음").is_none());
+    }
+
+    #[test]
+    fn test_placeholder_is_typed_and_escaped() {
+        let html = embed_placeholder(EmbedKind::Pdf, "a\"b.pdf", None);
+        assert!(html.contains("data-embed-kind=\"pdf\""));
+        assert!(html.contains("a&quot;b.pdf"));
+    }
+}
@@ -22,6 +22,15 @@ pub struct VaultFileDeletedPayload {
     pub paths: Vec<String>,
 }
 
+/// Payload emitted when a note embedded (`![[...]]`) by other notes changes
+#[derive(Clone, Serialize)]
+pub struct EmbedDependencyChangedPayload {
+    /// The modified note
+    pub changed_path: String,
+    /// Notes transcluding it, directly or through nested embeds
+    pub dependents: Vec<String>,
+}
+
 /// Payload emitted when a Synology Drive conflict file is detected
 #[derive(Clone, Serialize)]
 pub struct SynologyConflictPayload {
@@ -111,7 +120,9 @@ impl VaultWatcher {
                         }
 
                         for path in &event.paths {
-                            if should_process_path(path, &vault_clone) {
+                            if should_process_path(path, &vault_clone)
+                                || is_attachment_path(path, &vault_clone)
+                            {
                                 pending_paths.insert(path.clone(), now);
                             }
                         }
@@ -129,6 +140,15 @@ impl VaultWatcher {
                                     deleted_paths.push(path.to_string_lossy().to_string());
                                 }
                             }
+                            // Deleted folders: drop every indexed note underneath
+                            // (some platforms only report the folder itself)
+                            for path in &event.paths {
+                                if is_removed_folder_candidate(path, &vault_clone) {
+                                    if let Err(e) = index_clone.remove_dir(path) {
+                                        log::warn!("Failed to remove folder from index: {}", e);
+                                    }
+                                }
+                            }
                             for path in &event.paths {
                                 pending_paths.remove(path);
                             }
@@ -181,8 +201,16 @@ impl VaultWatcher {
 
                         // Collect changed file paths for frontend notification
                         let mut changed_paths: Vec<String> = Vec::new();
+                        // Attachments are not indexed; they only refresh notes embedding them
+                        let mut changed_attachments: Vec<String> = Vec::new();
 
                         for path in to_process {
+                            if is_attachment_path(&path, &vault_clone) {
+                                if path.is_file() {
+                                    changed_attachments.push(path.to_string_lossy().to_string());
+                                }
+                                continue;
+                            }
                             if path.exists() && path.is_file() {
                                 let file_name = path
                                     .file_name()
@@ -248,6 +276,30 @@ impl VaultWatcher {
                                     changed_paths
                                         .push(path.to_string_lossy().to_string());
                                 }
+                            } else if !path.exists()
+                                && path.extension().and_then(|e| e.to_str()) == Some("md")
+                            {
+                                // Old side of a rename: keep index and link graph exact
+                                if let Err(e) = index_clone.remove_file(&path) {
+                                    log::warn!("Failed to remove renamed file from index: {}", e);
+                                }
+                            }
+                        }
+
+                        // Notes transcluding a changed note or attachment must refresh their expanded embeds
+                        for changed in changed_paths.iter().chain(&changed_attachments) {
+                            match index_clone.embed_dependents(changed) {
+                                Ok(dependents) if !dependents.is_empty() => {
+                                    let _ = app_handle.emit(
+                                        "embed-dependency-changed",
+                                        EmbedDependencyChangedPayload {
+                                            changed_path: changed.clone(),
+                                            dependents,
+                                        },
+                                    );
+                                }
+                                Ok(_) => {}
+                                Err(e) => log::warn!("Failed to find embed dependents of {}: {}", changed, e),
                             }
                         }
 
@@ -319,6 +371,47 @@ fn should_process_path(path: &Path, vault_path: &Path) -> bool {
     false
 }
 
+/// A non-note file whose changes matter only to notes embedding it (images, PDFs, ...),
+/// inside attachment folders or next to notes, but not in hidden directories
+fn is_attachment_path(path: &Path, vault_path: &Path) -> bool {
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    if file_name.is_empty()
+        || file_name.starts_with('.')
+        || file_name.ends_with(".notology-tmp")
+        || file_name == "comments.json"
+    {
+        return false;
+    }
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext != "md" => {}
+        _ => return false,
+    }
+    let relative = path.strip_prefix(vault_path).unwrap_or(path);
+    !relative.components().any(|component| match component {
+        std::path::Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+        _ => false,
+    })
+}
+
+/// A removed path that may have been a folder containing indexed notes
+/// (not a note, not comments.json, not inside hidden or attachment folders)
+fn is_removed_folder_candidate(path: &Path, vault_path: &Path) -> bool {
+    if path.exists() || path == vault_path || !path.starts_with(vault_path) {
+        return false;
+    }
+    if path.extension().and_then(|e| e.to_str()) == Some("md") {
+        return false;
+    }
+    let relative = path.strip_prefix(vault_path).unwrap_or(path);
+    !relative.components().any(|component| match component {
+        std::path::Component::Normal(name) => {
+            let name_str = name.to_string_lossy();
+            name_str.starts_with('.') || name_str.ends_with("_att") || name_str == "comments.json"
+        }
+        _ => false,
+    })
+}
+
 /// Check if a filename matches Synology Drive conflict patterns.
 /// Synology Drive creates files like:
 ///   "filename (SynologyDrive Conflict).md"
@@ -486,6 +579,17 @@ mod tests {
         assert!(!should_process_path(&path, &vault), "_att 외부 comments.json은 건너뛰어야 함");
     }
 
+    #[test]
+    fn test_removed_folder_candidate() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let vault = dir.path();
+        assert!(is_removed_folder_candidate(&vault.join("삭제된폴더"), vault));
+        assert!(!is_removed_folder_candidate(&vault.join("노트.md"), vault), ".md 파일은 폴더가 아님");
+        assert!(!is_removed_folder_candidate(&vault.join("노트_att"), vault), "첨부 폴더 제외");
+        assert!(!is_removed_folder_candidate(&vault.join(".notology/x"), vault), "숨김 폴더 제외");
+        assert!(!is_removed_folder_candidate(vault, vault), "볼트 루트 제외");
+    }
+
     #[test]
     fn test_skips_non_md_files() {
         let vault = PathBuf::from("/vault");
@@ -494,6 +598,20 @@ mod tests {
         assert!(!should_process_path(&PathBuf::from("/vault/data.json"), &vault));
     }
 
+    #[test]
+    fn test_attachment_paths_only_trigger_embed_lookup() {
+        let vault = PathBuf::from("/vault");
+        for path in ["/vault/image.png", "/vault/Note_att/doc.pdf"] {
+            let path = PathBuf::from(path);
+            assert!(is_attachment_path(&path, &vault), "{:?}: 임베드 확인 대상", path);
+            assert!(!should_process_path(&path, &vault), "{:?}: 인덱싱하지 않음", path);
+        }
+        assert!(!is_attachment_path(&PathBuf::from("/vault/note.md"), &vault));
+        assert!(!is_attachment_path(&PathBuf::from("/vault/Note_att/comments.json"), &vault));
+        assert!(!is_attachment_path(&PathBuf::from("/vault/.notology/index.json"), &vault));
+        assert!(!is_attachment_path(&PathBuf::from("/vault/image.png.notology-tmp"), &vault));
+    }
+
     // =========================================================================
     // 통합: 충돌 파일은 should_process_path를 통과하지만 별도 처리
     // =========================================================================
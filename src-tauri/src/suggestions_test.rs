@@ -0,0 +1,118 @@
+// Content-aware suggestions - 본문 핵심어, 공통 링크, 태그 동시 출현 기반 관계/태그 제안과 근거 검증
+
+#[cfg(test)]
+mod suggestions_tests {
+    use crate::frontmatter::suggestions::{SuggestionAction, SuggestionEngine, SuggestionReason, SuggestionType};
+    use crate::frontmatter::types::RelationType;
+    use crate::frontmatter::workflow::WorkflowConfig;
+    use crate::test_common::open_index;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn note(title: &str, note_type: &str, tags: &str, extra: &str, body: &str) -> String {
+        format!(
+            "---\ntitle: {}\ntype: {}\ntags:\n{}{}created: 2026-01-01T00:00:00+09:00\nmodified: 2026-01-01T00:00:00+09:00\n---\n\n{}\n",
+            title, note_type, tags, extra, body
+        )
+    }
+
+    fn build_vault(vault: &std::path::Path) {
+        fs::write(
+            vault.join("트랜스포머.md"),
+            note(
+                "트랜스포머", "PAPER", "  domain: [ml]\n", "",
+                "Transformer encoder decoder stacks use multi head attention and positional encoding. [[어텐션]]",
+            ),
+        ).unwrap();
+        fs::write(
+            vault.join("어텐션 이론.md"),
+            note(
+                "어텐션 이론", "THEO", "  domain: [ml]\n", "",
+                "Multi head attention projects queries keys values; positional encoding keeps order. [[어텐션]]",
+            ),
+        ).unwrap();
+        fs::write(vault.join("어텐션.md"), note("어텐션", "NOTE", "  domain: [ml]\n", "", "Softmax over scores.")).unwrap();
+        fs::write(vault.join("김치찌개.md"), note("김치찌개", "NOTE", "  domain: [요리]\n", "", "Kimchi stew recipe with pork and tofu.")).unwrap();
+        fs::write(
+            vault.join("실험 1.md"),
+            note("실험 1", "NOTE", "  domain: [ml]\n  method: [실험]\n", "", "Training run notes for the baseline."),
+        ).unwrap();
+        fs::write(
+            vault.join("실험 2.md"),
+            note(
+                "실험 2", "NOTE", "  domain: [ml]\n  method: [실험]\n",
+                "relations:\n  - relation_type: extends\n    target: \"[[트랜스포머]]\"\n",
+                "Ablation of positional encoding in the transformer encoder.",
+            ),
+        ).unwrap();
+    }
+
+    #[test]
+    fn test_relation_suggestions_with_reasons() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        build_vault(vault);
+        let (index, _guard) = open_index(vault);
+
+        let paper = vault.join("트랜스포머.md").to_string_lossy().to_string();
+        let evidence = index.suggestion_evidence(&paper, 5).unwrap();
+        let titles: Vec<&str> = evidence.related.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles.first(), Some(&"어텐션 이론"), "본문과 링크가 가장 많이 겹치는 노트가 먼저: {:?}", titles);
+        assert!(!titles.contains(&"김치찌개"), "관련 없는 노트 제외");
+
+        let theory = &evidence.related[0];
+        assert!(theory.shared_terms.iter().any(|t| t == "attention"), "{:?}", theory.shared_terms);
+        assert_eq!(theory.shared_links, vec!["어텐션".to_string()]);
+        assert_eq!(theory.shared_tags, vec!["domain/ml".to_string()]);
+        let linked = evidence.related.iter().find(|r| r.title == "어텐션").expect("직접 링크한 노트");
+        assert!(linked.links_to && !linked.linked_from);
+
+        let suggestions = SuggestionEngine::generate_suggestions(None, &evidence, &WorkflowConfig::default());
+        let first = suggestions.iter().find(|s| matches!(s.suggestion_type, SuggestionType::RelationSuggestion)).unwrap();
+        match &first.action {
+            SuggestionAction::AddRelation { relation_type, target, .. } => {
+                assert!(matches!(relation_type, RelationType::Implements), "논문 -> 이론은 구현 관계");
+                assert_eq!(target, "어텐션 이론");
+            }
+            other => panic!("관계 제안이어야 함: {:?}", other),
+        }
+        assert!(first.reasons.iter().any(|r| matches!(r, SuggestionReason::SharedTerms { .. })));
+        assert!(first.reasons.contains(&SuggestionReason::SharedLinks { notes: vec!["어텐션".to_string()] }));
+
+        // 이미 관계를 선언한 노트는 다시 제안하지 않음
+        let experiment = vault.join("실험 2.md").to_string_lossy().to_string();
+        let evidence = index.suggestion_evidence(&experiment, 5).unwrap();
+        assert!(!evidence.related.iter().any(|r| r.title == "트랜스포머"), "{:?}", evidence.related);
+    }
+
+    #[test]
+    fn test_tag_suggestions_from_cooccurrence() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        build_vault(vault);
+        let (index, _guard) = open_index(vault);
+
+        let paper = vault.join("트랜스포머.md").to_string_lossy().to_string();
+        let evidence = index.suggestion_evidence(&paper, 5).unwrap();
+        assert_eq!(evidence.tags, vec!["domain/ml".to_string()]);
+        assert_eq!(evidence.tag_candidates.len(), 1);
+        assert_eq!(evidence.tag_candidates[0].tag, "method/실험");
+        assert_eq!((evidence.tag_candidates[0].together, evidence.tag_candidates[0].with_count), (2, 5));
+
+        let suggestions = SuggestionEngine::generate_suggestions(None, &evidence, &WorkflowConfig::default());
+        let tag = suggestions.iter().find(|s| matches!(s.suggestion_type, SuggestionType::TagSuggestion)).unwrap();
+        assert!(matches!(&tag.action, SuggestionAction::AddTag { facet, tag } if facet == "method" && tag == "실험"));
+        assert_eq!(
+            tag.reasons,
+            vec![SuggestionReason::TagCooccurrence { with_tag: "domain/ml".to_string(), together: 2, with_count: 5 }]
+        );
+
+        // 태그 통계는 재색인 시 갱신됨
+        let first = vault.join("실험 1.md");
+        fs::write(&first, note("실험 1", "NOTE", "  domain: [ml]\n", "", "Training run notes for the baseline.")).unwrap();
+        index.index_file(&first).unwrap();
+        assert!(index.suggestion_evidence(&paper, 5).unwrap().tag_candidates.is_empty());
+
+        assert!(index.suggestion_evidence(&vault.join("없음.md").to_string_lossy(), 5).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! Migration of legacy list-style tags (`tags: [a, b]`) to faceted tags
+//!
+//! Each legacy tag is resolved through the user-editable mapping table in
+//! `.notology/tag-mapping.json`, then prefix rules. A tag is only dropped when the table maps
+//! it to `null`; tags no rule matches stay under `tags.legacy` so they are never lost.
+
+use crate::bulk_edit::{apply_edits, parse_tag, FrontmatterEdit, TAG_FACETS};
+use crate::VaultJsonConfig;
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+
+/// Facet key holding legacy tags that could not be resolved yet
+pub const LEGACY_FACET: &str = "legacy";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TagMapping {
+    /// Exact legacy tag -> "facet/name"; `null` drops the tag
+    #[serde(default)]
+    pub tags: BTreeMap<String, Option<String>>,
+    /// Tag prefix -> facet ("@" -> "who" turns "@kim" into who/kim)
+    #[serde(default)]
+    pub prefixes: BTreeMap<String, String>,
+    /// Facet for tags no rule matches; unset keeps them under `tags.legacy`
+    #[serde(default)]
+    pub default_facet: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TagResolution {
+    /// (facet, name)
+    Mapped(String, String),
+    Dropped,
+    Unresolved,
+}
+
+impl VaultJsonConfig for TagMapping {
+    const FILE_NAME: &'static str = "tag-mapping.json";
+
+    fn validate(&self) -> Result<(), String> {
+        for target in self.tags.values().flatten() {
+            parse_tag(target)?;
+        }
+        for facet in self.prefixes.values().chain(self.default_facet.iter()) {
+            if !TAG_FACETS.contains(&facet.as_str()) {
+                return Err(format!("Unknown tag facet: {}", facet));
+            }
+        }
+        if self.prefixes.keys().any(|p| p.is_empty()) {
+            return Err("Tag prefix must not be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl TagMapping {
+    /// Table entry, then the longest matching prefix rule, then a `facet/name` or
+    /// `facet:name` tag, then `default_facet`
+    pub fn resolve(&self, tag: &str) -> TagResolution {
+        let tag = tag.trim().trim_start_matches('#');
+        if tag.is_empty() {
+            return TagResolution::Dropped;
+        }
+        if let Some(target) = self.tags.get(tag) {
+            return match target.as_deref().map(parse_tag) {
+                Some(Ok((facet, name))) => TagResolution::Mapped(facet, name),
+                Some(Err(_)) => TagResolution::Unresolved,
+                None => TagResolution::Dropped,
+            };
+        }
+
+        let by_prefix = self
+            .prefixes
+            .iter()
+            .filter(|(prefix, _)| tag.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, facet)| (facet.clone(), tag[prefix.len()..].trim().to_string()));
+        let faceted = tag
+            .split_once(['/', ':'])
+            .filter(|(facet, _)| TAG_FACETS.contains(facet))
+            .map(|(facet, name)| (facet.to_string(), name.trim().to_string()));
+        let fallback = self.default_facet.clone().map(|facet| (facet, tag.to_string()));
+
+        match by_prefix.or(faceted).or(fallback) {
+            Some((facet, name)) if !name.is_empty() => TagResolution::Mapped(facet, name),
+            _ => TagResolution::Unresolved,
+        }
+    }
+}
+
+/// Legacy tags of a note: a `tags` list, or the `tags.legacy` list left by an earlier migration
+pub fn legacy_tags(fm: &Mapping) -> Vec<String> {
+    let list = match fm.get("tags") {
+        Some(Value::Sequence(items)) => items,
+        Some(Value::Mapping(facets)) => match facets.get(LEGACY_FACET) {
+            Some(Value::Sequence(items)) => items,
+            _ => return Vec::new(),
+        },
+        _ => return Vec::new(),
+    };
+    list.iter()
+        .filter_map(|v| match v {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Move resolved legacy tags into their facets; Ok(true) if the note changed.
+/// Tags still unresolved are appended to `unresolved` and kept under `tags.legacy`.
+pub fn migrate_legacy_tags(fm: &mut Mapping, mapping: &TagMapping, unresolved: &mut Vec<String>) -> Result<bool, String> {
+    let legacy = legacy_tags(fm);
+    let (pending, settled): (Vec<_>, Vec<_>) = legacy
+        .into_iter()
+        .map(|tag| {
+            let resolution = mapping.resolve(&tag);
+            (tag, resolution)
+        })
+        .partition(|(_, r)| *r == TagResolution::Unresolved);
+    let pending: Vec<String> = pending.into_iter().map(|(tag, _)| tag).collect();
+    unresolved.extend(pending.iter().cloned());
+    if settled.is_empty() {
+        return Ok(false);
+    }
+
+    // Convert in place so `tags` keeps its position among the keys
+    if let Some(tags) = fm.get_mut("tags") {
+        match tags {
+            Value::Mapping(facets) => {
+                facets.remove(LEGACY_FACET);
+            }
+            _ => *tags = Value::Mapping(Mapping::new()),
+        }
+    }
+
+    let edits: Vec<FrontmatterEdit> = settled
+        .into_iter()
+        .filter_map(|(_, r)| match r {
+            TagResolution::Mapped(facet, tag) => Some(FrontmatterEdit::AddTag { facet, tag }),
+            _ => None,
+        })
+        .collect();
+    apply_edits(fm, &edits)?;
+
+    if !pending.is_empty() {
+        if let Some(Value::Mapping(facets)) = fm.get_mut("tags") {
+            facets.insert(
+                Value::String(LEGACY_FACET.to_string()),
+                Value::Sequence(pending.into_iter().map(Value::String).collect()),
+            );
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> TagMapping {
+        serde_json::from_str(
+            r#"{"tags": {"ML": "domain/machine-learning", "temp": null},
+                "prefixes": {"@": "who", "org-": "org"}}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_order() {
+        let mapping = mapping();
+        assert_eq!(mapping.resolve("ML"), TagResolution::Mapped("domain".into(), "machine-learning".into()));
+        assert_eq!(mapping.resolve("temp"), TagResolution::Dropped);
+        assert_eq!(mapping.resolve("@김"), TagResolution::Mapped("who".into(), "김".into()));
+        assert_eq!(mapping.resolve("#ctx:연구"), TagResolution::Mapped("ctx".into(), "연구".into()));
+        assert_eq!(mapping.resolve("기타"), TagResolution::Unresolved);
+
+        let with_default = TagMapping { default_facet: Some("domain".into()), ..mapping };
+        assert_eq!(with_default.resolve("기타"), TagResolution::Mapped("domain".into(), "기타".into()));
+    }
+
+    #[test]
+    fn test_migrate_keeps_unresolved_tags() {
+        let mut fm: Mapping = serde_yaml::from_str("title: a\ntags: [ML, temp, '@김', 기타]").unwrap();
+        let mut unresolved = Vec::new();
+        assert!(migrate_legacy_tags(&mut fm, &mapping(), &mut unresolved).unwrap());
+        assert_eq!(unresolved, vec!["기타"]);
+        assert_eq!(fm["tags"]["domain"][0], "machine-learning");
+        assert_eq!(fm["tags"]["who"][0], "김");
+        assert_eq!(fm["tags"][LEGACY_FACET][0], "기타");
+        assert_eq!(legacy_tags(&fm), vec!["기타"]);
+
+        // Nothing resolvable: the note is left as it is
+        let mut only_unknown: Mapping = serde_yaml::from_str("tags: [기타]").unwrap();
+        let before = only_unknown.clone();
+        assert!(!migrate_legacy_tags(&mut only_unknown, &mapping(), &mut Vec::new()).unwrap());
+        assert_eq!(only_unknown, before);
+    }
+}
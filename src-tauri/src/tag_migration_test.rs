@@ -0,0 +1,71 @@
+// Legacy tag migration - 배열형 태그를 facet으로 이동, 미리보기 무변경, 미해결 태그 보존 검증
+
+#[cfg(test)]
+mod tag_migration_tests {
+    use crate::tag_migration::TagMapping;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn setup_vault(vault: &Path) {
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        fs::write(vault.join("옛노트.md"), "---\ntitle: 옛노트\ntags: [ml, '@김', 기타]\n---\n\n본문\n").unwrap();
+        fs::write(vault.join("새노트.md"), "---\ntitle: 새노트\ntags:\n  domain: [ml]\n---\n\n본문\n").unwrap();
+        fs::write(vault.join("메모.md"), "frontmatter 없는 노트\n").unwrap();
+        crate::save_tag_mapping(
+            vault.to_string_lossy().to_string(),
+            serde_json::from_str(r#"{"tags": {"ml": "domain/machine-learning"}, "prefixes": {"@": "who"}}"#).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_preview_then_migrate_keeps_unresolved() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+        let mapping = crate::load_vault_json_config::<TagMapping>(vault).unwrap();
+        let before = fs::read_to_string(vault.join("옛노트.md")).unwrap();
+
+        let (preview, written) = crate::migrate_legacy_tags_in_vault(vault, &mapping, true);
+        assert!(written.is_empty());
+        assert_eq!(preview.result.changed.len(), 1);
+        assert!(preview.result.failed.is_empty(), "frontmatter 없는 노트는 실패가 아님");
+        assert_eq!(preview.unresolved.len(), 1);
+        assert_eq!(preview.unresolved[0].tag, "기타");
+        assert_eq!(fs::read_to_string(vault.join("옛노트.md")).unwrap(), before);
+
+        let (_, written) = crate::migrate_legacy_tags_in_vault(vault, &mapping, false);
+        assert_eq!(written.len(), 1);
+        let migrated = fs::read_to_string(vault.join("옛노트.md")).unwrap();
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&migrated[4..migrated.find("\n---\n").unwrap()]).unwrap();
+        let tags = &yaml["tags"];
+        assert_eq!(tags["domain"][0], "machine-learning");
+        assert_eq!(tags["who"][0], "김");
+        assert_eq!(tags["legacy"][0], "기타", "규칙이 없는 태그는 legacy에 보존");
+        assert!(vault.join(".notology").join("backups").exists());
+    }
+
+    #[test]
+    fn test_explicit_drop_and_second_pass() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        setup_vault(vault);
+        let mapping = crate::load_vault_json_config::<TagMapping>(vault).unwrap();
+        crate::migrate_legacy_tags_in_vault(vault, &mapping, false);
+
+        // 남은 legacy 태그를 명시적으로 삭제하도록 규칙 추가 후 재실행
+        let mut mapping = mapping;
+        mapping.tags.insert("기타".to_string(), None);
+        let (migration, written) = crate::migrate_legacy_tags_in_vault(vault, &mapping, false);
+        assert_eq!(written.len(), 1);
+        assert!(migration.unresolved.is_empty());
+        let content = fs::read_to_string(vault.join("옛노트.md")).unwrap();
+        assert!(!content.contains("기타"));
+        assert!(!content.contains("legacy"));
+        assert!(content.contains("- machine-learning"));
+
+        let invalid: TagMapping = serde_json::from_str(r#"{"prefixes": {"@": "people"}}"#).unwrap();
+        assert!(crate::save_tag_mapping(vault.to_string_lossy().to_string(), invalid).is_err());
+    }
+}
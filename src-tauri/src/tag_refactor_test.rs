@@ -0,0 +1,67 @@
+// Tag refactor - vault 전체 태그 이름 변경/병합/삭제 후 YAML과 인덱스 tags 필드 반영 검증
+
+#[cfg(test)]
+mod tag_refactor_tests {
+    use crate::bulk_edit::TagRefactor;
+    use crate::search::SearchIndex;
+    use crate::test_common::{open_index, IndexDirGuard};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn setup_vault(vault: &Path) -> (SearchIndex, IndexDirGuard) {
+        fs::create_dir_all(vault.join(".notology")).unwrap();
+        fs::write(vault.join("a.md"), "---\ntitle: a\ntags:\n  domain: [ml, 통계]\n---\n\n본문 a\n").unwrap();
+        fs::write(vault.join("b.md"), "---\ntitle: b\ntags:\n  domain: [domain/ml]\n  ctx: [연구]\n---\n\n본문 b\n").unwrap();
+        fs::write(vault.join("c.md"), "---\ntitle: c\ntags:\n  domain: [통계]\n---\n\n본문 c\n").unwrap();
+
+        open_index(vault)
+    }
+
+    /// Same steps as the `refactor_tags` command: find targets, rewrite, re-index what was written
+    fn run_refactor(index: &SearchIndex, refactor: &TagRefactor, dry_run: bool, vault: &Path) -> crate::BulkEditResult {
+        let targets = crate::tag_refactor_targets(index, refactor).unwrap();
+        let (result, written) = crate::retag_notes(&targets, refactor, dry_run, vault).unwrap();
+        index.index_files(&written).unwrap();
+        result
+    }
+
+    #[test]
+    fn test_rename_updates_yaml_and_index_tags() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let (index, _guard) = setup_vault(vault);
+
+        let refactor = TagRefactor::Rename { from: "domain/ml".into(), to: "domain/machine-learning".into() };
+        let preview = run_refactor(&index, &refactor, true, vault);
+        assert_eq!(preview.changed.len(), 2);
+        assert!(index.get_all_tags().unwrap().contains(&"domain/ml".to_string()), "dry-run은 인덱스 유지");
+
+        let result = run_refactor(&index, &refactor, false, vault);
+        assert_eq!(result.changed.len(), 2);
+        assert!(fs::read_to_string(vault.join("a.md")).unwrap().contains("domain: [machine-learning, 통계]\n"), "flow 스타일 유지");
+        assert!(fs::read_to_string(vault.join("c.md")).unwrap().contains("[통계]"), "관련 없는 노트는 그대로");
+
+        let tags = index.get_all_tags().unwrap();
+        assert!(!tags.contains(&"domain/ml".to_string()));
+        assert!(tags.contains(&"domain/machine-learning".to_string()));
+        assert_eq!(index.notes_with_tags(&["domain/machine-learning".to_string()]).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_merge_and_delete() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let (index, _guard) = setup_vault(vault);
+
+        let merge = TagRefactor::Merge { sources: vec!["domain/통계".into(), "ctx/연구".into()], into: "domain/ml".into() };
+        let result = run_refactor(&index, &merge, false, vault);
+        assert_eq!(result.changed.len(), 3);
+        assert_eq!(index.notes_with_tags(&["domain/ml".to_string()]).unwrap().len(), 3);
+        assert!(fs::read_to_string(vault.join("a.md")).unwrap().contains("domain: [ml]\n"), "중복 없이 병합");
+
+        let delete = TagRefactor::Delete { tag: "domain/ml".into() };
+        run_refactor(&index, &delete, false, vault);
+        assert!(index.get_all_tags().unwrap().iter().all(|t| !t.starts_with("domain/")));
+    }
+}
@@ -0,0 +1,622 @@
+//! Vault note templates
+//!
+//! Templates are markdown files under `.notology/templates/` (subfolders allowed; the name is
+//! the relative path without `.md`). Their frontmatter is the new note's frontmatter plus an
+//! optional `template:` header with a description, a file name pattern and the prompts to
+//! ask the user; the header is not copied into the note.
+//!
+//! In frontmatter string values and in the body:
+//! - `{{title}}`, `{{container}}`, `{{uuid}}`, `{{now}}`, `{{date}}`, `{{time}}`,
+//!   `{{date:YYYY-MM-DD ddd}}` and prompt answers by name
+//! - `{{#if name}}...{{else}}...{{/if}}`, true for non-empty answers
+//! - `{{#each name}}...{{this}}...{{/each}}` over list answers (`multiple` prompts)
+//! - `{{> name}}` inserts the rendered body of another template
+//!
+//! A frontmatter value that is exactly one `{{name}}` keeps the answer's type: lists become
+//! YAML lists, numbers and booleans stay unquoted (e.g. `year: "{{year}}"`).
+
+use crate::autofix::DEFAULT_DATE_FORMAT;
+use crate::frontmatter::ids::{claim_note_id, peek_note_id};
+use crate::frontmatter::schemas::VaultSchemas;
+use crate::frontmatter::types::Frontmatter;
+use crate::frontmatter::FrontmatterParser;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value as Yaml};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const HEADER_KEY: &str = "template";
+const DEFAULT_FILE_NAME: &str = "{{title}}";
+/// Fields every note has; put first in the rendered frontmatter
+const LEADING_FIELDS: [&str; 5] = ["id", "title", "type", "created", "modified"];
+
+pub fn templates_dir(vault_path: &Path) -> PathBuf {
+    vault_path.join(".notology").join("templates")
+}
+
+/// A value the user is asked for before the template is rendered
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TemplatePrompt {
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Allowed answers; empty for free text
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// Comma-separated answer, rendered as a list
+    #[serde(default)]
+    pub multiple: bool,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Deserialize, Default)]
+struct TemplateHeader {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    file_name: Option<String>,
+    #[serde(default)]
+    prompts: Vec<TemplatePrompt>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TemplateInfo {
+    pub name: String,
+    pub description: Option<String>,
+    pub note_type: Option<String>,
+    pub prompts: Vec<TemplatePrompt>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct RenderedNote {
+    /// Without `.md`
+    pub file_name: String,
+    pub frontmatter: Frontmatter,
+    /// Full note text, frontmatter included
+    pub content: String,
+}
+
+/// Input of one render
+pub struct TemplateContext {
+    pub title: String,
+    /// Name of the folder the note is created in
+    pub container: String,
+    /// Prompt answers by prompt name (`multiple` answers comma-separated)
+    pub answers: HashMap<String, String>,
+    pub now: DateTime<Local>,
+}
+
+struct Template {
+    header: TemplateHeader,
+    frontmatter: Mapping,
+    body: String,
+}
+
+fn load_template(vault_path: &Path, name: &str) -> Result<Template, String> {
+    if name.is_empty() || name.split(['/', '\\']).any(|part| part.is_empty() || part == "..") {
+        return Err(format!("Invalid template name: {}", name));
+    }
+    let path = templates_dir(vault_path).join(format!("{}.md", name));
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("Template {}: {}", name, e))?;
+    parse_template(&content).map_err(|e| format!("Template {}: {}", name, e))
+}
+
+fn parse_template(content: &str) -> Result<Template, String> {
+    let content = content.replace("\r\n", "\n");
+    let Some(end_idx) = content.strip_prefix("---").and_then(|rest| rest.find("\n---")) else {
+        return Ok(Template { header: TemplateHeader::default(), frontmatter: Mapping::new(), body: content });
+    };
+    let mut frontmatter = match serde_yaml::from_str::<Yaml>(&content[3..3 + end_idx]) {
+        Ok(Yaml::Mapping(map)) => map,
+        Ok(Yaml::Null) => Mapping::new(),
+        Ok(_) => return Err("frontmatter is not a mapping".to_string()),
+        Err(e) => return Err(format!("invalid frontmatter: {}", e)),
+    };
+    let header = match frontmatter.remove(HEADER_KEY) {
+        Some(value) => serde_yaml::from_value(value).map_err(|e| format!("invalid {} header: {}", HEADER_KEY, e))?,
+        None => TemplateHeader::default(),
+    };
+    let body = content[3 + end_idx + 4..].trim_start_matches('\n').to_string();
+    Ok(Template { header, frontmatter, body })
+}
+
+/// Every readable template in the vault, sorted by name. Unreadable ones are logged and skipped.
+pub fn list_templates(vault_path: &Path) -> Vec<TemplateInfo> {
+    let dir = templates_dir(vault_path);
+    let mut templates: Vec<TemplateInfo> = walkdir::WalkDir::new(&dir)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file() && e.path().extension().and_then(|x| x.to_str()) == Some("md"))
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(&dir).ok()?.with_extension("");
+            let name = relative.to_string_lossy().replace('\\', "/");
+            match load_template(vault_path, &name) {
+                Ok(template) => Some(TemplateInfo {
+                    name,
+                    description: template.header.description,
+                    note_type: template.frontmatter.get("type").and_then(|t| t.as_str()).map(str::to_string),
+                    prompts: template.header.prompts,
+                }),
+                Err(e) => {
+                    log::warn!("[templates] Skipping {}", e);
+                    None
+                }
+            }
+        })
+        .collect();
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
+// ---------------------------------------------------------------------------
+// Syntax
+// ---------------------------------------------------------------------------
+
+enum Token {
+    Text(String),
+    Tag(String),
+}
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Var(String),
+    If { name: String, then: Vec<Node>, otherwise: Vec<Node> },
+    Each { name: String, body: Vec<Node> },
+    Include(String),
+}
+
+fn is_block_tag(tag: &str) -> bool {
+    tag.starts_with('#') || tag.starts_with('/') || tag == "else"
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("{{") {
+        if start > 0 {
+            tokens.push(Token::Text(rest[..start].to_string()));
+        }
+        let end = start + rest[start..].find("}}").ok_or("Unclosed {{ in template")?;
+        tokens.push(Token::Tag(rest[start + 2..end].trim().to_string()));
+        rest = &rest[end + 2..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+
+    // A block tag alone on its line takes the whole line with it. Positions are found on the
+    // original tokens first: a text between two such tags is trimmed from both ends.
+    let mut standalone = Vec::new();
+    for i in 0..tokens.len() {
+        if !matches!(&tokens[i], Token::Tag(tag) if is_block_tag(tag)) {
+            continue;
+        }
+        let line_start = match i.checked_sub(1).map(|p| &tokens[p]) {
+            None => Some(0),
+            Some(Token::Text(text)) => {
+                let from = text.rfind('\n').map(|n| n + 1).unwrap_or(0);
+                let at_line_start = from > 0 || i == 1;
+                (at_line_start && text[from..].trim().is_empty()).then_some(from)
+            }
+            Some(Token::Tag(_)) => None,
+        };
+        let line_end = match tokens.get(i + 1) {
+            None => Some(0),
+            Some(Token::Text(text)) => {
+                let to = text.find('\n').map(|n| n + 1).unwrap_or(text.len());
+                let at_line_end = to < text.len() || text.ends_with('\n') || i + 2 == tokens.len();
+                (at_line_end && text[..to].trim().is_empty()).then_some(to)
+            }
+            Some(Token::Tag(_)) => None,
+        };
+        if let (Some(from), Some(to)) = (line_start, line_end) {
+            standalone.push((i, from, to));
+        }
+    }
+    for &(i, from, _) in &standalone {
+        if let Some(Token::Text(text)) = i.checked_sub(1).and_then(|p| tokens.get_mut(p)) {
+            text.truncate(from);
+        }
+    }
+    for &(i, _, to) in &standalone {
+        if let Some(Token::Text(text)) = tokens.get_mut(i + 1) {
+            text.drain(..to.min(text.len()));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Nodes up to the tag closing `block` (returned), or to the end when `block` is None
+fn parse_nodes(tokens: &[Token], pos: &mut usize, block: Option<&str>) -> Result<(Vec<Node>, Option<String>), String> {
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.get(*pos) {
+        *pos += 1;
+        let tag = match token {
+            Token::Text(text) => {
+                nodes.push(Node::Text(text.clone()));
+                continue;
+            }
+            Token::Tag(tag) => tag.as_str(),
+        };
+        if let Some(name) = tag.strip_prefix("#if ") {
+            let (then, end) = parse_nodes(tokens, pos, Some("if"))?;
+            let otherwise = if end.as_deref() == Some("else") {
+                match parse_nodes(tokens, pos, Some("if"))? {
+                    (nodes, Some(end)) if end == "/if" => nodes,
+                    _ => return Err("Duplicate {{else}} in {{#if}}".to_string()),
+                }
+            } else {
+                Vec::new()
+            };
+            nodes.push(Node::If { name: name.trim().to_string(), then, otherwise });
+        } else if let Some(name) = tag.strip_prefix("#each ") {
+            let (body, _) = parse_nodes(tokens, pos, Some("each"))?;
+            nodes.push(Node::Each { name: name.trim().to_string(), body });
+        } else if let Some(name) = tag.strip_prefix('>') {
+            nodes.push(Node::Include(name.trim().to_string()));
+        } else if tag == "else" || tag.starts_with('/') {
+            let closes = tag == "else" && block == Some("if") || tag.strip_prefix('/') == block;
+            if !closes {
+                return Err(format!("Unexpected {{{{{}}}}}", tag));
+            }
+            return Ok((nodes, Some(tag.to_string())));
+        } else if tag.starts_with('#') {
+            return Err(format!("Unknown block {{{{{}}}}}", tag));
+        } else {
+            nodes.push(Node::Var(tag.to_string()));
+        }
+    }
+    match block {
+        Some(block) => Err(format!("Unclosed {{{{#{}}}}}", block)),
+        None => Ok((nodes, None)),
+    }
+}
+
+fn parse(source: &str) -> Result<Vec<Node>, String> {
+    let tokens = tokenize(source)?;
+    Ok(parse_nodes(&tokens, &mut 0, None)?.0)
+}
+
+/// Moment-style date pattern (`YYYY-MM-DD HH:mm`, `GGGG-[W]WW`, `[literal]`) as a chrono format string
+pub(crate) fn date_format(pattern: &str) -> String {
+    const TOKENS: [(&str, &str); 19] = [
+        ("GGGG", "%G"), ("WW", "%V"),
+        ("YYYY", "%Y"), ("YY", "%y"), ("MMMM", "%B"), ("MMM", "%b"), ("MM", "%m"), ("M", "%-m"),
+        ("DD", "%d"), ("D", "%-d"), ("dddd", "%A"), ("ddd", "%a"), ("HH", "%H"), ("H", "%-H"),
+        ("hh", "%I"), ("mm", "%M"), ("ss", "%S"), ("A", "%p"), ("Z", "%:z"),
+    ];
+    let mut format = String::new();
+    let mut rest = pattern;
+    'outer: while let Some(c) = rest.chars().next() {
+        if c == '[' {
+            if let Some(end) = rest.find(']') {
+                format.push_str(&rest[1..end].replace('%', "%%"));
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+        for (token, spec) in TOKENS {
+            if let Some(after) = rest.strip_prefix(token) {
+                format.push_str(spec);
+                rest = after;
+                continue 'outer;
+            }
+        }
+        if c == '%' {
+            format.push('%');
+        }
+        format.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    format
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+#[derive(Clone)]
+enum Value {
+    Text(String),
+    List(Vec<String>),
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Text(text) => !text.trim().is_empty(),
+            Value::List(items) => !items.is_empty(),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        match self {
+            Value::Text(text) => text.clone(),
+            Value::List(items) => items.join(", "),
+        }
+    }
+}
+
+struct Renderer<'a> {
+    vault_path: &'a Path,
+    context: &'a TemplateContext,
+    values: HashMap<String, Value>,
+    /// One per render so frontmatter and body agree
+    uuid: String,
+    /// Templates being included, for cycle detection
+    includes: Vec<String>,
+}
+
+impl Renderer<'_> {
+    fn lookup(&self, name: &str, this: Option<&str>) -> Result<Value, String> {
+        let context = self.context;
+        let value = match name {
+            "this" => Value::Text(this.ok_or("{{this}} used outside {{#each}}")?.to_string()),
+            "title" => Value::Text(context.title.clone()),
+            "container" => Value::Text(context.container.clone()),
+            "uuid" => Value::Text(self.uuid.clone()),
+            "now" => Value::Text(context.now.format(DEFAULT_DATE_FORMAT).to_string()),
+            "date" => Value::Text(context.now.format("%Y-%m-%d").to_string()),
+            "time" => Value::Text(context.now.format("%H:%M").to_string()),
+            _ => match name.strip_prefix("date:") {
+                Some(pattern) => Value::Text(context.now.format(&date_format(pattern.trim())).to_string()),
+                None => self.values.get(name).cloned().ok_or_else(|| format!("Unknown template variable: {}", name))?,
+            },
+        };
+        Ok(value)
+    }
+
+    fn render(&mut self, nodes: &[Node], this: Option<&str>, out: &mut String) -> Result<(), String> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Var(name) => out.push_str(&self.lookup(name, this)?.to_text()),
+                Node::If { name, then, otherwise } => {
+                    let branch = if self.lookup(name, this)?.is_truthy() { then } else { otherwise };
+                    self.render(branch, this, out)?;
+                }
+                Node::Each { name, body } => {
+                    let items = match self.lookup(name, this)? {
+                        Value::List(items) => items,
+                        Value::Text(text) if text.trim().is_empty() => Vec::new(),
+                        Value::Text(text) => vec![text],
+                    };
+                    for item in &items {
+                        self.render(body, Some(item), out)?;
+                    }
+                }
+                Node::Include(name) => {
+                    if self.includes.contains(name) {
+                        return Err(format!("Template include cycle: {} -> {}", self.includes.join(" -> "), name));
+                    }
+                    let included = parse(&load_template(self.vault_path, name)?.body)
+                        .map_err(|e| format!("Template {}: {}", name, e))?;
+                    self.includes.push(name.clone());
+                    let mut text = String::new();
+                    self.render(&included, this, &mut text)?;
+                    self.includes.pop();
+                    out.push_str(text.trim_end_matches('\n'));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn render_str(&mut self, source: &str) -> Result<String, String> {
+        let mut out = String::new();
+        self.render(&parse(source)?, None, &mut out)?;
+        Ok(out)
+    }
+
+    fn render_yaml(&mut self, value: &Yaml) -> Result<Yaml, String> {
+        Ok(match value {
+            Yaml::String(text) => {
+                let whole_var = text
+                    .trim()
+                    .strip_prefix("{{")
+                    .and_then(|t| t.strip_suffix("}}"))
+                    .map(str::trim)
+                    .filter(|name| !name.contains("{{") && !name.contains("}}") && !is_block_tag(name) && !name.starts_with('>'));
+                match whole_var.map(|name| self.lookup(name, None)).transpose()? {
+                    Some(Value::List(items)) => Yaml::Sequence(items.into_iter().map(Yaml::String).collect()),
+                    Some(Value::Text(text)) => match serde_yaml::from_str::<Yaml>(&text) {
+                        Ok(scalar @ (Yaml::Number(_) | Yaml::Bool(_))) => scalar,
+                        _ => Yaml::String(text),
+                    },
+                    None => Yaml::String(self.render_str(text)?),
+                }
+            }
+            Yaml::Sequence(items) => Yaml::Sequence(items.iter().map(|item| self.render_yaml(item)).collect::<Result<_, _>>()?),
+            Yaml::Mapping(map) => Yaml::Mapping(
+                map.iter()
+                    .map(|(key, value)| Ok((key.clone(), self.render_yaml(value)?)))
+                    .collect::<Result<_, String>>()?,
+            ),
+            other => other.clone(),
+        })
+    }
+}
+
+/// Answers checked against the template's prompts, with defaults filled in
+fn prompt_values(prompts: &[TemplatePrompt], answers: &HashMap<String, String>) -> Result<HashMap<String, Value>, String> {
+    let split = |text: &str| text.split(',').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect();
+    let mut values: HashMap<String, Value> = answers.iter().map(|(k, v)| (k.clone(), Value::Text(v.clone()))).collect();
+    for prompt in prompts {
+        let answer = answers
+            .get(&prompt.name)
+            .filter(|a| !a.trim().is_empty())
+            .or(prompt.default.as_ref())
+            .cloned()
+            .unwrap_or_default();
+        if prompt.required && answer.trim().is_empty() {
+            return Err(format!("Missing answer for prompt: {}", prompt.label.as_deref().unwrap_or(&prompt.name)));
+        }
+        let value = if prompt.multiple { Value::List(split(&answer)) } else { Value::Text(answer) };
+        if !prompt.options.is_empty() {
+            let chosen = match &value {
+                Value::List(items) => items.clone(),
+                Value::Text(text) if text.is_empty() => Vec::new(),
+                Value::Text(text) => vec![text.clone()],
+            };
+            if let Some(bad) = chosen.iter().find(|c| !prompt.options.contains(c)) {
+                return Err(format!("Invalid answer for prompt {}: {}", prompt.name, bad));
+            }
+        }
+        values.insert(prompt.name.clone(), value);
+    }
+    Ok(values)
+}
+
+/// Characters not allowed in file names on any platform the vault syncs to
+fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') || c.is_control() { '_' } else { c })
+        .collect();
+    cleaned.trim().trim_end_matches('.').to_string()
+}
+
+/// Render template `name` into a new note. The frontmatter gets a fresh id that `taken`
+/// does not report as used, defaults for missing base fields, and must pass the vault schemas.
+/// The id is claimed, so only render this way when the note is about to be created.
+pub fn render_template(
+    vault_path: &Path,
+    name: &str,
+    context: &TemplateContext,
+    taken: impl Fn(&str) -> bool,
+) -> Result<RenderedNote, String> {
+    render(vault_path, name, load_template(vault_path, name)?, context, |id| claim_note_id(id, &taken))
+}
+
+/// `render_template` for a preview: the id shown is not reserved
+pub fn preview_template(
+    vault_path: &Path,
+    name: &str,
+    context: &TemplateContext,
+    taken: impl Fn(&str) -> bool,
+) -> Result<RenderedNote, String> {
+    render(vault_path, name, load_template(vault_path, name)?, context, |id| peek_note_id(id, &taken))
+}
+
+/// Render template text that is not a vault file (a built-in layout); `label` names it in errors
+pub fn render_template_source(
+    vault_path: &Path,
+    label: &str,
+    source: &str,
+    context: &TemplateContext,
+    taken: impl Fn(&str) -> bool,
+) -> Result<RenderedNote, String> {
+    let template = parse_template(source).map_err(|e| format!("Template {}: {}", label, e))?;
+    render(vault_path, label, template, context, |id| claim_note_id(id, &taken))
+}
+
+fn render(
+    vault_path: &Path,
+    name: &str,
+    template: Template,
+    context: &TemplateContext,
+    note_id: impl FnOnce(&str) -> String,
+) -> Result<RenderedNote, String> {
+    let mut renderer = Renderer {
+        vault_path,
+        context,
+        values: prompt_values(&template.header.prompts, &context.answers)?,
+        uuid: uuid::Uuid::new_v4().to_string(),
+        includes: vec![name.to_string()],
+    };
+    let in_template = |e: String| format!("Template {}: {}", name, e);
+
+    let body = renderer.render_str(&template.body).map_err(in_template)?;
+    let file_pattern = template.header.file_name.as_deref().unwrap_or(DEFAULT_FILE_NAME);
+    let file_name = sanitize_file_name(&renderer.render_str(file_pattern).map_err(in_template)?);
+    if file_name.is_empty() {
+        return Err(in_template("file name is empty".to_string()));
+    }
+    let mut rendered = match renderer.render_yaml(&Yaml::Mapping(template.frontmatter)).map_err(in_template)? {
+        Yaml::Mapping(map) => map,
+        _ => Mapping::new(),
+    };
+
+    let text = |value: Option<&Yaml>| match value {
+        Some(Yaml::String(s)) => s.trim().to_string(),
+        Some(Yaml::Number(n)) => n.to_string(),
+        _ => String::new(),
+    };
+    let now = context.now.format(DEFAULT_DATE_FORMAT).to_string();
+    let title = Some(text(rendered.get("title"))).filter(|t| !t.is_empty()).unwrap_or_else(|| context.title.clone());
+    let note_type = Some(text(rendered.get("type"))).filter(|t| !t.is_empty()).unwrap_or_else(|| "NOTE".to_string());
+    let created = Some(text(rendered.get("created"))).filter(|t| !t.is_empty()).unwrap_or_else(|| now.clone());
+    let modified = Some(text(rendered.get("modified"))).filter(|t| !t.is_empty()).unwrap_or(now);
+    let id = note_id(&text(rendered.get("id")));
+
+    let mut map = Mapping::new();
+    for (key, value) in LEADING_FIELDS.iter().zip([id, title, note_type, created, modified]) {
+        rendered.remove(*key);
+        map.insert(Yaml::from(*key), Yaml::String(value));
+    }
+    map.extend(rendered);
+
+    let frontmatter: Frontmatter = serde_yaml::from_value(Yaml::Mapping(map.clone()))
+        .map_err(|e| in_template(format!("invalid frontmatter: {}", e)))?;
+    let errors = FrontmatterParser::validate_with(&frontmatter, &VaultSchemas::load(vault_path)?)?;
+    if !errors.is_empty() {
+        let messages: Vec<String> = errors.iter().map(|e| format!("{} {}", e.path, e.message)).collect();
+        return Err(in_template(format!("frontmatter does not match schema: {}", messages.join("; "))));
+    }
+
+    let yaml = serde_yaml::to_string(&map).map_err(|e| format!("Failed to serialize frontmatter: {}", e))?;
+    Ok(RenderedNote {
+        file_name,
+        frontmatter,
+        content: format!("---\n{}\n---\n\n{}", yaml.trim_end(), body),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(source: &str, answers: &[(&str, Value)]) -> Result<String, String> {
+        let context = TemplateContext {
+            title: "제목".to_string(),
+            container: "프로젝트".to_string(),
+            answers: HashMap::new(),
+            now: Local::now(),
+        };
+        let mut renderer = Renderer {
+            vault_path: Path::new("."),
+            context: &context,
+            values: answers.iter().map(|(k, v)| (k.to_string(), v.clone())).collect(),
+            uuid: uuid::Uuid::new_v4().to_string(),
+            includes: Vec::new(),
+        };
+        renderer.render_str(source)
+    }
+
+    #[test]
+    fn test_conditionals_and_loops() {
+        let people = Value::List(vec!["김".to_string(), "이".to_string()]);
+        let source = "# {{title}} ({{container}})\n{{#if people}}\n## 참석자\n{{#each people}}\n- {{this}}\n{{/each}}\n{{else}}\n참석자 없음\n{{/if}}\n끝\n";
+        assert_eq!(render(source, &[("people", people)]).unwrap(), "# 제목 (프로젝트)\n## 참석자\n- 김\n- 이\n끝\n");
+        assert_eq!(render(source, &[("people", Value::List(vec![]))]).unwrap(), "# 제목 (프로젝트)\n참석자 없음\n끝\n");
+        assert_eq!(render("{{#if x}}예{{/if}}!", &[("x", Value::Text("1".into()))]).unwrap(), "예!");
+
+        assert!(render("{{#if x}}", &[("x", Value::Text("1".into()))]).unwrap_err().contains("Unclosed"));
+        assert!(render("{{/each}}", &[]).unwrap_err().contains("Unexpected"));
+        assert!(render("{{missing}}", &[]).unwrap_err().contains("Unknown template variable"));
+        assert!(render("{{this}}", &[]).is_err());
+    }
+
+    #[test]
+    fn test_date_patterns() {
+        assert_eq!(date_format("YYYY-MM-DD HH:mm"), "%Y-%m-%d %H:%M");
+        assert_eq!(date_format("[Week] D/M ddd"), "Week %-d/%-m %a");
+        assert_eq!(date_format("100%"), "100%%");
+        assert_eq!(date_format("GGGG-[W]WW"), "%G-W%V");
+    }
+}
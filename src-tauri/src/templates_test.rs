@@ -0,0 +1,123 @@
+// Vault templates - 변수/프롬프트/조건/반복/포함 렌더링과 유효한 frontmatter 생성 검증
+
+#[cfg(test)]
+mod templates_tests {
+    use crate::frontmatter::ids::is_note_id;
+    use crate::frontmatter::types::NoteType;
+    use crate::templates::{list_templates, preview_template, render_template, templates_dir, TemplateContext};
+    use chrono::{Local, TimeZone};
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const MEETING: &str = r#"---
+template:
+  description: 주간 회의록
+  file_name: "{{date:YYMMDD}} {{title}}"
+  prompts:
+    - name: participants
+      label: 참석자
+      multiple: true
+    - name: project
+      required: true
+    - name: kind
+      options: [정기, 임시]
+      default: 정기
+type: MTG
+title: "{{project}} {{title}}"
+date: "{{date:YYYY-MM-DD HH:mm}}"
+participants: "{{participants}}"
+cssclasses: [mtg-type]
+---
+# {{title}} ({{container}}, {{kind}})
+{{#if participants}}
+## 참석자
+{{#each participants}}
+- [[{{this}}]]
+{{/each}}
+{{else}}
+참석자 미정
+{{/if}}
+
+{{> partials/서명}}
+"#;
+
+    fn context(answers: &[(&str, &str)]) -> TemplateContext {
+        TemplateContext {
+            title: "스프린트 회고".to_string(),
+            container: "개발팀".to_string(),
+            answers: answers.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<_, _>>(),
+            now: Local.with_ymd_and_hms(2026, 3, 9, 14, 5, 0).unwrap(),
+        }
+    }
+
+    fn setup() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = templates_dir(temp_dir.path());
+        fs::create_dir_all(dir.join("partials")).unwrap();
+        fs::write(dir.join("회의.md"), MEETING).unwrap();
+        fs::write(dir.join("partials").join("서명.md"), "작성: {{date}}\n").unwrap();
+        fs::write(dir.join("순환.md"), "{{> 순환}}").unwrap();
+        fs::write(dir.join("깨진 값.md"), "---\ntype: NOTE\nstate: {workflow: draft, confidence: unverified, maturity: 9}\n---\n").unwrap();
+        fs::write(dir.join("note-template.yaml"), "templates: {}").unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_render_meeting_template() {
+        let temp_dir = setup();
+        let vault = temp_dir.path();
+
+        let names: Vec<String> = list_templates(vault).into_iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["partials/서명", "깨진 값", "순환", "회의"]);
+
+        let note = render_template(vault, "회의", &context(&[("participants", "김철수, 이영희"), ("project", "알파")]), |_| false).unwrap();
+        assert_eq!(note.file_name, "260309 스프린트 회고");
+        assert_eq!(note.frontmatter.note_type, NoteType::MTG);
+        assert_eq!(note.frontmatter.title, "알파 스프린트 회고");
+        assert_eq!(note.frontmatter.participants, Some(vec!["김철수".to_string(), "이영희".to_string()]));
+        assert_eq!(note.frontmatter.date.as_deref(), Some("2026-03-09 14:05"));
+        assert!(is_note_id(&note.frontmatter.id));
+        assert!(!note.content.contains("template:"), "헤더는 노트에 복사하지 않음");
+        assert!(note.content.starts_with(&format!("---\nid: '{}'\n", note.frontmatter.id)), "{}", note.content);
+        assert!(note.content.ends_with("---\n\n# 스프린트 회고 (개발팀, 정기)\n## 참석자\n- [[김철수]]\n- [[이영희]]\n\n작성: 2026-03-09\n"), "{}", note.content);
+
+        let empty = render_template(vault, "회의", &context(&[("project", "알파")]), |_| false).unwrap();
+        assert!(empty.content.contains("참석자 미정\n"));
+        assert_eq!(empty.frontmatter.participants, Some(vec![]));
+    }
+
+    #[test]
+    fn test_template_errors() {
+        let temp_dir = setup();
+        let vault = temp_dir.path();
+
+        let missing = render_template(vault, "회의", &context(&[]), |_| false).unwrap_err();
+        assert!(missing.contains("Missing answer for prompt: project"), "{}", missing);
+        let invalid = render_template(vault, "회의", &context(&[("project", "a"), ("kind", "번개")]), |_| false).unwrap_err();
+        assert!(invalid.contains("Invalid answer"), "{}", invalid);
+        assert!(render_template(vault, "순환", &context(&[]), |_| false).unwrap_err().contains("cycle"));
+        assert!(render_template(vault, "깨진 값", &context(&[]), |_| false).unwrap_err().contains("schema"));
+        assert!(render_template(vault, "../회의", &context(&[]), |_| false).unwrap_err().contains("Invalid template name"));
+    }
+
+    #[test]
+    fn test_preview_does_not_claim_id() {
+        let temp_dir = setup();
+        let vault = temp_dir.path();
+        fs::write(templates_dir(vault).join("고정 id.md"), "---
+id: \"20010101000001\"
+type: NOTE
+---
+본문
+").unwrap();
+
+        let preview = || preview_template(vault, "고정 id", &context(&[]), |_| false).unwrap().frontmatter.id;
+        assert_eq!(preview(), "20010101000001");
+        assert_eq!(preview(), "20010101000001", "미리보기는 id를 예약하지 않음");
+
+        let note = render_template(vault, "고정 id", &context(&[]), |_| false).unwrap();
+        assert_eq!(note.frontmatter.id, "20010101000001");
+        assert_ne!(preview(), "20010101000001", "생성 후에는 다른 id");
+    }
+}
@@ -0,0 +1,32 @@
+// 테스트 공용 도우미 - 인덱스 디렉토리 정리 가드, 노트 작성, 재색인된 인덱스 열기
+
+use crate::search::SearchIndex;
+use std::fs;
+use std::path::Path;
+
+/// Removes the local (out-of-vault) index directory when the test finishes
+pub struct IndexDirGuard(pub String);
+
+impl Drop for IndexDirGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(SearchIndex::get_index_dir(&self.0));
+    }
+}
+
+/// Writes a `type: NOTE` note with the given body
+pub fn write_note(path: &Path, body: &str) {
+    fs::write(path, format!("---\ntype: NOTE\n---\n\n{}\n", body)).unwrap();
+}
+
+/// Opens and fully reindexes the vault; keep the guard alive for the test's duration
+pub fn open_index(vault: &Path) -> (SearchIndex, IndexDirGuard) {
+    let vault_str = path_str(vault);
+    let guard = IndexDirGuard(vault_str.clone());
+    let index = SearchIndex::new(&vault_str).unwrap();
+    index.full_reindex().unwrap();
+    (index, guard)
+}
+
+pub fn path_str(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
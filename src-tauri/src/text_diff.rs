@@ -0,0 +1,147 @@
+//! Line-based unified diff for previews (dry-run renames, bulk edits)
+//!
+//! Common prefix/suffix lines are trimmed first; the remaining middle is diffed with an
+//! LCS table. Very large middles fall back to a single replace hunk to bound memory.
+
+const CONTEXT_LINES: usize = 3;
+/// Max LCS table cells before falling back to a replace hunk
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Line operations turning `old` into `new`, as (op, old index, new index)
+fn diff_ops(old: &[&str], new: &[&str]) -> Vec<(Op, usize, usize)> {
+    let prefix = old.iter().zip(new.iter()).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<(Op, usize, usize)> = (0..prefix).map(|i| (Op::Equal, i, i)).collect();
+
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_LCS_CELLS {
+        ops.extend((0..old_mid.len()).map(|i| (Op::Delete, prefix + i, prefix)));
+        ops.extend((0..new_mid.len()).map(|j| (Op::Insert, prefix + old_mid.len(), prefix + j)));
+    } else {
+        // lcs[i][j] = LCS length of old_mid[i..] and new_mid[j..]
+        let (n, m) = (old_mid.len(), new_mid.len());
+        let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old_mid[i] == new_mid[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && old_mid[i] == new_mid[j] {
+                ops.push((Op::Equal, prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+                ops.push((Op::Delete, prefix + i, prefix + j));
+                i += 1;
+            } else {
+                ops.push((Op::Insert, prefix + i, prefix + j));
+                j += 1;
+            }
+        }
+    }
+
+    let old_off = old.len() - suffix;
+    let new_off = new.len() - suffix;
+    ops.extend((0..suffix).map(|k| (Op::Equal, old_off + k, new_off + k)));
+    ops
+}
+
+/// Unified diff of `old` -> `new` with `a/` and `b/` headers. Empty string when identical.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_ops(&old_lines, &new_lines);
+
+    let mut out = format!("--- a/{}\n+++ b/{}\n", path, path);
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _, _))| *op != Op::Equal)
+        .map(|(k, _)| k)
+        .collect();
+    if changed.is_empty() {
+        // Only trailing newline differences
+        return out;
+    }
+
+    // Group changes closer than 2*context into one hunk
+    let mut k = 0;
+    while k < changed.len() {
+        let mut last = k;
+        while last + 1 < changed.len() && changed[last + 1] - changed[last] <= 2 * CONTEXT_LINES {
+            last += 1;
+        }
+        let start = changed[k].saturating_sub(CONTEXT_LINES);
+        let end = (changed[last] + CONTEXT_LINES + 1).min(ops.len());
+        let hunk = &ops[start..end];
+
+        let old_count = hunk.iter().filter(|(op, _, _)| *op != Op::Insert).count();
+        let new_count = hunk.iter().filter(|(op, _, _)| *op != Op::Delete).count();
+        let old_start = hunk[0].1 + usize::from(old_count > 0);
+        let new_start = hunk[0].2 + usize::from(new_count > 0);
+        out.push_str(&format!("@@ -{},{} +{},{} @@\n", old_start, old_count, new_start, new_count));
+        for (op, i, j) in hunk {
+            match op {
+                Op::Equal => out.push_str(&format!(" {}\n", old_lines[*i])),
+                Op::Delete => out.push_str(&format!("-{}\n", old_lines[*i])),
+                Op::Insert => out.push_str(&format!("+{}\n", new_lines[*j])),
+            }
+        }
+        k = last + 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_line_change_has_context() {
+        let old = "a\nb\nc\n[[옛]]\nd\ne\nf\ng\n";
+        let new = "a\nb\nc\n[[새]]\nd\ne\nf\ng\n";
+        let diff = unified_diff("노트.md", old, new);
+        assert_eq!(
+            diff,
+            "--- a/노트.md\n+++ b/노트.md\n@@ -1,7 +1,7 @@\n a\n b\n c\n-[[옛]]\n+[[새]]\n d\n e\n f\n"
+        );
+    }
+
+    #[test]
+    fn test_distant_changes_make_separate_hunks() {
+        let old: String = (0..20).map(|i| format!("{}\n", i)).collect();
+        let new = old.replace("\n2\n", "\nX\n").replace("\n17\n", "\nY\n");
+        let diff = unified_diff("n.md", &old, &new);
+        assert_eq!(diff.matches("@@ -").count(), 2);
+    }
+
+    #[test]
+    fn test_insertions_and_identical() {
+        assert!(unified_diff("n.md", "same", "same").is_empty());
+        let diff = unified_diff("n.md", "a\nc\n", "a\nb\nc\n");
+        assert!(diff.contains("@@ -1,2 +1,3 @@\n a\n+b\n c\n"));
+    }
+}
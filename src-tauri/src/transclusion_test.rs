@@ -0,0 +1,91 @@
+// Transclusion - ![[embed]] 확장, 순환/깊이 제한, 의존성 목록 및 역의존 노트 검증
+
+#[cfg(test)]
+mod transclusion_tests {
+    use crate::search::transclusion::EmbedKind;
+    use crate::test_common::{open_index, path_str, write_note};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_expands_note_section_and_attachment() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let att = vault.join("보고서_att");
+        fs::create_dir_all(&att).unwrap();
+        fs::write(att.join("도면.pdf"), b"%PDF").unwrap();
+        write_note(&vault.join("보고서.md"), "서문\n![[요약]]\n![[실험#결과]]\n![[도면.pdf]]\n![[없는노트]]");
+        write_note(&vault.join("요약.md"), "요약 본문");
+        write_note(&vault.join("실험.md"), "## 방법\n절차\n## 결과\n성공");
+
+        let (index, _guard) = open_index(vault);
+        let result = index.resolve_transclusions(&path_str(&vault.join("보고서.md")), None).unwrap();
+
+        assert!(result.content.contains("요약 본문"));
+        assert!(result.content.contains("## 결과\n성공"));
+        assert!(!result.content.contains("절차"), "섹션 외 내용은 포함되지 않아야 함");
+        assert!(result.content.contains("data-embed-kind=\"pdf\""));
+        assert!(result.content.contains("data-embed-kind=\"missing\""));
+
+        let kinds: Vec<EmbedKind> = result.embeds.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, vec![EmbedKind::Note, EmbedKind::Section, EmbedKind::Pdf, EmbedKind::Missing]);
+        assert_eq!(result.dependencies.len(), 3, "요약, 실험, 도면.pdf: {:?}", result.dependencies);
+    }
+
+    #[test]
+    fn test_cycle_and_depth_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        write_note(&vault.join("A.md"), "A 시작 ![[B]]");
+        write_note(&vault.join("B.md"), "B 시작 ![[C]]");
+        write_note(&vault.join("C.md"), "C 시작 ![[A]]");
+
+        let (index, _guard) = open_index(vault);
+        let a = path_str(&vault.join("A.md"));
+
+        let result = index.resolve_transclusions(&a, None).unwrap();
+        assert!(result.content.contains("B 시작") && result.content.contains("C 시작"));
+        assert_eq!(result.embeds.last().unwrap().kind, EmbedKind::Cycle);
+        assert_eq!(result.embeds.last().unwrap().target_path.as_deref(), Some(a.as_str()));
+
+        let shallow = index.resolve_transclusions(&a, Some(1)).unwrap();
+        assert!(shallow.content.contains("B 시작"));
+        assert!(!shallow.content.contains("C 시작"));
+        assert_eq!(shallow.embeds[1].kind, EmbedKind::DepthLimit);
+    }
+
+    #[test]
+    fn test_embed_dependents_are_transitive_and_ignore_plain_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        write_note(&vault.join("기반.md"), "공통 내용");
+        write_note(&vault.join("중간.md"), "![[기반]]");
+        write_note(&vault.join("최상위.md"), "![[중간]]");
+        write_note(&vault.join("참조만.md"), "[[기반]]");
+
+        let (index, _guard) = open_index(vault);
+        let dependents = index.embed_dependents(&path_str(&vault.join("기반.md"))).unwrap();
+
+        assert_eq!(dependents, vec![path_str(&vault.join("중간.md")), path_str(&vault.join("최상위.md"))]);
+    }
+
+    #[test]
+    fn test_modified_attachment_notifies_embedding_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        let att = vault.join("보고서_att");
+        fs::create_dir_all(&att).unwrap();
+        fs::write(att.join("도표.png"), b"v1").unwrap();
+        fs::write(vault.join("다른.png"), b"v1").unwrap();
+        write_note(&vault.join("보고서.md"), "![[도표.png]]");
+        write_note(&vault.join("요약.md"), "![[보고서]]");
+        write_note(&vault.join("링크만.md"), "[[다른.png]]");
+
+        let (index, _guard) = open_index(vault);
+        fs::write(att.join("도표.png"), b"v2").unwrap();
+        let dependents = index.embed_dependents(&path_str(&att.join("도표.png"))).unwrap();
+
+        assert_eq!(dependents, vec![path_str(&vault.join("보고서.md")), path_str(&vault.join("요약.md"))], "첨부를 임베드한 노트와 그 노트를 임베드한 노트");
+        assert!(index.embed_dependents(&path_str(&vault.join("다른.png"))).unwrap().is_empty(), "일반 링크는 제외");
+    }
+}
@@ -0,0 +1,84 @@
+// Vault schemas - 내장 타입별 스키마, .notology/schemas 확장/대체, 필수 필드 검증
+
+#[cfg(test)]
+mod vault_schema_tests {
+    use crate::frontmatter::schemas::{get_schema_for_type, schema_properties, VaultSchemas};
+    use crate::frontmatter::FrontmatterParser;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const NOTE_TYPES: [&str; 15] = [
+        "NOTE", "MTG", "PAPER", "THEO", "TASK", "LIT", "EVENT", "CONTACT", "CONTAINER", "ADM", "OFA", "SEM", "DATA",
+        "SETUP", "SKETCH",
+    ];
+
+    fn note(note_type: &str, extra: &str) -> crate::frontmatter::types::Frontmatter {
+        FrontmatterParser::parse_yaml(&format!(
+            "id: \"20260101090000\"\ntitle: t\ntype: {}\ncreated: \"2026-01-01\"\nmodified: \"2026-01-01\"\n{}",
+            note_type, extra
+        ))
+        .unwrap()
+    }
+
+    fn write_schema(vault: &Path, name: &str, json: &str) {
+        let dir = vault.join(".notology").join("schemas");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(name), json).unwrap();
+    }
+
+    #[test]
+    fn test_every_builtin_type_has_own_schema() {
+        for note_type in NOTE_TYPES {
+            let schema = get_schema_for_type(note_type);
+            assert_eq!(schema["title"], format!("{} Note Schema", note_type), "{} 전용 스키마", note_type);
+            assert!(FrontmatterParser::validate(&note(note_type, "")).unwrap().is_empty(), "{}", note_type);
+        }
+        let event = schema_properties(get_schema_for_type("EVENT"));
+        assert!(event.contains(&"location".to_string()) && event.contains(&"participants".to_string()));
+        assert!(schema_properties(get_schema_for_type("LIT")).contains(&"publisher".to_string()));
+    }
+
+    #[test]
+    fn test_vault_extension_requires_team_field() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        // 모든 타입에 project_code 필수
+        write_schema(
+            vault,
+            "team.json",
+            r#"{"required": ["project_code"], "properties": {"project_code": {"type": "string", "pattern": "^P-[0-9]+$"}}}"#,
+        );
+        // 파일 이름이 타입이면 그 타입에만 적용
+        write_schema(vault, "MTG.json", r#"{"required": ["participants"]}"#);
+        let schemas = VaultSchemas::load(vault).unwrap();
+
+        let errors = FrontmatterParser::validate_with(&note("NOTE", ""), &schemas).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("project_code"));
+        assert!(FrontmatterParser::validate_with(&note("NOTE", "project_code: P-12\n"), &schemas).unwrap().is_empty());
+        assert!(!FrontmatterParser::validate_with(&note("NOTE", "project_code: X\n"), &schemas).unwrap().is_empty());
+
+        assert_eq!(FrontmatterParser::validate_with(&note("MTG", "project_code: P-1\n"), &schemas).unwrap().len(), 1);
+        assert!(schema_properties(&schemas.schema_for_type("NOTE")).contains(&"project_code".to_string()));
+    }
+
+    #[test]
+    fn test_replace_mode_and_invalid_schema_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault = temp_dir.path();
+        write_schema(
+            vault,
+            "data.json",
+            r#"{"x-notology-types": ["DATA"], "x-notology-mode": "replace", "type": "object", "required": ["dataset"]}"#,
+        );
+        let schemas = VaultSchemas::load(vault).unwrap();
+        assert_eq!(schemas.schema_for_type("DATA")["required"][0], "dataset");
+        assert_eq!(schemas.schema_for_type("NOTE"), *get_schema_for_type("NOTE"));
+
+        write_schema(vault, "broken.json", r#"{"type": 12}"#);
+        assert!(VaultSchemas::load(vault).unwrap_err().contains("broken.json"));
+        let missing = VaultSchemas::load(&vault.join("없음")).unwrap();
+        assert_eq!(missing.schema_for_type("DATA"), *get_schema_for_type("DATA"));
+    }
+}
@@ -0,0 +1,104 @@
+// Workflow state machine - 허용되지 않은 전환 거부, 가드 조건, 타입별 규칙, state_history 기록 검증
+
+#[cfg(test)]
+mod workflow_tests {
+    use crate::frontmatter::workflow::{StateChange, HISTORY_KEY};
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn note(workflow: &str, confidence: &str, note_type: &str) -> String {
+        format!(
+            "---\nid: \"20260301090000\"\ntitle: 계획\ntype: {}\nstate:\n  workflow: {}\n  confidence: {}\n  maturity: 3\ncreated: 2026-03-01T09:00:00+09:00\nmodified: 2026-03-01T09:00:00+09:00\n---\n\n본문\n",
+            note_type, workflow, confidence
+        )
+    }
+
+    fn update(path: &Path, workflow: &str, confidence: &str, note_type: &str) -> Result<(), String> {
+        let content = fs::read_to_string(path).unwrap();
+        let yaml = content.trim_start_matches("---\n").split("\n---").next().unwrap().to_string();
+        let mut map: serde_yaml::Mapping = serde_yaml::from_str(&yaml).unwrap();
+        let mut state: serde_yaml::Mapping = serde_yaml::from_str(&format!("workflow: {}\nconfidence: {}\nmaturity: 3", workflow, confidence)).unwrap();
+        map.insert("type".into(), note_type.into());
+        std::mem::swap(map.get_mut("state").unwrap().as_mapping_mut().unwrap(), &mut state);
+        let yaml = serde_yaml::to_string(&map).unwrap();
+        crate::update_note_frontmatter(path.to_string_lossy().to_string(), yaml)
+    }
+
+    fn history(path: &Path) -> Vec<StateChange> {
+        let content = fs::read_to_string(path).unwrap();
+        let yaml = content.trim_start_matches("---\n").split("\n---").next().unwrap().to_string();
+        let map: serde_yaml::Mapping = serde_yaml::from_str(&yaml).unwrap();
+        map.get(HISTORY_KEY).map(|h| serde_yaml::from_value(h.clone()).unwrap()).unwrap_or_default()
+    }
+
+    fn vault() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".notology")).unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_invalid_transition_rejected() {
+        let temp_dir = vault();
+        let path = temp_dir.path().join("계획.md");
+        fs::write(&path, note("draft", "unverified", "NOTE")).unwrap();
+
+        let err = update(&path, "archived", "unverified", "NOTE").unwrap_err();
+        assert!(err.contains("not allowed"), "{}", err);
+        assert_eq!(fs::read_to_string(&path).unwrap(), note("draft", "unverified", "NOTE"), "거부 시 파일 변경 없음");
+
+        let err = update(&path, "in_progress", "unverified", "NOTE").unwrap_err();
+        assert!(err.contains("Unknown workflow state"), "{}", err);
+    }
+
+    #[test]
+    fn test_guard_and_history() {
+        let temp_dir = vault();
+        let path = temp_dir.path().join("계획.md");
+        fs::write(&path, note("review", "unverified", "NOTE")).unwrap();
+
+        let err = update(&path, "final", "unverified", "NOTE").unwrap_err();
+        assert!(err.contains("requires confidence: verified"), "{}", err);
+        assert!(history(&path).is_empty());
+
+        update(&path, "final", "verified", "NOTE").unwrap();
+        let entries = history(&path);
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].from.as_str(), entries[0].to.as_str()), ("review", "final"));
+        assert_eq!(entries[0].host, crate::vault_lock::get_hostname());
+        assert!(chrono::DateTime::parse_from_str(&entries[0].at, "%Y-%m-%dT%H:%M:%S%:z").is_ok(), "{}", entries[0].at);
+
+        // 에디터가 이력 없이 보내도 기존 이력은 유지되고, 상태가 같으면 추가되지 않음
+        update(&path, "final", "verified", "NOTE").unwrap();
+        assert_eq!(history(&path).len(), 1);
+        update(&path, "in-progress", "verified", "NOTE").unwrap();
+        let entries = history(&path);
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[1].from.as_str(), entries[1].to.as_str()), ("final", "in-progress"));
+        assert!(fs::read_to_string(&path).unwrap().ends_with("---\n\n본문\n"));
+    }
+
+    #[test]
+    fn test_per_type_rules_from_vault() {
+        let temp_dir = vault();
+        fs::write(
+            temp_dir.path().join(".notology").join("workflow.json"),
+            r#"{"types": {"PAPER": [{"from": ["*"], "to": "archived"}]}}"#,
+        )
+        .unwrap();
+        let paper = temp_dir.path().join("논문.md");
+        fs::write(&paper, note("draft", "unverified", "PAPER")).unwrap();
+        let memo = temp_dir.path().join("메모.md");
+        fs::write(&memo, note("draft", "unverified", "NOTE")).unwrap();
+
+        update(&paper, "archived", "unverified", "PAPER").unwrap();
+        assert_eq!(history(&paper).len(), 1);
+        assert!(update(&memo, "archived", "unverified", "NOTE").is_err(), "다른 타입은 기본 규칙");
+
+        // 잘못된 설정 파일은 저장되지 않음
+        let bad: crate::frontmatter::workflow::WorkflowConfig =
+            serde_json::from_str(r#"{"types": {"PAPER": [{"from": ["draft"], "to": "done"}]}}"#).unwrap();
+        assert!(crate::save_workflow_config(temp_dir.path().to_string_lossy().to_string(), bad).is_err());
+    }
+}
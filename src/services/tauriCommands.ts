@@ -1,8 +1,11 @@
 import { invoke } from '@tauri-apps/api/core';
 import type {
   FileNode, FileContent, SearchResult, NoteMetadata, NoteFilter, AttachmentInfo,
-  CalendarMemo, LockAcquireResult, NasPlatformInfo, GraphData,
+  CalendarMemo, Period, PeriodicNotesConfig, PeriodicNote, LockAcquireResult, NasPlatformInfo, GraphData, GraphExportFormat,
+  RelatedNote, RelationDirection, LocalGraphData, LocalGraphFilter,
+  BrokenLink, UnlinkedMention, MentionSelection, TransclusionResult, PathChangePreview, PathChangeResult,
 } from '../types';
+import type { FrontmatterEdit, BulkEditResult, TagRefactor, TagMapping, LegacyTagMigration, FrontmatterFixReport, DuplicateNoteId, DuplicateIdFix, TemplateInfo, RenderedNote, TypeConversion, NoteType, LintReport, WorkflowConfig, Suggestion } from '../types/frontmatter';
 
 // Types not in ../types - defined locally
 export interface FrontmatterOnly {
@@ -51,7 +54,7 @@ export const fileCommands = {
     invoke<void>('delete_folder', { path }),
 
   moveFile: (oldPath: string, newPath: string) =>
-    invoke<void>('move_file', { oldPath, newPath }),
+    invoke<PathChangeResult>('move_file', { oldPath, newPath }),
 
   ensureDirectory: (path: string) =>
     invoke<void>('ensure_directory', { path }),
@@ -87,18 +90,110 @@ export const noteCommands = {
   createNoteWithTemplate: (dirPath: string, fileName: string, frontmatterYaml: string, body: string) =>
     invoke<string>('create_note_with_template', { dirPath, fileName, frontmatterYaml, body }),
 
+  listNoteTemplates: (vaultPath: string) =>
+    invoke<TemplateInfo[]>('list_note_templates', { vaultPath }),
+
+  /** Preview of createNoteFromTemplate; answers are keyed by prompt name */
+  renderNoteTemplate: (vaultPath: string, template: string, dirPath: string, title: string, answers: Record<string, string>) =>
+    invoke<RenderedNote>('render_note_template', { vaultPath, template, dirPath, title, answers }),
+
+  createNoteFromTemplate: (vaultPath: string, template: string, dirPath: string, title: string, answers: Record<string, string>) =>
+    invoke<string>('create_note_from_template', { vaultPath, template, dirPath, title, answers }),
+
   deleteNote: (notePath: string) =>
     invoke<void>('delete_note', { notePath }),
 
   moveNote: (notePath: string, newDir: string) =>
-    invoke<string>('move_note', { notePath, newDir }),
+    invoke<PathChangeResult>('move_note', { notePath, newDir }),
 
   renameFileWithLinks: (filePath: string, newName: string, vaultPath: string) =>
-    invoke<string>('rename_file_with_links', { filePath, newName, vaultPath }),
+    invoke<PathChangeResult>('rename_file_with_links', { filePath, newName, vaultPath }),
+
+  /** Dry runs: moves, per-file link diffs and collisions, nothing written */
+  previewRenameFileWithLinks: (filePath: string, newName: string, vaultPath: string) =>
+    invoke<PathChangePreview>('preview_rename_file_with_links', { filePath, newName, vaultPath }),
+
+  previewMoveNote: (notePath: string, newDir: string, vaultPath: string) =>
+    invoke<PathChangePreview>('preview_move_note', { notePath, newDir, vaultPath }),
+
+  previewMoveFile: (oldPath: string, newPath: string, vaultPath: string) =>
+    invoke<PathChangePreview>('preview_move_file', { oldPath, newPath, vaultPath }),
+
+  previewMoveFolder: (folderPath: string, newParent: string, vaultPath: string) =>
+    invoke<PathChangePreview>('preview_move_folder', { folderPath, newParent, vaultPath }),
+
+  /** Folder operations rewrite [[folder/Note]] links to everything inside; folder notes and _att folders follow */
+  moveFolder: (folderPath: string, newParent: string, vaultPath: string) =>
+    invoke<PathChangeResult>('move_folder', { folderPath, newParent, vaultPath }),
+
+  renameFolder: (folderPath: string, newName: string, vaultPath: string) =>
+    invoke<PathChangeResult>('rename_folder', { folderPath, newName, vaultPath }),
+
+  /** Merge source into target; source goes to .notology/backups. heading = section to insert under */
+  mergeNotes: (sourcePath: string, targetPath: string, heading: string | null, vaultPath: string) =>
+    invoke<PathChangeResult>('merge_notes', { sourcePath, targetPath, heading, vaultPath }),
+
+  previewMergeNotes: (sourcePath: string, targetPath: string, heading: string | null, vaultPath: string) =>
+    invoke<PathChangePreview>('preview_merge_notes', { sourcePath, targetPath, heading, vaultPath }),
 
+  /** One note per heading section; returns the new note paths */
+  splitNote: (notePath: string, headingLevel: number, vaultPath: string) =>
+    invoke<string[]>('split_note', { notePath, headingLevel, vaultPath }),
+
+  /** Rejects workflow changes the vault's workflow rules don't allow; changes are added to state_history */
   updateFrontmatter: (notePath: string, newFrontmatterYaml: string) =>
     invoke<void>('update_note_frontmatter', { notePath, newFrontmatterYaml }),
 
+  getWorkflowConfig: (vaultPath: string) =>
+    invoke<WorkflowConfig>('get_workflow_config', { vaultPath }),
+
+  saveWorkflowConfig: (vaultPath: string, config: WorkflowConfig) =>
+    invoke<void>('save_workflow_config', { vaultPath, config }),
+
+  /** Change a note's type, mapping type-specific fields; unmapped fields go under `extra` */
+  convertNoteType: (path: string, newType: NoteType) =>
+    invoke<TypeConversion>('convert_note_type', { path, newType }),
+
+  /** Apply edits to every note matching filter (or paths); one index commit, backups before write */
+  bulkUpdateFrontmatter: (
+    target: { filter?: NoteFilter; paths?: string[] },
+    edits: FrontmatterEdit[],
+    dryRun: boolean,
+    vaultPath: string,
+  ) =>
+    invoke<BulkEditResult>('bulk_update_frontmatter', {
+      filter: target.filter ?? null,
+      paths: target.paths ?? null,
+      edits,
+      dryRun,
+      vaultPath,
+    }),
+
+  /** Rename, merge, move or delete a tag in every note that uses it */
+  refactorTags: (refactor: TagRefactor, dryRun: boolean, vaultPath: string) =>
+    invoke<BulkEditResult>('refactor_tags', { refactor, dryRun, vaultPath }),
+
+  getTagMapping: (vaultPath: string) =>
+    invoke<TagMapping>('get_tag_mapping', { vaultPath }),
+
+  saveTagMapping: (vaultPath: string, mapping: TagMapping) =>
+    invoke<void>('save_tag_mapping', { vaultPath, mapping }),
+
+  /** Move legacy list-style tags into facets; pass mapping to preview unsaved rules */
+  migrateLegacyTags: (mapping: TagMapping | null, dryRun: boolean, vaultPath: string) =>
+    invoke<LegacyTagMigration>('migrate_legacy_tags', { mapping, dryRun, vaultPath }),
+
+  /** paths = null fixes the whole vault; dateFormat is a chrono format, default %Y-%m-%dT%H:%M:%S%:z */
+  fixFrontmatter: (paths: string[] | null, dateFormat: string | null, dryRun: boolean, vaultPath: string) =>
+    invoke<FrontmatterFixReport>('fix_frontmatter', { paths, dateFormat, dryRun, vaultPath }),
+
+  findDuplicateNoteIds: (vaultPath: string) =>
+    invoke<DuplicateNoteId[]>('find_duplicate_note_ids', { vaultPath }),
+
+  /** Oldest note keeps a duplicated id; the others get new ids */
+  fixDuplicateNoteIds: (dryRun: boolean, vaultPath: string) =>
+    invoke<DuplicateIdFix>('fix_duplicate_note_ids', { dryRun, vaultPath }),
+
   importAttachment: (sourcePath: string, notePath: string) =>
     invoke<string>('import_attachment', { sourcePath, notePath }),
 
@@ -146,6 +241,42 @@ export const searchCommands = {
 
   getGraphData: (containerPath?: string | null, includeAttachments?: boolean) =>
     invoke<GraphData>('get_graph_data', { containerPath: containerPath ?? null, includeAttachments: includeAttachments ?? false }),
+
+  /** Frontmatter id -> note path, for [[id:...]] links */
+  getNoteIds: () =>
+    invoke<Record<string, string>>('get_note_ids'),
+
+  /** Unresolved wiki-links for one note, or the whole vault when path is omitted */
+  getBrokenLinks: (path?: string | null) =>
+    invoke<BrokenLink[]>('get_broken_links', { path: path ?? null }),
+
+  /** Note body with ![[embeds]] expanded; re-run when an "embed-dependency-changed" event lists this note */
+  resolveTransclusions: (path: string, maxDepth?: number) =>
+    invoke<TransclusionResult>('resolve_transclusions', { path, maxDepth: maxDepth ?? null }),
+
+  /** Plain-text mentions of a note's title/aliases in notes that don't link to it */
+  findUnlinkedMentions: (path: string) =>
+    invoke<UnlinkedMention[]>('find_unlinked_mentions', { path }),
+
+  /** Turn selected mentions into wiki-links; returns the number of links created */
+  linkUnlinkedMentions: (targetPath: string, mentions: MentionSelection[]) =>
+    invoke<number>('link_unlinked_mentions', { targetPath, mentions }),
+
+  /** Tag, relation, workflow and quality suggestions with their evidence; limit caps related notes (default 5) */
+  generateSuggestions: (path: string, limit?: number) =>
+    invoke<Suggestion[]>('generate_suggestions', { path, limit: limit ?? null }),
+
+  /** N-hop neighborhood of a single note, capped at filters.max_nodes */
+  getLocalGraph: (path: string, depth?: number, filters?: LocalGraphFilter) =>
+    invoke<LocalGraphData>('get_local_graph', { path, depth: depth ?? 1, filters: filters ?? null }),
+
+  /** Typed relation query, e.g. notes that refute X or (transitively) everything derived from Y */
+  queryRelations: (path: string, relationType?: string | null, direction?: RelationDirection, transitive?: boolean) =>
+    invoke<RelatedNote[]>('query_relations', { path, relationType: relationType ?? null, direction: direction ?? 'incoming', transitive: transitive ?? false }),
+
+  /** Export graph to GraphML / GEXF / DOT. Returns the written file path. */
+  exportGraph: (format: GraphExportFormat, outputPath: string, containerPath?: string | null, includeAttachments?: boolean) =>
+    invoke<string>('export_graph', { format, outputPath, containerPath: containerPath ?? null, includeAttachments: includeAttachments ?? false }),
 };
 
 // ============================================================================
@@ -171,6 +302,22 @@ export const memoCommands = {
     invoke<CalendarMemo[]>('collect_calendar_memos', { containerPath }),
 };
 
+// ============================================================================
+// Periodic Note Commands
+// ============================================================================
+
+export const periodicNoteCommands = {
+  getConfig: (vaultPath: string) =>
+    invoke<PeriodicNotesConfig>('get_periodic_notes_config', { vaultPath }),
+
+  saveConfig: (vaultPath: string, config: PeriodicNotesConfig) =>
+    invoke<void>('save_periodic_notes_config', { vaultPath, config }),
+
+  /** Note for the period containing date (default today); create=false only looks it up */
+  open: (vaultPath: string, period: Period, date: string | null, create: boolean) =>
+    invoke<PeriodicNote>('open_periodic_note', { vaultPath, period, date, create }),
+};
+
 // ============================================================================
 // Vault Commands
 // ============================================================================
@@ -241,8 +388,17 @@ export const frontmatterCommands = {
   parseFrontmatter: <T>(content: string) =>
     invoke<T>('parse_frontmatter', { content }),
 
-  validateFrontmatter: <T>(frontmatterJson: string) =>
-    invoke<T>('validate_frontmatter', { frontmatterJson }),
+  /** With vaultPath, the vault's .notology/schemas/*.json extensions are checked too */
+  validateFrontmatter: <T>(frontmatterJson: string, vaultPath?: string | null) =>
+    invoke<T>('validate_frontmatter', { frontmatterJson, vaultPath: vaultPath ?? null }),
+
+  /** Check every note's frontmatter; issues grouped by severity with file lines */
+  lintVault: (vaultPath: string) =>
+    invoke<LintReport>('lint_vault', { vaultPath }),
+
+  /** Effective JSON schema of a note type (built-in plus vault schemas) */
+  getNoteSchema: (noteType: NoteType, vaultPath: string) =>
+    invoke<Record<string, unknown>>('get_note_schema', { noteType, vaultPath }),
 
   frontmatterToYaml: (frontmatterJson: string) =>
     invoke<string>('frontmatter_to_yaml', { frontmatterJson }),
@@ -16,7 +16,7 @@ import { templateActions, useTemplateStore } from './zustand/templateStore';
 import { uiActions } from './zustand/uiStore';
 import { vaultConfigActions, useVaultConfigStore } from './zustand/vaultConfigStore';
 import type { RecentVault } from './zustand/vaultConfigStore';
-import type { LockAcquireResult } from '../types';
+import type { LockAcquireResult, PathChangeResult } from '../types';
 import type { FacetedTagSelection } from '../components/TagInputSection';
 import { computeLevel } from '../utils/frontmatter';
 import { findTemplateForLevel, applyTemplateVariables, applyNoteTemplateVariables } from '../utils/templates';
@@ -200,9 +200,16 @@ export async function deleteFolder(path: string) {
   refreshActions.refreshCalendar(); // Sync memos/todos
 }
 
+/** Notes the backend left untouched because they changed after the rename was planned */
+function warnSkippedLinkUpdates(result: PathChangeResult) {
+  if (result.skipped.length > 0) {
+    console.warn('Links not updated in notes changed during the rename:', result.skipped);
+  }
+}
+
 export async function moveFile(oldPath: string, newPath: string) {
   await searchCommands.removeFromIndex(oldPath).catch(() => {});
-  await fileCommands.moveFile(oldPath, newPath);
+  warnSkippedLinkUpdates(await fileCommands.moveFile(oldPath, newPath));
   hoverActions.updateFilePath(oldPath, newPath);
   await fileTreeActions.refreshFileTree();
   await searchCommands.indexNote(newPath).catch(() => {});
@@ -212,7 +219,9 @@ export async function moveFile(oldPath: string, newPath: string) {
 
 export async function moveNote(notePath: string, newDir: string): Promise<string> {
   await searchCommands.removeFromIndex(notePath).catch(() => {});
-  const newPath = await noteCommands.moveNote(notePath, newDir);
+  const result = await noteCommands.moveNote(notePath, newDir);
+  warnSkippedLinkUpdates(result);
+  const newPath = result.final_path;
   hoverActions.updateFilePath(notePath, newPath);
   await fileTreeActions.refreshFileTree();
   await searchCommands.indexNote(newPath).catch(() => {});
@@ -243,7 +252,9 @@ export async function renameFile(filePath: string, newName: string): Promise<str
   const vaultPath = useFileTreeStore.getState().vaultPath;
   if (!vaultPath) throw new Error('No vault open');
   await searchCommands.removeFromIndex(filePath).catch(() => {});
-  const newPath = await noteCommands.renameFileWithLinks(filePath, newName, vaultPath);
+  const result = await noteCommands.renameFileWithLinks(filePath, newName, vaultPath);
+  warnSkippedLinkUpdates(result);
+  const newPath = result.final_path;
   hoverActions.updateFilePathAndRefreshAll(filePath, newPath);
   await fileTreeActions.refreshFileTree();
   await searchCommands.indexNote(newPath).catch(() => {});
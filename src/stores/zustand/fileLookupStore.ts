@@ -26,6 +26,7 @@ interface FileLookupIndex {
   byName: Map<string, string>;           // fileName.toLowerCase() → fullPath
   byNameNoExt: Map<string, string>;      // fileNameWithoutExtension.toLowerCase() → fullPath
   byTitle: Map<string, string>;          // noteTitle.toLowerCase() → fullPath
+  byId: Map<string, string>;             // frontmatter id → fullPath ([[id:...]] links)
 
   // Secondary lookups for attachments
   attachmentPaths: Set<string>;          // Set of all attachment paths
@@ -51,6 +52,7 @@ interface FileLookupState {
   // Actions
   rebuildIndex: (fileTree: FileNode[]) => void;
   incrementalUpdate: (added: FileNode[], removed: string[]) => void;
+  setNoteIds: (ids: Record<string, string>) => void;
 
   // Fast lookups
   resolveNotePath: (name: string) => string | null;
@@ -69,6 +71,7 @@ function createEmptyIndex(): FileLookupIndex {
     byName: new Map(),
     byNameNoExt: new Map(),
     byTitle: new Map(),
+    byId: new Map(),
     attachmentPaths: new Set(),
     notePaths: new Set(),
     folderPaths: new Set(),
@@ -106,6 +109,8 @@ export const useFileLookupStore = create<FileLookupState>()(
     rebuildIndex: (fileTree: FileNode[]) => {
       const startTime = performance.now();
       const index = createEmptyIndex();
+      // Ids come from the search index, not the file tree
+      index.byId = get().index.byId;
       const noteCountMap = new Map<string, number>();
 
       // Single-pass tree traversal with stack (avoids recursion overhead)
@@ -257,11 +262,25 @@ export const useFileLookupStore = create<FileLookupState>()(
       set({ version: get().version + 1 });
     },
 
+    // Replace the id lookup ([[id:...]] links) with a fresh map from the search index
+    setNoteIds: (ids: Record<string, string>) => {
+      const { index } = get();
+      index.byId = new Map(
+        Object.entries(ids).map(([id, path]) => [id, path.replace(/\\/g, '/')])
+      );
+      set({ version: get().version + 1 });
+    },
+
     // O(1) note path resolution
     resolveNotePath: (name: string): string | null => {
       const { index } = get();
       const nameLower = name.toLowerCase();
 
+      // [[id:20250101120000]] resolves by frontmatter id only
+      if (nameLower.startsWith('id:')) {
+        return index.byId.get(name.slice(3).split('#')[0].trim()) || null;
+      }
+
       // Try exact match with extension
       if (nameLower.endsWith('.md')) {
         return index.byName.get(nameLower) || null;
@@ -362,6 +381,8 @@ export const fileLookupActions = {
     useFileLookupStore.getState().rebuildIndex(fileTree),
   incrementalUpdate: (added: FileNode[], removed: string[]) =>
     useFileLookupStore.getState().incrementalUpdate(added, removed),
+  setNoteIds: (ids: Record<string, string>) =>
+    useFileLookupStore.getState().setNoteIds(ids),
   resolveNotePath: (name: string) =>
     useFileLookupStore.getState().resolveNotePath(name),
   resolveAttachmentPath: (name: string, basePath?: string) =>
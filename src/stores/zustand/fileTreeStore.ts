@@ -1,6 +1,6 @@
 import { create } from 'zustand';
 import { subscribeWithSelector } from 'zustand/middleware';
-import { fileCommands } from '../../services/tauriCommands';
+import { fileCommands, searchCommands } from '../../services/tauriCommands';
 import type { FileNode } from '../../types';
 import { contentCacheActions } from './contentCacheStore';
 import { fileLookupActions } from './fileLookupStore';
@@ -61,6 +61,10 @@ export const useFileTreeStore = create<FileTreeState>()(
       // Rebuild file lookup index synchronously so newly inserted wikilinks
       // can resolve against the updated index immediately (e.g. after drag-drop)
       fileLookupActions.rebuildIndex(tree);
+      // Note ids for [[id:...]] links (index may not be ready yet; the next refresh retries)
+      searchCommands.getNoteIds()
+        .then(fileLookupActions.setNoteIds)
+        .catch(() => {});
     },
 
     // Set selected container
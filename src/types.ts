@@ -101,8 +101,21 @@ export interface NoteFilter {
   created_before?: string;
   modified_after?: string;
   modified_before?: string;
+  /** Also accepts any NoteMetricName */
   sort_by?: string;
   sort_order?: string;
+  /** Inclusive ranges, e.g. { metric: 'word_count', max: 50 } for stubs */
+  metrics?: MetricRange[];
+}
+
+export type NoteMetricName =
+  | 'word_count' | 'char_count' | 'reading_time' | 'heading_count'
+  | 'outgoing_link_count' | 'incoming_link_count' | 'attachment_count' | 'open_task_count';
+
+export interface MetricRange {
+  metric: NoteMetricName;
+  min?: number;
+  max?: number;
 }
 
 export interface NoteMetadata {
@@ -114,6 +127,17 @@ export interface NoteMetadata {
   modified: string;
   has_body: boolean;
   comment_count: number;
+  // Computed at index time; absent on optimistic patches until the next refresh
+  word_count?: number;
+  char_count?: number;
+  /** Minutes */
+  reading_time?: number;
+  heading_count?: number;
+  outgoing_link_count?: number;
+  /** Only present when a metric range or sort_by uses it */
+  incoming_link_count?: number;
+  attachment_count?: number;
+  open_task_count?: number;
 }
 
 export interface RelationshipData {
@@ -194,6 +218,9 @@ export interface GraphEdge {
   source: string;
   target: string;
   edgeType: string;
+  /** Only present for edgeType 'relation' */
+  relationType?: string;
+  strength?: number;
 }
 
 export interface GraphData {
@@ -201,6 +228,120 @@ export interface GraphData {
   edges: GraphEdge[];
 }
 
+export type GraphExportFormat = 'graphml' | 'gexf' | 'dot';
+
+export interface BrokenLink {
+  /** Path of the note containing the link */
+  source: string;
+  link: string;
+}
+
+export interface UnlinkedMention {
+  source_path: string;
+  source_title: string;
+  target_path: string;
+  matched_text: string;
+  /** Byte offsets in the source file */
+  start: number;
+  end: number;
+  line: number;
+  context: string;
+}
+
+export type MentionSelection = Pick<UnlinkedMention, 'source_path' | 'start' | 'end' | 'matched_text'>;
+
+export type EmbedKind =
+  | 'note' | 'section' | 'image' | 'pdf' | 'video' | 'audio' | 'file'
+  | 'missing' | 'missing_section' | 'cycle' | 'depth_limit';
+
+export interface EmbedInfo {
+  source_path: string;
+  /** Embed text as written, without ![[ ]] */
+  link: string;
+  kind: EmbedKind;
+  target_path: string | null;
+  heading: string | null;
+  depth: number;
+}
+
+export interface TransclusionResult {
+  path: string;
+  /** Body with note embeds inlined; other embeds become <div class="embed-placeholder"> elements */
+  content: string;
+  embeds: EmbedInfo[];
+  dependencies: string[];
+}
+
+export interface PlannedMove {
+  from: string;
+  to: string;
+  is_dir: boolean;
+}
+
+export interface FileDiffPreview {
+  path: string;
+  /** Unified diff of the link rewrites */
+  diff: string;
+}
+
+export interface PathCollision {
+  path: string;
+  /** Free name resolve_collision would pick */
+  resolved_path: string;
+  reason: string;
+}
+
+/** Dry-run result of a rename/move */
+export interface PathChangePreview {
+  final_path: string;
+  moves: PlannedMove[];
+  diffs: FileDiffPreview[];
+  collisions: PathCollision[];
+}
+
+/** Outcome of a rename/move; skipped notes changed after planning and kept their old links */
+export interface PathChangeResult {
+  final_path: string;
+  skipped: string[];
+}
+
+/** Payload of the "embed-dependency-changed" event */
+export interface EmbedDependencyChangedPayload {
+  changed_path: string;
+  dependents: string[];
+}
+
+export interface LocalGraphFilter {
+  include_links?: boolean;
+  include_tags?: boolean;
+  include_folders?: boolean;
+  include_relations?: boolean;
+  note_types?: string[];
+  max_nodes?: number;
+}
+
+export interface LocalGraphNode extends GraphNode {
+  /** Hop distance from the center note (0 = center) */
+  hop: number;
+}
+
+export interface LocalGraphData {
+  nodes: LocalGraphNode[];
+  edges: GraphEdge[];
+  truncated: boolean;
+}
+
+export type RelationDirection = 'incoming' | 'outgoing';
+
+export interface RelatedNote {
+  path: string;
+  title: string;
+  relation_type: string;
+  strength: number | null;
+  depth: number;
+  via: string;
+}
+
 export interface GraphSettings {
   showTags: boolean;
   showAttachments: boolean;
@@ -319,6 +460,34 @@ export interface CalendarMemo {
 
 export type CalendarViewMode = 'task' | 'memo';
 
+export type Period = 'daily' | 'weekly' | 'monthly';
+
+export interface PeriodConfig {
+  /** Vault-relative folder */
+  folder: string;
+  /** e.g. 'YYYY-MM-DD', 'GGGG-[W]WW', 'YYYY-MM' (applied to the period's first day) */
+  name_format: string;
+  /** Vault template name; built-in layout when null */
+  template?: string | null;
+  rollover: boolean;
+}
+
+export interface PeriodicNotesConfig {
+  daily: PeriodConfig;
+  weekly: PeriodConfig;
+  monthly: PeriodConfig;
+}
+
+export interface PeriodicNote {
+  path: string;
+  period: Period;
+  /** First day of the period (YYYY-MM-DD) */
+  start: string;
+  exists: boolean;
+  created: boolean;
+  rolled_over: string[];
+}
+
 // Tag Settings Types
 export interface TagConfig {
   color?: string;
@@ -33,9 +33,162 @@ export interface FacetedTags {
   source?: string[];
   method?: string[];
   status?: string[];
+  /** Legacy list-style tags not yet assigned to a facet */
+  legacy?: string[];
   [key: string]: string[] | undefined;
 }
 
+export type TagFacet = 'domain' | 'who' | 'org' | 'ctx' | 'source' | 'method' | 'status';
+
+export type TaskPriority = 'low' | 'medium' | 'high' | 'urgent';
+
+/** One edit of bulk_update_frontmatter */
+export type FrontmatterEdit =
+  | { op: 'add_tag'; facet: TagFacet; tag: string }
+  | { op: 'remove_tag'; facet: TagFacet; tag: string }
+  | { op: 'set_workflow'; workflow: WorkflowState }
+  | { op: 'set_priority'; priority: TaskPriority | null }
+  | { op: 'set_extra'; key: string; value: unknown };
+
+/** Vault-wide tag refactor; tags are written as "facet/name" */
+export type TagRefactor =
+  | { op: 'rename'; from: string; to: string }
+  | { op: 'merge'; sources: string[]; into: string }
+  | { op: 'move'; tag: string; facet: TagFacet }
+  | { op: 'delete'; tag: string };
+
+export interface BulkEditResult {
+  dry_run: boolean;
+  changed: { path: string; diff: string }[];
+  unchanged: number;
+  failed: { path: string; error: string }[];
+}
+
+/** Rules for migrate_legacy_tags, stored in .notology/tag-mapping.json */
+export interface TagMapping {
+  /** Exact legacy tag -> "facet/name"; null drops the tag */
+  tags: Record<string, string | null>;
+  /** Tag prefix -> facet */
+  prefixes: Record<string, TagFacet>;
+  /** Facet for tags no rule matches; null keeps them under tags.legacy */
+  default_facet: TagFacet | null;
+}
+
+export interface LegacyTagMigration {
+  result: BulkEditResult;
+  unresolved: { tag: string; notes: number }[];
+}
+
+/** Per-note outcome of fix_frontmatter */
+export interface NoteFix {
+  path: string;
+  fixes: string[];
+  /** Validator errors auto-fix could not resolve */
+  remaining: string[];
+}
+
+export interface FrontmatterFixReport {
+  result: BulkEditResult;
+  notes: NoteFix[];
+}
+
+/** Notes sharing one frontmatter id; the first (oldest) keeps it when fixed */
+export interface DuplicateNoteId {
+  id: string;
+  paths: string[];
+}
+
+export interface DuplicateIdFix {
+  result: BulkEditResult;
+  reassigned: { path: string; old_id: string; new_id: string }[];
+}
+
+/** Prompt declared in a vault template's `template:` header */
+export interface TemplatePrompt {
+  name: string;
+  label?: string | null;
+  default?: string | null;
+  /** Allowed answers; empty for free text */
+  options: string[];
+  /** Answer is comma-separated and rendered as a list */
+  multiple: boolean;
+  required: boolean;
+}
+
+/** Template from .notology/templates/; name is the relative path without .md */
+export interface TemplateInfo {
+  name: string;
+  description: string | null;
+  note_type: string | null;
+  prompts: TemplatePrompt[];
+}
+
+export interface RenderedNote {
+  file_name: string;
+  frontmatter: Frontmatter;
+  content: string;
+}
+
+/** Result of convert_note_type; target is a field, "tags.<facet>" or "extra.<field>" */
+export interface TypeConversion {
+  from: NoteType;
+  to: NoteType;
+  moves: { field: string; target: string }[];
+}
+
+/** Condition a note must meet after a transition */
+export interface WorkflowGuard {
+  confidence?: ConfidenceState | null;
+  min_maturity?: number | null;
+  requires_relations?: boolean;
+}
+
+/** from may contain "*" for any state */
+export interface WorkflowTransition {
+  from: (WorkflowState | '*')[];
+  to: WorkflowState;
+  guard?: WorkflowGuard;
+  /** Offered as a suggestion when this also holds */
+  suggest_when?: WorkflowGuard | null;
+}
+
+/** .notology/workflow.json; types replaces transitions for that note type */
+export interface WorkflowConfig {
+  transitions: WorkflowTransition[];
+  types: Partial<Record<NoteType, WorkflowTransition[]>>;
+  verify_when?: WorkflowGuard | null;
+}
+
+export interface StateChange {
+  from: WorkflowState;
+  to: WorkflowState;
+  at: string;
+  host: string;
+}
+
+export type SuggestionAction =
+  | { type: 'add-relation'; relation_type: RelationType; target: string; strength: number }
+  | { type: 'add-tag'; facet: string; tag: string }
+  | { type: 'update-state'; field: string; value: string }
+  | { type: 'add-field'; field: string; value: string };
+
+/** Evidence behind a content-aware suggestion */
+export type SuggestionReason =
+  | { kind: 'shared-terms'; terms: string[]; similarity: number }
+  | { kind: 'shared-links'; notes: string[] }
+  | { kind: 'links-to' }
+  | { kind: 'linked-from' }
+  | { kind: 'shared-tags'; tags: string[] }
+  | { kind: 'tag-cooccurrence'; with_tag: string; together: number; with_count: number };
+
+export interface Suggestion {
+  suggestion_type: 'relation-suggestion' | 'tag-suggestion' | 'state-transition' | 'quality-improvement';
+  confidence: number;
+  description: string;
+  action: SuggestionAction;
+  reasons?: SuggestionReason[];
+}
+
 export interface BaseFrontmatter {
   id: string;
   title: string;
@@ -47,6 +200,7 @@ export interface BaseFrontmatter {
   relations?: Relation[];
   cssclasses?: string[];
   canvas?: boolean;
+  state_history?: StateChange[];
   [key: string]: unknown;
 }
 
@@ -120,6 +274,20 @@ export interface ValidationError {
   message: string;
 }
 
+export interface LintIssue {
+  path: string;
+  line: number;
+  code: 'yaml' | 'unclosed-frontmatter' | 'unknown-type' | 'schema' | 'duplicate-id' | 'missing-relation-target' | 'read';
+  message: string;
+}
+
+/** Result of lint_vault, grouped by severity */
+export interface LintReport {
+  notes_checked: number;
+  errors: LintIssue[];
+  warnings: LintIssue[];
+}
+
 export interface ParsedNote {
   frontmatter: Frontmatter | null;
   body: string;
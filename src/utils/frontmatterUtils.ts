@@ -17,11 +17,11 @@ export async function parseFrontmatter(content: string): Promise<ParsedNote> {
 }
 
 /**
- * Validate frontmatter against schema
+ * Validate frontmatter against schema (and the vault's schemas when vaultPath is given)
  */
-export async function validateFrontmatter(frontmatter: Frontmatter): Promise<ValidationError[]> {
+export async function validateFrontmatter(frontmatter: Frontmatter, vaultPath?: string | null): Promise<ValidationError[]> {
   const frontmatterJson = JSON.stringify(frontmatter);
-  const errors = await frontmatterCommands.validateFrontmatter<ValidationError[]>(frontmatterJson);
+  const errors = await frontmatterCommands.validateFrontmatter<ValidationError[]>(frontmatterJson, vaultPath);
   return errors;
 }
 
@@ -42,11 +42,18 @@ export async function yamlToFrontmatter(yamlStr: string): Promise<Frontmatter> {
   return frontmatter;
 }
 
+let lastIssuedIdTime = 0;
+
 /**
  * Generate a new note ID (14-digit timestamp)
+ * Notes created in the same second get the following seconds, so ids stay unique;
+ * the backend also replaces ids already used in the vault when the note is created.
  */
 export function generateNoteId(): string {
-  const now = new Date();
+  const nowSeconds = Math.floor(Date.now() / 1000) * 1000;
+  const time = Math.max(nowSeconds, lastIssuedIdTime + 1000);
+  lastIssuedIdTime = time;
+  const now = new Date(time);
   const year = now.getFullYear();
   const month = String(now.getMonth() + 1).padStart(2, '0');
   const day = String(now.getDate()).padStart(2, '0');